@@ -0,0 +1,273 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand};
+use glob::Pattern;
+use relynx_lib::client::options::ClientOptions;
+use relynx_lib::config::{load_client_settings_or_default, load_collection_config};
+use relynx_lib::environment::load_environments;
+use relynx_lib::import::load_requests_for_collection;
+use relynx_lib::model::{Collection, Environment, RequestModel};
+use relynx_lib::redaction::Redactor;
+use relynx_lib::report::RunReport;
+use relynx_lib::runner::{run_requests, RequestRun};
+
+/// Runs relynx collections without the desktop app, e.g. within a CI pipeline
+#[derive(Parser, Debug)]
+#[command(name = "relynx-cli", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run all requests of a collection in the order they are shown in the app
+    Run {
+        /// Path to the collection folder
+        collection: PathBuf,
+
+        /// Name of the environment used for replacing variables
+        #[arg(long)]
+        env: Option<String>,
+
+        /// Only run requests whose file path relative to the collection matches the glob,
+        /// e.g. 'users/**/*.http'
+        #[arg(long)]
+        filter: Option<String>,
+
+        /// Do not verify the certificates of the servers
+        #[arg(long)]
+        insecure: bool,
+
+        #[command(flatten)]
+        reports: ReportPaths,
+    },
+}
+
+#[derive(Args, Debug)]
+struct ReportPaths {
+    /// Write a JUnit XML report to the given file
+    #[arg(long)]
+    report_junit: Option<PathBuf>,
+
+    /// Write a TAP report to the given file
+    #[arg(long)]
+    report_tap: Option<PathBuf>,
+
+    /// Write a json report including all requests, responses and timings to the given file
+    #[arg(long)]
+    report_json: Option<PathBuf>,
+}
+
+/// Exit code if the collection could be run but a request or one of its tests failed
+const EXIT_FAILED_REQUESTS: u8 = 1;
+/// Exit code if the collection, environment or filter could not be loaded
+const EXIT_INVALID_INPUT: u8 = 2;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run {
+            collection,
+            env,
+            filter,
+            insecure,
+            reports,
+        } => match run_collection(&collection, env, filter, insecure, &reports) {
+            Ok(true) => ExitCode::SUCCESS,
+            Ok(false) => ExitCode::from(EXIT_FAILED_REQUESTS),
+            Err(msg) => {
+                eprintln!("Error: {}", msg);
+                ExitCode::from(EXIT_INVALID_INPUT)
+            }
+        },
+    }
+}
+
+/// Returns if all requests and tests of the collection have succeeded
+fn run_collection(
+    collection_path: &Path,
+    env_name: Option<String>,
+    filter: Option<String>,
+    insecure: bool,
+    reports: &ReportPaths,
+) -> Result<bool, String> {
+    let collection = load_collection(collection_path)?;
+    let filter = filter
+        .map(|filter| Pattern::new(&filter))
+        .transpose()
+        .map_err(|err| format!("Invalid filter: {}", err))?;
+
+    let load_result = load_requests_for_collection(&collection).map_err(|err| err.to_string())?;
+    for parse_err in load_result.errs.iter() {
+        eprintln!("Could not parse request file: {:?}", parse_err);
+    }
+
+    let collection_config =
+        load_collection_config(&collection.get_config_file_path()).unwrap_or_default();
+    let requests: Vec<RequestModel> = load_result
+        .request_tree
+        .root
+        .requests()
+        .into_iter()
+        .filter(|request| matches_filter(request, &collection.path, filter.as_ref()))
+        .map(|request| RequestModel {
+            auth: collection_config.resolve_auth(request, &collection.path),
+            ..request.clone()
+        })
+        .collect();
+
+    let mut environment = match env_name {
+        Some(env_name) => Some(find_environment(&collection.path, &env_name)?),
+        None => None,
+    };
+
+    let (client_settings, client_settings_warning) =
+        load_client_settings_or_default(&collection.path);
+    if let Some(warning) = client_settings_warning {
+        eprintln!("Warning: {}", warning);
+    }
+    let mut options = ClientOptions {
+        collection_path: Some(collection.path.clone()),
+        tls_profiles: client_settings.tls_profiles,
+        ..Default::default()
+    };
+    client_settings.defaults.apply_to(&mut options);
+    if insecure {
+        options.insecure = true;
+    }
+
+    println!(
+        "Running {} requests of '{}'",
+        requests.len(),
+        collection.name
+    );
+    // the redactor is shared by all requests so acquired tokens are redacted within the reports
+    let redactor = Redactor::new(environment.as_ref());
+    let runs = run_requests(&requests, &options, environment.as_mut(), &redactor);
+    for run in runs.iter() {
+        print_run(run, &redactor);
+    }
+
+    write_reports(&RunReport::new(&collection.name, &runs, &redactor), reports)?;
+
+    let failed = runs.iter().filter(|run| !run.is_success()).count();
+    println!(
+        "\n{} requests, {} passed, {} failed",
+        runs.len(),
+        runs.len() - failed,
+        failed
+    );
+    Ok(failed == 0 && load_result.errs.is_empty())
+}
+
+fn write_reports(report: &RunReport, reports: &ReportPaths) -> Result<(), String> {
+    let mut outputs: Vec<(&PathBuf, String)> = Vec::new();
+    if let Some(ref path) = reports.report_junit {
+        outputs.push((path, report.to_junit_xml()));
+    }
+    if let Some(ref path) = reports.report_tap {
+        outputs.push((path, report.to_tap()));
+    }
+    if let Some(ref path) = reports.report_json {
+        outputs.push((path, report.to_json().map_err(|err| err.to_string())?));
+    }
+    for (path, content) in outputs {
+        std::fs::write(path, content)
+            .map_err(|err| format!("Could not write report to '{}': {}", path.display(), err))?;
+    }
+    Ok(())
+}
+
+fn load_collection(collection_path: &Path) -> Result<Collection, String> {
+    if !collection_path.is_dir() {
+        return Err(format!(
+            "The collection folder does not exist: '{}'",
+            collection_path.display()
+        ));
+    }
+    let path = collection_path
+        .canonicalize()
+        .map_err(|err| err.to_string())?;
+    let mut collection = Collection {
+        name: String::new(),
+        path,
+        current_env_name: String::new(),
+        description: String::new(),
+        import_warnings: vec![],
+        path_exists: true,
+    };
+    collection.name = load_collection_config(&collection.get_config_file_path())
+        .map(|config| config.name)
+        .unwrap_or_else(|_| {
+            collection
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default()
+        });
+    Ok(collection)
+}
+
+fn find_environment(collection_path: &Path, env_name: &str) -> Result<Environment, String> {
+    let environments =
+        load_environments(collection_path.to_path_buf()).map_err(|err| err.to_string())?;
+    let names: Vec<String> = environments.iter().map(|env| env.name.clone()).collect();
+    environments
+        .into_iter()
+        .find(|env| env.name == env_name)
+        .ok_or_else(|| {
+            format!(
+                "Environment '{}' not found, available environments: {}",
+                env_name,
+                names.join(", ")
+            )
+        })
+}
+
+fn matches_filter(
+    request: &RequestModel,
+    collection_path: &Path,
+    filter: Option<&Pattern>,
+) -> bool {
+    let filter = match filter {
+        Some(filter) => filter,
+        None => return true,
+    };
+    let request_path = PathBuf::from(&request.rest_file_path);
+    let relative_path = request_path
+        .strip_prefix(collection_path)
+        .unwrap_or(&request_path);
+    filter.matches_path(relative_path)
+}
+
+fn print_run(run: &RequestRun, redactor: &Redactor) {
+    let status = if run.is_success() { "PASS" } else { "FAIL" };
+    let details = match (run.error.as_ref(), run.calls.last()) {
+        (Some(err), _) => redactor.redact(err),
+        (None, Some(call)) => format!(
+            "{}, {} ms",
+            call.response.status,
+            call.response.duration.as_millis()
+        ),
+        (None, None) => String::new(),
+    };
+    println!(
+        "{} {} {} ({})",
+        status,
+        run.request.method.to_string(),
+        run.request.name,
+        details
+    );
+    for test in run.tests.iter() {
+        let mark = if test.passed { "ok" } else { "failed" };
+        match test.message {
+            Some(ref message) => println!("    {} - {}: {}", mark, test.name, message),
+            None => println!("    {} - {}", mark, test.name),
+        }
+    }
+    for warning in run.warnings.iter() {
+        println!("    warning: {}", redactor.redact(warning));
+    }
+}
@@ -21,23 +21,36 @@
 *
 */
 
+mod auth;
 mod certificate;
 pub mod client_model;
 mod easy_ext;
 pub mod error;
+mod http_version;
+pub mod oauth;
 pub mod options;
 mod request;
+mod response_decoding;
 mod timings;
+mod tls;
 
+use self::auth::{auth_headers, url_with_auth, SigningRequest};
 use self::certificate::Certificate;
 use self::client_model::{parse_cookies, Call, RequestCookie, Response};
 use self::error::HttpError;
+use self::http_version::{curl_http_version, downgrade_warning, format_http_version};
+use self::oauth::add_auth_tokens;
 use self::options::{ClientOptions, Verbosity};
 use self::timings::Timings;
+use self::tls::{
+    certificate_expiry_warnings, find_tls_profile, parse_tls_session, pinned_public_keys,
+};
+use crate::environment::{replace_variables_in_bytes, replace_variables_in_str};
 use crate::model::{
-    DataSource, Environment, GetHeadersOption, Header, Multipart, RequestBody, RequestModel,
-    RunLogger,
+    CertificateFormat, CertificateInfo, DataSource, Environment, GetHeadersOption, Header,
+    Multipart, RequestAuth, RequestBody, RequestModel, RunLogger, TlsInfo, TlsProfile,
 };
+use crate::scripting::run_pre_request_script;
 use base64::engine::general_purpose;
 use base64::Engine;
 use chrono::Utc;
@@ -45,6 +58,7 @@ use curl::easy::{self, List, SslOpt};
 use encoding::all::ISO_8859_1;
 use encoding::{DecoderTrap, Encoding};
 use http_rest_file::model::{HttpMethod, HttpVersion, UrlEncodedParam};
+use std::collections::HashMap;
 use std::io::Read;
 use std::path::PathBuf;
 
@@ -56,6 +70,17 @@ use std::path::PathBuf;
 pub struct Client {
     /// The handle to libcurl binding
     handle: Box<easy::Easy>,
+    /// Placeholders of the last executed request that could not be resolved and have been sent
+    /// as they are
+    unresolved_variables: Vec<String>,
+    /// Problems of the last executed request that did not prevent it from being sent, such as a
+    /// downgraded http version
+    warnings: Vec<String>,
+    /// Globals set by the pre request script of the last executed request, they have to be
+    /// written back into the environment by the caller like the ones of the response handler
+    changed_globals: HashMap<String, String>,
+    /// Globals cleared by the pre request script of the last executed request
+    removed_globals: Vec<String>,
 }
 
 // @TODO: use our own Response struct?
@@ -75,6 +100,40 @@ impl Response {
                 .collect(),
         }
     }
+
+    /// Returns the http version of the response as within the status line, such as `HTTP/2`
+    pub fn http_version(&self) -> String {
+        format_http_version(&self.version)
+    }
+
+    /// Returns the negotiated tls parameters and the certificates of the server, if the
+    /// response has been received over tls
+    pub fn tls_info(&self) -> Option<TlsInfo> {
+        if self.tls_session.is_none() && self.certificate_chain.is_empty() {
+            return None;
+        }
+        Some(TlsInfo {
+            version: self
+                .tls_session
+                .as_ref()
+                .map(|session| session.version.clone()),
+            cipher: self
+                .tls_session
+                .as_ref()
+                .map(|session| session.cipher.clone()),
+            certificates: self
+                .certificate_chain
+                .iter()
+                .map(CertificateInfo::from)
+                .collect(),
+        })
+    }
+
+    /// Returns a warning for every certificate of the server that has expired or expires within
+    /// the given number of days
+    pub fn certificate_expiry_warnings(&self, days: u32) -> Vec<String> {
+        certificate_expiry_warnings(&self.certificate_chain, days, Utc::now())
+    }
 }
 
 impl RequestModel {
@@ -102,6 +161,10 @@ impl Client {
 
         Client {
             handle: Box::new(h),
+            unresolved_variables: vec![],
+            warnings: vec![],
+            changed_globals: HashMap::new(),
+            removed_globals: vec![],
         }
     }
 
@@ -112,6 +175,26 @@ impl Client {
         environment: Option<&Environment>,
         logger: &RunLogger,
     ) -> Result<Vec<Call>, HttpError> {
+        self.warnings = vec![];
+        self.changed_globals = HashMap::new();
+        self.removed_globals = vec![];
+        let script_environment = self.run_pre_request_script(request_model, environment, logger)?;
+        let environment = script_environment.as_ref().or(environment);
+
+        // tokens are acquired or refreshed before the request so it is sent with a valid token
+        let auth_environment = add_auth_tokens(
+            &request_model.get_unresolved_variables(environment),
+            environment,
+            options,
+            logger,
+        )?;
+        let environment = auth_environment.as_ref().or(environment);
+
+        self.unresolved_variables = request_model.get_unresolved_variables(environment);
+        for name in self.unresolved_variables.iter() {
+            logger.log_info(format!("Variable '{}' could not be resolved", name));
+        }
+
         let calls = if options.follow_location {
             logger.log_info("Run request: follow location");
             self.execute_with_redirect(request_model, options, environment, logger)
@@ -124,6 +207,63 @@ impl Client {
         Ok(calls)
     }
 
+    /// Returns the placeholders of the last executed request that could not be resolved
+    pub fn unresolved_variables(&self) -> &[String] {
+        &self.unresolved_variables
+    }
+
+    /// Returns the warnings of the last executed request
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Returns the globals set by the pre request script of the last executed request
+    pub fn changed_globals(&self) -> &HashMap<String, String> {
+        &self.changed_globals
+    }
+
+    /// Returns the globals cleared by the pre request script of the last executed request
+    pub fn removed_globals(&self) -> &[String] {
+        &self.removed_globals
+    }
+
+    /// Runs the pre request script of the request if there is one. Variables set by the script
+    /// are only valid for this request and are returned within a copy of the environment which
+    /// is then used for replacing the variables within the request. Changed globals are kept
+    /// as well, see [`Client::changed_globals`].
+    fn run_pre_request_script(
+        &mut self,
+        request_model: &RequestModel,
+        environment: Option<&Environment>,
+        logger: &RunLogger,
+    ) -> Result<Option<Environment>, HttpError> {
+        let script = match request_model.pre_request_script {
+            Some(ref script) => script,
+            None => return Ok(None),
+        };
+        logger.log_info("Run pre request script");
+        let result = run_pre_request_script(script, request_model, environment).map_err(|err| {
+            logger.log_error(format!("Pre request script failed: {}", err));
+            HttpError::PreRequestScript(err.to_string())
+        })?;
+        for msg in result.logs.iter() {
+            logger.log_info(format!("Pre request script: {}", msg));
+        }
+
+        let mut script_environment = environment
+            .cloned()
+            .unwrap_or_else(|| Environment::new(String::new()));
+        script_environment
+            .apply_script_globals(result.changed_globals.clone(), &result.removed_globals);
+        // request variables take precedence over global ones
+        for (name, value) in result.variables {
+            script_environment.set_variable(&name, value);
+        }
+        self.changed_globals = result.changed_globals;
+        self.removed_globals = result.removed_globals;
+        Ok(Some(script_environment))
+    }
+
     // Executes an HTTP request `request_spec`, optionally follows redirection and returns a
     // list of pair of [`Request`], [`Response`].
     fn execute_with_redirect(
@@ -160,7 +300,10 @@ impl Client {
                     return Err(HttpError::TooManyRedirect);
                 }
             }
-            request_model = request_model.clone();
+            // credentials are only sent to the origin of the request, as curl does it
+            if !is_same_origin(&calls[calls.len() - 1].request.url, &redirect_url) {
+                request_model.auth = None;
+            }
             request_model.url = redirect_url;
         }
         Ok(calls)
@@ -174,6 +317,21 @@ impl Client {
         options: &ClientOptions,
         environment: Option<&Environment>,
         logger: &RunLogger,
+    ) -> Result<Call, HttpError> {
+        let result = self.perform_without_redirect(request_model, options, environment, logger);
+        // The handle is reset on every outcome, so that credentials and certificates of this
+        // request are neither kept nor reused by the next one.
+        self.handle.reset();
+        result
+    }
+
+    /// Sets the handle attributes of the request and performs it, the handle must be reset afterwards.
+    fn perform_without_redirect(
+        &mut self,
+        request_model: &RequestModel,
+        options: &ClientOptions,
+        environment: Option<&Environment>,
+        logger: &RunLogger,
     ) -> Result<Call, HttpError> {
         // Set handle attributes that have not been set or reset.
 
@@ -185,50 +343,41 @@ impl Client {
         // Activates the access of certificates info chain after a transfer has been executed.
         self.handle.certinfo(true).unwrap();
 
-        if !options.connects_to.is_empty() {
-            let connects = to_list(&options.connects_to);
-            self.handle.connect_to(connects).unwrap();
-        }
-        if !options.resolves.is_empty() {
-            let resolves = to_list(&options.resolves);
-            self.handle.resolve(resolves).unwrap();
-        }
-        self.handle.ssl_verify_host(!options.insecure).unwrap();
-        self.handle.ssl_verify_peer(!options.insecure).unwrap();
-        if let Some(cacert_file) = options.cacert_file.clone() {
-            self.handle.cainfo(cacert_file).unwrap();
-            self.handle.ssl_cert_type("PEM").unwrap();
-        }
-        if let Some(client_cert_file) = options.client_cert_file.clone() {
-            self.handle.ssl_cert(client_cert_file).unwrap();
-            self.handle.ssl_cert_type("PEM").unwrap();
-        }
-        if let Some(client_key_file) = options.client_key_file.clone() {
-            self.handle.ssl_key(client_key_file).unwrap();
-            self.handle.ssl_cert_type("PEM").unwrap();
-        }
-        if let Some(proxy) = options.proxy.clone() {
-            self.handle.proxy(proxy.as_str()).unwrap();
-        }
-        if let Some(s) = options.no_proxy.clone() {
-            self.handle.noproxy(s.as_str()).unwrap();
-        }
-        self.handle.timeout(options.timeout).unwrap();
-        self.handle
-            .connect_timeout(options.connect_timeout)
-            .unwrap();
-
-        self.set_ssl_options(options.ssl_no_revoke);
+        set_connection_options(&mut self.handle, options, environment)?;
 
+        let auth = request_model
+            .auth
+            .as_ref()
+            .map(|auth| auth.with_env(environment));
         let url = request_model.get_url_with_env(true, environment);
+        let url = match auth {
+            Some(ref auth) => url_with_auth(&url, auth),
+            None => url,
+        };
         logger.log_debug(format!("=>Url: {}", url).as_str());
 
         self.handle.url(url.as_str()).unwrap();
+        if let Some(profile) = find_tls_profile(&options.tls_profiles, &url) {
+            logger.log_debug(format!("=>Tls profile: {}", profile.host).as_str());
+            set_tls_profile(&mut self.handle, profile, options, environment)?;
+        }
+        // the version is only set if the request line contains one, otherwise curl decides
+        if !request_model.http_version.is_replaced {
+            self.set_http_version(&request_model.http_version.value, &url, logger);
+        }
         let method = &request_model.method;
         self.set_method(method);
         self.set_cookies(&request_model.cookies());
+        if let Some(RequestAuth::Digest {
+            ref username,
+            ref password,
+        }) = auth
+        {
+            self.set_digest_auth(username, password);
+        }
+        let mut url_encoded_body: Option<String> = None;
         if let RequestBody::UrlEncoded { .. } = request_model.body {
-            self.set_form_url_encoded(
+            url_encoded_body = self.set_form_url_encoded(
                 &request_model
                     .get_url_encoded_params_with_env(environment)
                     .unwrap(),
@@ -239,13 +388,13 @@ impl Client {
             ref parts,
         } = request_model.body
         {
-            self.set_multipart(request_model, boundary, parts, logger)?;
+            self.set_multipart(request_model, boundary, parts, environment, logger)?;
         }
 
         let request_body_bytes: Option<Vec<u8>> = match request_model.body {
             RequestBody::None => None,
-            RequestBody::Raw { ref data } => match data {
-                DataSource::Raw(ref raw_data) => Some(raw_data.as_bytes().to_vec()),
+            RequestBody::Raw { ref data } => match data.with_env(environment) {
+                DataSource::Raw(raw_data) => Some(raw_data.into_bytes()),
                 ref filepath => {
                     let filepath = filepath
                         .get_abs_path_relative_to(&request_model)
@@ -258,7 +407,7 @@ impl Client {
                         log::error!("Io Error: {:?}", err);
                         HttpError::CouldNotReadBodyFile(filepath)
                     })?;
-                    Some(content)
+                    Some(replace_variables_in_bytes(content, environment))
                 }
             },
             RequestBody::Multipart { .. } => None, // Nothing done here, handled separately
@@ -269,7 +418,31 @@ impl Client {
             self.set_body(bytes);
         }
         //let mut request_body_bytes: &[u8] = &request_model.body.bytes();
-        self.set_headers(request_model, options, environment);
+        let request_auth_headers = match auth {
+            Some(ref auth) => {
+                let payload: Option<&[u8]> = match request_model.body {
+                    RequestBody::None => Some(b"".as_slice()),
+                    RequestBody::Raw { .. } => request_body_bytes.as_deref(),
+                    RequestBody::UrlEncoded { .. } => Some(
+                        url_encoded_body
+                            .as_ref()
+                            .map(|body| body.as_bytes())
+                            .unwrap_or_default(),
+                    ),
+                    // the multipart body is built by curl and cannot be signed
+                    RequestBody::Multipart { .. } => None,
+                };
+                let method = method.to_string();
+                let signing_request = SigningRequest {
+                    method: &method,
+                    url: &url,
+                    payload,
+                };
+                auth_headers(auth, &signing_request, Utc::now())
+            }
+            None => vec![],
+        };
+        self.set_headers(request_model, options, environment, &request_auth_headers);
 
         let start = Utc::now();
         let verbose = options.verbosity.is_some();
@@ -287,6 +460,7 @@ impl Client {
         // of key-value.
         let mut request_body = Vec::<u8>::new();
         let mut response_body = Vec::<u8>::new();
+        let mut tls_session = None;
 
         if *method == HttpMethod::HEAD {
             self.handle.nobody(true).unwrap();
@@ -315,6 +489,11 @@ impl Client {
                         // Extracts request headers from libcurl debug info.
                         lines.pop().unwrap(); // Remove last empty line.
                         lines.remove(0); // Remove method/path/version line.
+
+                        // curl sends the request again for a digest challenge, only the headers
+                        // and the body of the last request are kept
+                        request_headers.clear();
+                        request_body.clear();
                         for line in lines {
                             if let Some(header) = Header::parse(&line) {
                                 request_headers.push(header);
@@ -366,10 +545,14 @@ impl Client {
                     // Curl debug logs
                     easy::InfoType::Text => {
                         let len = data.len();
-                        if very_verbose && len > 0 {
-                            let text = std::str::from_utf8(&data[..len - 1]);
-                            if let Ok(text) = text {
-                                logger.log_debug(format!("Curl text: {}", text).as_str());
+                        if len > 0 {
+                            if let Ok(text) = std::str::from_utf8(&data[..len - 1]) {
+                                if let Some(session) = parse_tls_session(text) {
+                                    tls_session = Some(session);
+                                }
+                                if very_verbose {
+                                    logger.log_debug(format!("Curl text: {}", text).as_str());
+                                }
                             }
                         }
                     }
@@ -416,25 +599,28 @@ impl Client {
             None => return Err(HttpError::StatuslineIsMissing { url }),
             Some(status_line) => self.parse_response_version(status_line)?,
         };
+        if !request_model.http_version.is_replaced {
+            if let Some(warning) = downgrade_warning(&request_model.http_version.value, &version) {
+                logger.log_info(&warning);
+                self.add_warning(warning);
+            }
+        }
         let headers = self.parse_response_headers(&response_headers);
         let _length = response_body.len();
-        let certificate = if let Some(cert_info) = easy_ext::get_certinfo(&self.handle)? {
-            match Certificate::try_from(cert_info) {
+        let certificate_chain = easy_ext::get_certinfo_chain(&self.handle)?
+            .into_iter()
+            .filter_map(|cert_info| match Certificate::try_from(cert_info) {
                 Ok(value) => Some(value),
                 Err(message) => {
                     logger.log_error(format!("can not parse certificate - {message}").as_str());
                     None
                 }
-            }
-        } else {
-            None
-        };
+            })
+            .collect();
         let stop = Utc::now();
         let duration = (stop - start).to_std().unwrap();
         let timings = Timings::new(&mut self.handle, start, stop);
 
-        self.handle.reset();
-
         let request = self::request::Request {
             url: url.clone(),
             method: method.to_string(),
@@ -447,15 +633,22 @@ impl Client {
                 .collect(),
             body: request_body,
         };
-        let response = Response {
+        let raw_body_size = response_body.len();
+        let mut response = Response {
             version,
             status,
             headers,
             body: response_body,
+            raw_body_size,
             duration,
             url,
-            certificate,
+            certificate_chain,
+            tls_session,
         };
+        if let Err(err) = response.decompress() {
+            logger.log_error(format!("Could not decompress response: {:?}", err).as_str());
+            self.add_warning(format!("{}. The response is shown as received.", err));
+        }
 
         if verbose {
             // FIXME: the cast to u64 seems not necessary.
@@ -497,12 +690,14 @@ impl Client {
             .unwrap()
     }
 
-    /// Sets HTTP headers.
+    /// Sets HTTP headers. Headers of the request auth are only added if the request does not
+    /// define a header with the same name itself.
     fn set_headers(
         &mut self,
         request: &RequestModel,
         options: &ClientOptions,
         env: Option<&Environment>,
+        auth_headers: &[Header],
     ) {
         let mut list = easy::List::new();
 
@@ -511,6 +706,16 @@ impl Client {
                 .unwrap();
         }
 
+        for header in auth_headers {
+            if request
+                .get_header_values(&header.key, GetHeadersOption::JustValues)
+                .is_empty()
+            {
+                list.append(format!("{}: {}", header.key, header.value).as_str())
+                    .unwrap();
+            }
+        }
+
         // @TODO: we do not store the content type somewhere else, only the header is present
         // @TODO: maybe create a helper function for that
         // if !request.has_header("Content-Type") {
@@ -564,7 +769,8 @@ impl Client {
                 .get_header_values("Accept-Encoding", GetHeadersOption::JustValues)
                 .is_empty()
         {
-            list.append("Accept-Encoding: gzip, deflate, br").unwrap();
+            list.append("Accept-Encoding: gzip, deflate, br, zstd")
+                .unwrap();
         }
 
         self.handle.http_headers(list).unwrap();
@@ -583,20 +789,60 @@ impl Client {
     }
 
     /// Sets form params.
-    fn set_form_url_encoded(&mut self, params: &[UrlEncodedParam]) {
+    /// Returns the encoded body if one has been set.
+    fn set_form_url_encoded(&mut self, params: &[UrlEncodedParam]) -> Option<String> {
         if !params.is_empty() {
             let s = self.url_encode_params(params);
             self.handle.post_fields_copy(s.as_str().as_bytes()).unwrap();
             //self.handle.write_function(sink);
+            return Some(s);
+        }
+        None
+    }
+
+    /// Sets the http version the request is sent with. HTTP/3 falls back to HTTP/2 if curl has
+    /// been built without HTTP/3 support.
+    fn set_http_version(&mut self, version: &HttpVersion, url: &str, logger: &RunLogger) {
+        let mut curl_version = curl_http_version(version, url);
+        if matches!(curl_version, easy::HttpVersion::V3) && !curl::Version::get().feature_http3() {
+            self.add_warning(
+                "HTTP/3 is not supported by the http client, the request has been sent with HTTP/2."
+                    .to_string(),
+            );
+            curl_version = curl_http_version(&HttpVersion { major: 2, minor: 0 }, url);
+        }
+        if let Err(err) = self.handle.http_version(curl_version) {
+            logger.log_error(format!("Could not set http version: {:?}", err).as_str());
+            self.add_warning(format!(
+                "The http version {}.{} is not supported by the http client, the request has been sent with the default version.",
+                version.major, version.minor
+            ));
+        }
+    }
+
+    /// Adds a warning once, hops of a redirected request may run into the same problem
+    fn add_warning(&mut self, warning: String) {
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning);
         }
     }
 
+    /// Lets curl answer the digest challenge of the server.
+    fn set_digest_auth(&mut self, username: &str, password: &str) {
+        let mut auth = easy::Auth::new();
+        auth.digest(true);
+        self.handle.http_auth(&auth).unwrap();
+        self.handle.username(username).unwrap();
+        self.handle.password(password).unwrap();
+    }
+
     /// Sets multipart form data.
     fn set_multipart(
         &mut self,
         request: &RequestModel,
         _boundary: &str,
         parts: &[Multipart],
+        environment: Option<&Environment>,
         logger: &RunLogger,
     ) -> Result<(), HttpError> {
         let mut form = easy::Form::new();
@@ -605,30 +851,37 @@ impl Client {
                 logger.log_error("Disposition part has no name, skipping multipart part.");
                 continue;
             }
-            let contents = match part.data {
-                DataSource::Raw(ref raw_data) => raw_data.as_bytes().to_vec(),
+            let contents = match part.data.with_env(environment) {
+                DataSource::Raw(raw_data) => raw_data.into_bytes(),
                 ref filepath => {
                     let path = filepath
                         .get_abs_path_relative_to(&request)
                         .expect("should be present");
-                    std::fs::read(&path).map_err(|err| {
+                    let content = std::fs::read(&path).map_err(|err| {
                         logger.log_error(format!(
                             "Could not read of file: '{}'. Check if the file exists!",
                             path.display()
                         ));
                         logger.log_error(format!("Error: {:?}", err));
                         HttpError::CouldNotReadBodyPartFromFile(PathBuf::from(path))
-                    })?
+                    })?;
+                    replace_variables_in_bytes(content, environment)
                 }
             };
             // @TODO error log
             let mut curl_part = form.part(&part.disposition.name);
-            // @TODO: filename
-            let filename = part.disposition.filename.as_ref();
-            if filename.is_none() || filename.unwrap().is_empty() {
-                curl_part.contents(&contents);
-            } else {
-                curl_part.buffer(filename.unwrap(), contents);
+            let filename = part
+                .disposition
+                .filename
+                .as_ref()
+                .map(|filename| replace_variables_in_str(filename, environment));
+            match filename {
+                Some(ref filename) if !filename.is_empty() => {
+                    curl_part.buffer(filename, contents);
+                }
+                _ => {
+                    curl_part.contents(&contents);
+                }
             }
             // @TODO: set multipart boundary yourself!
 
@@ -665,13 +918,6 @@ impl Client {
         }
     }
 
-    /// Sets SSL options
-    fn set_ssl_options(&mut self, no_revoke: bool) {
-        let mut ssl_opt = SslOpt::new();
-        ssl_opt.no_revoke(no_revoke);
-        self.handle.ssl_options(&ssl_opt).unwrap();
-    }
-
     /// URL encodes parameters.
     fn url_encode_params(&mut self, params: &[UrlEncodedParam]) -> String {
         params
@@ -740,6 +986,14 @@ fn get_redirect_url(location: &str, base_url: &str) -> String {
     }
 }
 
+/// Whether both urls have the same scheme, host and port
+fn is_same_origin(url: &str, other_url: &str) -> bool {
+    match (url::Url::parse(url), url::Url::parse(other_url)) {
+        (Ok(url), Ok(other_url)) => url.origin() == other_url.origin(),
+        _ => false,
+    }
+}
+
 /// Splits an array of bytes into HTTP lines (\r\n separator).
 fn split_lines(data: &[u8]) -> Vec<String> {
     let mut lines = vec![];
@@ -776,6 +1030,14 @@ pub fn decode_header(data: &[u8], logger: &RunLogger) -> Option<String> {
     }
 }
 
+/// Returns the name of a certificate format as used by libcurl
+fn curl_cert_type(format: CertificateFormat) -> &'static str {
+    match format {
+        CertificateFormat::Pem => "PEM",
+        CertificateFormat::Pkcs12 => "P12",
+    }
+}
+
 /// Converts a list of [`String`] to a libcurl's list of strings.
 fn to_list(items: &[String]) -> List {
     let mut list = List::new();
@@ -783,6 +1045,95 @@ fn to_list(items: &[String]) -> List {
     list
 }
 
+/// Sets the name resolution, certificates, proxy and timeouts of the options, they apply to the
+/// requests as well as to the token requests of OAuth 2.0.
+fn set_connection_options(
+    handle: &mut easy::Easy,
+    options: &ClientOptions,
+    environment: Option<&Environment>,
+) -> Result<(), HttpError> {
+    if !options.connects_to.is_empty() {
+        handle.connect_to(to_list(&options.connects_to))?;
+    }
+    if !options.resolves.is_empty() {
+        handle.resolve(to_list(&options.resolves))?;
+    }
+    handle.ssl_verify_host(!options.insecure)?;
+    handle.ssl_verify_peer(!options.insecure)?;
+    if let Some(ref cacert_file) = options.cacert_file {
+        handle.cainfo(cacert_file)?;
+        handle.ssl_cert_type("PEM")?;
+    }
+    if let Some(ref client_cert_file) = options.client_cert_file {
+        handle.ssl_cert(client_cert_file)?;
+        handle.ssl_cert_type(curl_cert_type(options.client_cert_format))?;
+    }
+    if let Some(ref client_key_file) = options.client_key_file {
+        handle.ssl_key(client_key_file)?;
+        handle.ssl_key_type("PEM")?;
+    }
+    if let Some(ref client_key_password) = options.client_key_password {
+        handle.key_password(&replace_variables_in_str(client_key_password, environment))?;
+    }
+    if let Some(ref proxy) = options.proxy {
+        handle.proxy(proxy)?;
+    }
+    if let Some(ref no_proxy) = options.no_proxy {
+        handle.noproxy(no_proxy)?;
+    }
+    handle.timeout(options.timeout)?;
+    handle.connect_timeout(options.connect_timeout)?;
+
+    let mut ssl_opt = SslOpt::new();
+    ssl_opt.no_revoke(options.ssl_no_revoke);
+    handle.ssl_options(&ssl_opt)?;
+    Ok(())
+}
+
+/// Sets the certificates of a tls profile, they take precedence over the ones of the options.
+fn set_tls_profile(
+    handle: &mut easy::Easy,
+    profile: &TlsProfile,
+    options: &ClientOptions,
+    environment: Option<&Environment>,
+) -> Result<(), HttpError> {
+    if let Some(ref client_certificate) = profile.client_certificate {
+        handle.ssl_cert(client_certificate)?;
+        handle.ssl_cert_type(curl_cert_type(profile.client_certificate_format))?;
+    }
+    if let Some(ref client_key) = profile.client_key {
+        handle.ssl_key(client_key)?;
+        handle.ssl_key_type("PEM")?;
+    }
+    if let Some(ref passphrase) = profile.passphrase {
+        handle.key_password(&replace_variables_in_str(passphrase, environment))?;
+    }
+    if !profile.ca_bundles.is_empty() {
+        // curl accepts only one ca file, so all bundles are passed as one blob which replaces
+        // the ca file of the options
+        let mut bundle: Vec<u8> = Vec::new();
+        let ca_files = options
+            .cacert_file
+            .iter()
+            .map(PathBuf::from)
+            .chain(profile.ca_bundles.iter().cloned());
+        for ca_file in ca_files {
+            let content = std::fs::read(&ca_file).map_err(|err| {
+                log::error!("Could not read ca bundle: '{}'", ca_file.display());
+                log::error!("Io Error: {:?}", err);
+                HttpError::CouldNotReadCertificateFile(ca_file.clone())
+            })?;
+            bundle.extend(content);
+            bundle.push(b'\n');
+        }
+        handle.ssl_cainfo_blob(&bundle)?;
+    }
+    if let Some(pinned_public_keys) = pinned_public_keys(profile) {
+        handle.pinned_public_key(&pinned_public_keys)?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -821,4 +1172,24 @@ mod tests {
             "http://localhost:8000/redirected".to_string()
         );
     }
+
+    #[test]
+    fn test_is_same_origin() {
+        assert!(is_same_origin(
+            "https://example.com/login?key=abc",
+            "https://example.com:443/home"
+        ));
+        assert!(!is_same_origin(
+            "https://example.com/login",
+            "http://example.com/home"
+        ));
+        assert!(!is_same_origin(
+            "https://example.com/login",
+            "https://example.com:8443/home"
+        ));
+        assert!(!is_same_origin(
+            "https://example.com/login",
+            "https://other.com/home"
+        ));
+    }
 }
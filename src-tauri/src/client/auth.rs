@@ -0,0 +1,302 @@
+use base64::engine::general_purpose;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use rsa::sha2::{Digest, Sha256};
+
+use crate::model::{ApiKeyLocation, Header, RequestAuth};
+
+const AWS_ALGORITHM: &str = "AWS4-HMAC-SHA256";
+/// Payload hash used for bodies that are streamed by curl, such as multipart forms
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+/// The request a signature is calculated for
+pub struct SigningRequest<'a> {
+    pub method: &'a str,
+    pub url: &'a str,
+    /// `None` if the body is not known in advance
+    pub payload: Option<&'a [u8]>,
+}
+
+/// Adds the api key of an auth to the query of the url
+pub fn url_with_auth(url: &str, auth: &RequestAuth) -> String {
+    match auth {
+        RequestAuth::ApiKey {
+            name,
+            value,
+            location: ApiKeyLocation::Query,
+        } => match url::Url::parse(url) {
+            Ok(mut url) => {
+                url.query_pairs_mut().append_pair(name, value);
+                url.to_string()
+            }
+            Err(_) => url.to_string(),
+        },
+        _ => url.to_string(),
+    }
+}
+
+/// Returns the headers an auth adds to a request. Digest auth is not handled here as it needs a
+/// challenge of the server and is done by curl.
+pub fn auth_headers(
+    auth: &RequestAuth,
+    request: &SigningRequest,
+    now: DateTime<Utc>,
+) -> Vec<Header> {
+    match auth {
+        RequestAuth::Basic { username, password } => {
+            let credentials =
+                general_purpose::STANDARD.encode(format!("{}:{}", username, password));
+            vec![Header::new(
+                "Authorization",
+                format!("Basic {}", credentials),
+            )]
+        }
+        RequestAuth::Bearer { token } => {
+            vec![Header::new("Authorization", format!("Bearer {}", token))]
+        }
+        RequestAuth::ApiKey {
+            name,
+            value,
+            location: ApiKeyLocation::Header,
+        } => vec![Header::new(name, value)],
+        RequestAuth::AwsSigV4 {
+            access_key_id,
+            secret_access_key,
+            session_token,
+            region,
+            service,
+        } => aws_sigv4_headers(
+            request,
+            &AwsCredentials {
+                access_key_id,
+                secret_access_key,
+                session_token: session_token.as_deref(),
+                region,
+                service,
+            },
+            now,
+        ),
+        RequestAuth::NoAuth | RequestAuth::Digest { .. } | RequestAuth::ApiKey { .. } => vec![],
+    }
+}
+
+struct AwsCredentials<'a> {
+    access_key_id: &'a str,
+    secret_access_key: &'a str,
+    session_token: Option<&'a str>,
+    region: &'a str,
+    service: &'a str,
+}
+
+/// Signs a request with AWS Signature Version 4, see
+/// https://docs.aws.amazon.com/IAM/latest/UserGuide/create-signed-request.html
+fn aws_sigv4_headers(
+    request: &SigningRequest,
+    credentials: &AwsCredentials,
+    now: DateTime<Utc>,
+) -> Vec<Header> {
+    let url = match url::Url::parse(request.url) {
+        Ok(url) => url,
+        Err(_) => {
+            log::warn!(
+                "Could not sign request as the url is invalid: '{}'",
+                request.url
+            );
+            return vec![];
+        }
+    };
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+    let payload_hash = request
+        .payload
+        .map(|payload| hex::encode(Sha256::digest(payload)))
+        .unwrap_or_else(|| UNSIGNED_PAYLOAD.to_string());
+
+    let host = match url.port() {
+        Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+        None => url.host_str().unwrap_or_default().to_string(),
+    };
+    // sorted by name as required for the canonical request
+    let mut signed_headers: Vec<(&str, String)> = vec![
+        ("host", host),
+        ("x-amz-content-sha256", payload_hash.clone()),
+        ("x-amz-date", amz_date.clone()),
+    ];
+    if let Some(session_token) = credentials.session_token {
+        signed_headers.push(("x-amz-security-token", session_token.to_string()));
+    }
+    let canonical_headers: String = signed_headers
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+        .collect();
+    let signed_header_names = signed_headers
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<&str>>()
+        .join(";");
+
+    // s3 does not normalize the path, all other services expect it to be encoded twice
+    let canonical_uri = if credentials.service == "s3" {
+        url.path().to_string()
+    } else {
+        url.path()
+            .split('/')
+            .map(uri_encode)
+            .collect::<Vec<String>>()
+            .join("/")
+    };
+    let mut query: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(name, value)| (uri_encode(&name), uri_encode(&value)))
+        .collect();
+    query.sort();
+    let canonical_query = query
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<String>>()
+        .join("&");
+
+    let canonical_request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        request.method,
+        canonical_uri,
+        canonical_query,
+        canonical_headers,
+        signed_header_names,
+        payload_hash
+    );
+    let scope = format!(
+        "{}/{}/{}/aws4_request",
+        date, credentials.region, credentials.service
+    );
+    let string_to_sign = format!(
+        "{}\n{}\n{}\n{}",
+        AWS_ALGORITHM,
+        amz_date,
+        scope,
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+    let signing_key = aws_signing_key(
+        credentials.secret_access_key,
+        &date,
+        credentials.region,
+        credentials.service,
+    );
+    let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+    let mut headers = vec![
+        Header::new("X-Amz-Date", amz_date),
+        Header::new("X-Amz-Content-Sha256", payload_hash),
+    ];
+    if let Some(session_token) = credentials.session_token {
+        headers.push(Header::new("X-Amz-Security-Token", session_token));
+    }
+    headers.push(Header::new(
+        "Authorization",
+        format!(
+            "{} Credential={}/{}, SignedHeaders={}, Signature={}",
+            AWS_ALGORITHM, credentials.access_key_id, scope, signed_header_names, signature
+        ),
+    ));
+    headers
+}
+
+fn aws_signing_key(secret_access_key: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let date_key = hmac_sha256(
+        format!("AWS4{}", secret_access_key).as_bytes(),
+        date.as_bytes(),
+    );
+    let region_key = hmac_sha256(&date_key, region.as_bytes());
+    let service_key = hmac_sha256(&region_key, service.as_bytes());
+    hmac_sha256(&service_key, b"aws4_request")
+}
+
+/// HMAC as defined in rfc 2104 with sha256 as hash function
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("hmac accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Percent encodes everything except the unreserved characters of rfc 3986
+fn uri_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_hmac_and_signing_key() {
+        // test case 2 of rfc 4231
+        assert_eq!(
+            hex::encode(hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        // example of the aws documentation
+        assert_eq!(
+            hex::encode(aws_signing_key(
+                "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+                "20120215",
+                "us-east-1",
+                "iam"
+            )),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn test_auth_headers() {
+        let request = SigningRequest {
+            method: "GET",
+            url: "https://example.com/api?b=2&a=1",
+            payload: None,
+        };
+        let now = Utc.with_ymd_and_hms(2023, 7, 1, 12, 0, 0).unwrap();
+
+        let basic = RequestAuth::Basic {
+            username: "user".to_string(),
+            password: "pass".to_string(),
+        };
+        assert_eq!(
+            auth_headers(&basic, &request, now),
+            vec![Header::new("Authorization", "Basic dXNlcjpwYXNz")]
+        );
+
+        let aws = RequestAuth::AwsSigV4 {
+            access_key_id: "AKIDEXAMPLE".to_string(),
+            secret_access_key: "secret".to_string(),
+            session_token: None,
+            region: "eu-central-1".to_string(),
+            service: "execute-api".to_string(),
+        };
+        let headers = auth_headers(&aws, &request, now);
+        assert_eq!(headers[0], Header::new("X-Amz-Date", "20230701T120000Z"));
+        assert!(headers[2].value.starts_with(
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20230701/eu-central-1/execute-api/aws4_request, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature="
+        ));
+
+        let api_key = RequestAuth::ApiKey {
+            name: "api_key".to_string(),
+            value: "a b".to_string(),
+            location: ApiKeyLocation::Query,
+        };
+        assert_eq!(
+            url_with_auth("https://example.com/api?x=1", &api_key),
+            "https://example.com/api?x=1&api_key=a+b"
+        );
+        assert!(auth_headers(&api_key, &request, now).is_empty());
+    }
+}
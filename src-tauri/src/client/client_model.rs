@@ -2,20 +2,25 @@ use std::time::Duration;
 
 use http_rest_file::model::HttpVersion;
 
-use crate::model::Header;
+use crate::model::{CallTimings, GetHeadersOption, Header, RedirectHop, SentRequest};
 
-use super::{certificate::Certificate, request::Request, timings::Timings};
+use super::{certificate::Certificate, request::Request, timings::Timings, tls::TlsSession};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Response {
     pub version: HttpVersion,
     pub status: u32,
     pub headers: Vec<Header>,
+    /// The body decompressed according to its `Content-Encoding`
     pub body: Vec<u8>,
+    /// The size of the body as received, before it has been decompressed
+    pub raw_body_size: usize,
     pub duration: Duration,
     pub url: String,
-    /// The end-user certificate, in the response certificate chain
-    pub certificate: Option<Certificate>,
+    /// The certificate chain of the server, starting with the end-user certificate
+    pub certificate_chain: Vec<Certificate>,
+    /// The negotiated tls version and cipher, if the connection used tls
+    pub tls_session: Option<TlsSession>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -25,6 +30,62 @@ pub struct Call {
     pub timings: Timings,
 }
 
+impl From<&Call> for CallTimings {
+    fn from(call: &Call) -> Self {
+        let timings = &call.timings;
+        CallTimings {
+            url: call.request.url.clone(),
+            status_code: call.response.status,
+            begin_call: timings.begin_call.to_rfc3339(),
+            end_call: timings.end_call.to_rfc3339(),
+            name_lookup: timings.name_lookup.as_secs_f64(),
+            connect: timings.connect.as_secs_f64(),
+            app_connect: timings.app_connect.as_secs_f64(),
+            pre_transfer: timings.pre_transfer.as_secs_f64(),
+            start_transfer: timings.start_transfer.as_secs_f64(),
+            total: timings.total.as_secs_f64(),
+            size_upload: timings.size_upload as f64,
+            size_download: timings.size_download as f64,
+            speed_upload: timings.speed_upload as f64,
+            speed_download: timings.speed_download as f64,
+        }
+    }
+}
+
+impl From<&Call> for RedirectHop {
+    fn from(call: &Call) -> Self {
+        RedirectHop {
+            url: call.request.url.clone(),
+            status_code: call.response.status,
+            location: call
+                .response
+                .get_header_values("Location", GetHeadersOption::JustValues)
+                .into_iter()
+                .next(),
+            request: SentRequest::from(&call.request),
+        }
+    }
+}
+
+impl From<&Request> for SentRequest {
+    fn from(request: &Request) -> Self {
+        SentRequest {
+            method: request.method.clone(),
+            url: request.url.clone(),
+            headers: request
+                .headers
+                .iter()
+                .map(|header| Header {
+                    key: header.name.clone(),
+                    value: header.value.clone(),
+                    active: true,
+                })
+                .collect(),
+            body: String::from_utf8_lossy(&request.body).to_string(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RequestCookie {
     pub name: String,
@@ -53,3 +114,68 @@ impl std::fmt::Display for RequestCookie {
         write!(f, "{}={}", self.name, self.value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redirect_call() -> Call {
+        Call {
+            request: Request {
+                url: "http://localhost/old".to_string(),
+                method: "POST".to_string(),
+                headers: vec![crate::client::request::Header {
+                    name: "Host".to_string(),
+                    value: "localhost".to_string(),
+                }],
+                body: b"name=relynx".to_vec(),
+            },
+            response: Response {
+                version: HttpVersion { major: 1, minor: 1 },
+                status: 301,
+                headers: vec![Header {
+                    key: "location".to_string(),
+                    value: "/new".to_string(),
+                    active: true,
+                }],
+                body: vec![],
+                raw_body_size: 0,
+                duration: Duration::default(),
+                url: "http://localhost/old".to_string(),
+                certificate_chain: vec![],
+                tls_session: None,
+            },
+            timings: Timings::default(),
+        }
+    }
+
+    #[test]
+    fn test_redirect_hop() {
+        assert_eq!(
+            RedirectHop::from(&redirect_call()),
+            RedirectHop {
+                url: "http://localhost/old".to_string(),
+                status_code: 301,
+                location: Some("/new".to_string()),
+                request: SentRequest::from(&redirect_call().request),
+            }
+        );
+    }
+
+    #[test]
+    fn test_sent_request() {
+        assert_eq!(
+            SentRequest::from(&redirect_call().request),
+            SentRequest {
+                method: "POST".to_string(),
+                url: "http://localhost/old".to_string(),
+                headers: vec![Header {
+                    key: "Host".to_string(),
+                    value: "localhost".to_string(),
+                    active: true,
+                }],
+                body: "name=relynx".to_string(),
+            }
+        );
+    }
+}
@@ -31,6 +31,11 @@ use curl_sys::{curl_certinfo, curl_off_t, curl_slist, CURLINFO};
 /// Some definitions not present in curl-sys
 const CURLINFO_OFF_T: CURLINFO = 0x600000;
 
+const CURLINFO_SIZE_UPLOAD_T: CURLINFO = CURLINFO_OFF_T + 7;
+const CURLINFO_SIZE_DOWNLOAD_T: CURLINFO = CURLINFO_OFF_T + 8;
+const CURLINFO_SPEED_DOWNLOAD_T: CURLINFO = CURLINFO_OFF_T + 9;
+const CURLINFO_SPEED_UPLOAD_T: CURLINFO = CURLINFO_OFF_T + 10;
+
 const CURLINFO_TOTAL_TIME_T: CURLINFO = CURLINFO_OFF_T + 50;
 const CURLINFO_NAMELOOKUP_TIME_T: CURLINFO = CURLINFO_OFF_T + 51;
 const CURLINFO_CONNECT_TIME_T: CURLINFO = CURLINFO_OFF_T + 52;
@@ -45,23 +50,26 @@ pub struct CertInfo {
     pub data: Vec<String>,
 }
 
-/// Returns the information of the first certificate in the certificates chain.
-pub fn get_certinfo(easy: &Easy) -> Result<Option<CertInfo>, Error> {
+/// Returns the information of all certificates in the certificates chain, starting with the
+/// end-user certificate.
+pub fn get_certinfo_chain(easy: &Easy) -> Result<Vec<CertInfo>, Error> {
     unsafe {
         let mut certinfo = ptr::null_mut::<curl_certinfo>();
         let rc =
             curl_sys::curl_easy_getinfo(easy.raw(), curl_sys::CURLINFO_CERTINFO, &mut certinfo);
         cvt(easy, rc)?;
         if certinfo.is_null() {
-            return Ok(None);
+            return Ok(vec![]);
         }
         let count = (*certinfo).num_of_certs;
-        if count <= 0 {
-            return Ok(None);
+        let mut chain = vec![];
+        for i in 0..count.max(0) {
+            let slist = *((*certinfo).certinfo.offset(i as isize));
+            chain.push(CertInfo {
+                data: to_list(slist),
+            });
         }
-        let slist = *((*certinfo).certinfo.offset(0));
-        let data = to_list(slist);
-        Ok(Some(CertInfo { data }))
+        Ok(chain)
     }
 }
 
@@ -177,6 +185,49 @@ pub fn total_time_t(easy: &mut Easy) -> Result<Duration, Error> {
     getopt_off_t(easy, CURLINFO_TOTAL_TIME_T).map(microseconds_to_duration)
 }
 
+/// Get the number of uploaded bytes.
+///
+/// Returns the total amount of bytes that were uploaded in the previous transfer.
+///
+/// Corresponds to [`CURLINFO_SIZE_UPLOAD_T`] and may return an error if the
+/// option isn't supported.
+pub fn size_upload_t(easy: &mut Easy) -> Result<u64, Error> {
+    getopt_off_t(easy, CURLINFO_SIZE_UPLOAD_T).map(|bytes| bytes as u64)
+}
+
+/// Get the number of downloaded bytes.
+///
+/// Returns the total amount of bytes that were downloaded in the previous transfer. The amount
+/// is only for the latest transfer and does not include the headers.
+///
+/// Corresponds to [`CURLINFO_SIZE_DOWNLOAD_T`] and may return an error if the
+/// option isn't supported.
+pub fn size_download_t(easy: &mut Easy) -> Result<u64, Error> {
+    getopt_off_t(easy, CURLINFO_SIZE_DOWNLOAD_T).map(|bytes| bytes as u64)
+}
+
+/// Get the average upload speed.
+///
+/// Returns the average upload speed in bytes per second that curl measured for the complete
+/// upload.
+///
+/// Corresponds to [`CURLINFO_SPEED_UPLOAD_T`] and may return an error if the
+/// option isn't supported.
+pub fn speed_upload_t(easy: &mut Easy) -> Result<u64, Error> {
+    getopt_off_t(easy, CURLINFO_SPEED_UPLOAD_T).map(|bytes| bytes as u64)
+}
+
+/// Get the average download speed.
+///
+/// Returns the average download speed in bytes per second that curl measured for the complete
+/// download.
+///
+/// Corresponds to [`CURLINFO_SPEED_DOWNLOAD_T`] and may return an error if the
+/// option isn't supported.
+pub fn speed_download_t(easy: &mut Easy) -> Result<u64, Error> {
+    getopt_off_t(easy, CURLINFO_SPEED_DOWNLOAD_T).map(|bytes| bytes as u64)
+}
+
 /// Converts an instance of libcurl linked list [`curl_slist`] to a vec of [`String`].
 fn to_list(slist: *mut curl_slist) -> Vec<String> {
     let mut data = vec![];
@@ -60,6 +60,18 @@ pub enum HttpError {
 
     #[error("Form error")]
     FormError,
+
+    #[error("Could not read the certificate file: '{0}'")]
+    CouldNotReadCertificateFile(PathBuf),
+
+    #[error("Could not run the pre request script. {0}")]
+    PreRequestScript(String),
+
+    #[error("Could not acquire the OAuth 2.0 token '{auth_id}', {description}")]
+    OAuth2 {
+        auth_id: String,
+        description: String,
+    },
 }
 
 impl From<curl::Error> for HttpError {
@@ -0,0 +1,97 @@
+use curl::easy;
+use http_rest_file::model::HttpVersion;
+
+/// Returns the curl http version to send a request with the version of its request line.
+/// Plain http requests with HTTP/2 use prior knowledge (h2c) as there is no tls handshake in
+/// which the version could be negotiated.
+pub fn curl_http_version(version: &HttpVersion, url: &str) -> easy::HttpVersion {
+    let is_plain_http = url.to_lowercase().starts_with("http://");
+    match (version.major, version.minor) {
+        (1, 0) => easy::HttpVersion::V10,
+        (1, _) => easy::HttpVersion::V11,
+        (2, _) if is_plain_http => easy::HttpVersion::V2PriorKnowledge,
+        (2, _) => easy::HttpVersion::V2,
+        (3, _) => easy::HttpVersion::V3,
+        _ => easy::HttpVersion::Any,
+    }
+}
+
+/// Formats the version as within a status line, `HTTP/1.1` or `HTTP/2`
+pub fn format_http_version(version: &HttpVersion) -> String {
+    if version.major >= 2 && version.minor == 0 {
+        format!("HTTP/{}", version.major)
+    } else {
+        format!("HTTP/{}.{}", version.major, version.minor)
+    }
+}
+
+/// Returns a warning if the server responded with a lower version than requested
+pub fn downgrade_warning(requested: &HttpVersion, negotiated: &HttpVersion) -> Option<String> {
+    if (negotiated.major, negotiated.minor) < (requested.major, requested.minor) {
+        Some(format!(
+            "The request has been sent with {} but the server responded with {}.",
+            format_http_version(requested),
+            format_http_version(negotiated)
+        ))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_curl_http_version() {
+        let url = "https://example.com";
+        assert!(matches!(
+            curl_http_version(&HttpVersion { major: 1, minor: 0 }, url),
+            easy::HttpVersion::V10
+        ));
+        assert!(matches!(
+            curl_http_version(&HttpVersion { major: 1, minor: 1 }, url),
+            easy::HttpVersion::V11
+        ));
+        assert!(matches!(
+            curl_http_version(&HttpVersion { major: 2, minor: 0 }, url),
+            easy::HttpVersion::V2
+        ));
+        assert!(matches!(
+            curl_http_version(&HttpVersion { major: 2, minor: 0 }, "http://localhost:8080"),
+            easy::HttpVersion::V2PriorKnowledge
+        ));
+        assert!(matches!(
+            curl_http_version(&HttpVersion { major: 3, minor: 0 }, url),
+            easy::HttpVersion::V3
+        ));
+    }
+
+    #[test]
+    fn test_downgrade_warning() {
+        assert_eq!(
+            downgrade_warning(
+                &HttpVersion { major: 2, minor: 0 },
+                &HttpVersion { major: 1, minor: 1 }
+            ),
+            Some(
+                "The request has been sent with HTTP/2 but the server responded with HTTP/1.1."
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            downgrade_warning(
+                &HttpVersion { major: 1, minor: 1 },
+                &HttpVersion { major: 1, minor: 1 }
+            ),
+            None
+        );
+        assert_eq!(
+            downgrade_warning(
+                &HttpVersion { major: 1, minor: 1 },
+                &HttpVersion { major: 2, minor: 0 }
+            ),
+            None
+        );
+    }
+}
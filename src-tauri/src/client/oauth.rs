@@ -0,0 +1,670 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use base64::engine::general_purpose;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use curl::easy::{Easy, List};
+use lazy_static::lazy_static;
+use rand::distributions::{Alphanumeric, DistString};
+use regex::Regex;
+use rsa::sha2::{Digest, Sha256};
+use serde::Deserialize;
+
+use super::error::HttpError;
+use super::options::ClientOptions;
+use super::tls::find_tls_profile;
+use super::{set_connection_options, set_tls_profile};
+use crate::environment::replace_variables_in_str;
+use crate::model::{
+    Environment, OAuth2ClientCredentials, OAuth2Configuration, OAuth2GrantType, RunLogger,
+};
+
+lazy_static! {
+    /// Matches `$auth.token("id")`, the id is captured
+    static ref AUTH_TOKEN_REGEX: Regex =
+        Regex::new(r#"^\$auth\.token\s*\(\s*["']([^"']+)["']\s*\)$"#).unwrap();
+    static ref TOKEN_CACHE: Mutex<HashMap<TokenCacheKey, CachedToken>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Tokens are renewed this many seconds before they expire so they do not expire during a request
+const EXPIRY_LEEWAY_SECS: i64 = 30;
+/// How long we wait for the user to log in within the browser
+const AUTHORIZATION_TIMEOUT: Duration = Duration::from_secs(300);
+/// How long we wait for the request line of a connection to the redirect url
+const CALLBACK_READ_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_REDIRECT_URL: &str = "http://127.0.0.1:0/callback";
+const CODE_VERIFIER_LEN: usize = 64;
+const STATE_LEN: usize = 32;
+
+/// Tokens are cached per collection, environment and auth configuration, the configuration is
+/// part of the key so changing it within the env file acquires a new token
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct TokenCacheKey {
+    collection: Option<PathBuf>,
+    environment: String,
+    auth_id: String,
+    token_url: String,
+    client_id: String,
+    scope: Option<String>,
+    username: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct CachedToken {
+    access_token: String,
+    refresh_token: Option<String>,
+    /// `None` if the token endpoint did not return an expiry
+    expires_at: Option<DateTime<Utc>>,
+}
+
+impl CachedToken {
+    fn is_expired(&self) -> bool {
+        self.expires_at
+            .map(|expires_at| {
+                expires_at - chrono::Duration::seconds(EXPIRY_LEEWAY_SECS) <= Utc::now()
+            })
+            .unwrap_or(false)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    /// some providers return the number as string
+    expires_in: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize, Debug)]
+struct TokenErrorResponse {
+    error: String,
+    error_description: Option<String>,
+}
+
+/// Returns the auth id of a `$auth.token("id")` expression
+pub fn parse_auth_token_expression(expression: &str) -> Option<&str> {
+    AUTH_TOKEN_REGEX
+        .captures(expression)
+        .and_then(|captures| captures.get(1))
+        .map(|auth_id| auth_id.as_str())
+}
+
+/// Acquires the tokens of the `$auth.token("id")` expressions that are used within a request.
+/// Cached tokens are reused until they expire and are then refreshed if possible. The tokens are
+/// returned as variables named after their expression within a copy of the environment, `None`
+/// is returned if the request does not use any token. The tokens are added to the redactor of
+/// the logger so they are masked like the secrets of the environment.
+pub fn add_auth_tokens(
+    expressions: &[String],
+    environment: Option<&Environment>,
+    options: &ClientOptions,
+    logger: &RunLogger,
+) -> Result<Option<Environment>, HttpError> {
+    let environment = match environment {
+        Some(environment) => environment,
+        None => return Ok(None),
+    };
+    let mut auth_environment: Option<Environment> = None;
+    for expression in expressions {
+        let auth_id = match parse_auth_token_expression(expression) {
+            Some(auth_id) => auth_id,
+            None => continue,
+        };
+        let configuration = match environment.auth_configurations.get(auth_id) {
+            Some(configuration) => render_configuration(configuration, environment),
+            None => {
+                logger.log_info(format!(
+                    "There is no auth configuration '{}' within environment '{}'",
+                    auth_id, environment.name
+                ));
+                continue;
+            }
+        };
+        let token = get_token(environment, auth_id, &configuration, options, logger)?;
+        logger.redactor().add_secret(&token);
+        auth_environment
+            .get_or_insert_with(|| environment.clone())
+            .set_variable(expression, token);
+    }
+    Ok(auth_environment)
+}
+
+/// Removes all cached tokens so they are acquired again on the next request
+pub fn clear_token_cache() {
+    TOKEN_CACHE.lock().unwrap().clear();
+}
+
+/// Replaces the environment variables within the values of the configuration
+fn render_configuration(
+    configuration: &OAuth2Configuration,
+    environment: &Environment,
+) -> OAuth2Configuration {
+    let render = |value: &String| replace_variables_in_str(value, Some(environment));
+    OAuth2Configuration {
+        grant_type: configuration.grant_type,
+        token_url: render(&configuration.token_url),
+        auth_url: configuration.auth_url.as_ref().map(render),
+        redirect_url: configuration.redirect_url.as_ref().map(render),
+        client_id: render(&configuration.client_id),
+        client_secret: configuration.client_secret.as_ref().map(render),
+        client_credentials: configuration.client_credentials,
+        scope: configuration.scope.as_ref().map(render),
+        username: configuration.username.as_ref().map(render),
+        password: configuration.password.as_ref().map(render),
+        pkce: configuration.pkce,
+        custom_parameters: configuration
+            .custom_parameters
+            .iter()
+            .map(|(name, value)| (name.clone(), render(value)))
+            .collect(),
+    }
+}
+
+fn get_token(
+    environment: &Environment,
+    auth_id: &str,
+    configuration: &OAuth2Configuration,
+    options: &ClientOptions,
+    logger: &RunLogger,
+) -> Result<String, HttpError> {
+    let key = TokenCacheKey {
+        collection: options.collection_path.clone(),
+        environment: environment.name.clone(),
+        auth_id: auth_id.to_string(),
+        token_url: configuration.token_url.clone(),
+        client_id: configuration.client_id.clone(),
+        scope: configuration.scope.clone(),
+        username: configuration.username.clone(),
+    };
+    // the lock is not held while tokens are requested as this may take a while
+    let cached = TOKEN_CACHE.lock().unwrap().get(&key).cloned();
+    let token = match cached {
+        Some(cached) if !cached.is_expired() => {
+            logger.log_info(format!("Using cached OAuth 2.0 token '{}'", auth_id));
+            return Ok(cached.access_token);
+        }
+        Some(CachedToken {
+            refresh_token: Some(refresh_token),
+            ..
+        }) => {
+            logger.log_info(format!("Refreshing expired OAuth 2.0 token '{}'", auth_id));
+            match refresh_token_grant(auth_id, configuration, &refresh_token, options, environment)
+            {
+                Ok(token) => token,
+                Err(err) => {
+                    logger.log_info(format!(
+                        "Could not refresh OAuth 2.0 token '{}', acquiring a new one: {}",
+                        auth_id, err
+                    ));
+                    acquire_token(auth_id, configuration, options, environment, logger)?
+                }
+            }
+        }
+        _ => acquire_token(auth_id, configuration, options, environment, logger)?,
+    };
+    let access_token = token.access_token.clone();
+    TOKEN_CACHE.lock().unwrap().insert(key, token);
+    Ok(access_token)
+}
+
+fn acquire_token(
+    auth_id: &str,
+    configuration: &OAuth2Configuration,
+    options: &ClientOptions,
+    environment: &Environment,
+    logger: &RunLogger,
+) -> Result<CachedToken, HttpError> {
+    logger.log_info(format!(
+        "Acquiring OAuth 2.0 token '{}' with grant type {:?}",
+        auth_id, configuration.grant_type
+    ));
+    let mut params: Vec<(String, String)> = Vec::new();
+    match configuration.grant_type {
+        OAuth2GrantType::ClientCredentials => {
+            params.push(("grant_type".to_string(), "client_credentials".to_string()));
+        }
+        OAuth2GrantType::Password => {
+            params.push(("grant_type".to_string(), "password".to_string()));
+            params.push((
+                "username".to_string(),
+                configuration.username.clone().unwrap_or_default(),
+            ));
+            params.push((
+                "password".to_string(),
+                configuration.password.clone().unwrap_or_default(),
+            ));
+        }
+        OAuth2GrantType::AuthorizationCode => {
+            let authorization = authorize(auth_id, configuration, logger)?;
+            params.push(("grant_type".to_string(), "authorization_code".to_string()));
+            params.push(("code".to_string(), authorization.code));
+            params.push(("redirect_uri".to_string(), authorization.redirect_uri));
+            if let Some(code_verifier) = authorization.code_verifier {
+                params.push(("code_verifier".to_string(), code_verifier));
+            }
+        }
+    }
+    // the scope has already been granted with the authorization code
+    if configuration.grant_type != OAuth2GrantType::AuthorizationCode {
+        if let Some(ref scope) = configuration.scope {
+            params.push(("scope".to_string(), scope.clone()));
+        }
+    }
+    request_token(auth_id, configuration, params, options, environment)
+}
+
+fn refresh_token_grant(
+    auth_id: &str,
+    configuration: &OAuth2Configuration,
+    refresh_token: &str,
+    options: &ClientOptions,
+    environment: &Environment,
+) -> Result<CachedToken, HttpError> {
+    let params = vec![
+        ("grant_type".to_string(), "refresh_token".to_string()),
+        ("refresh_token".to_string(), refresh_token.to_string()),
+    ];
+    let mut token = request_token(auth_id, configuration, params, options, environment)?;
+    // the refresh token stays valid if the server does not issue a new one
+    if token.refresh_token.is_none() {
+        token.refresh_token = Some(refresh_token.to_string());
+    }
+    Ok(token)
+}
+
+/// Sends a request to the token endpoint and returns the received token
+fn request_token(
+    auth_id: &str,
+    configuration: &OAuth2Configuration,
+    mut params: Vec<(String, String)>,
+    options: &ClientOptions,
+    environment: &Environment,
+) -> Result<CachedToken, HttpError> {
+    let oauth_error = |description: String| HttpError::OAuth2 {
+        auth_id: auth_id.to_string(),
+        description,
+    };
+
+    let mut headers = List::new();
+    headers.append("Content-Type: application/x-www-form-urlencoded")?;
+    headers.append("Accept: application/json")?;
+    match configuration.client_credentials {
+        OAuth2ClientCredentials::Basic => {
+            // id and secret are form encoded before they are combined, see rfc 6749 section 2.3.1
+            let credentials = format!(
+                "{}:{}",
+                form_encode(&configuration.client_id),
+                form_encode(configuration.client_secret.as_deref().unwrap_or_default())
+            );
+            headers.append(&format!(
+                "Authorization: Basic {}",
+                general_purpose::STANDARD.encode(credentials)
+            ))?;
+        }
+        OAuth2ClientCredentials::InBody => {
+            params.push(("client_id".to_string(), configuration.client_id.clone()));
+            params.push((
+                "client_secret".to_string(),
+                configuration.client_secret.clone().unwrap_or_default(),
+            ));
+        }
+        OAuth2ClientCredentials::None => {
+            params.push(("client_id".to_string(), configuration.client_id.clone()));
+        }
+    }
+    params.extend(configuration.custom_parameters.clone());
+    let body = url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(params)
+        .finish();
+
+    let mut handle = Easy::new();
+    handle
+        .url(&configuration.token_url)
+        .map_err(|_| HttpError::InvalidUrl(configuration.token_url.clone()))?;
+    handle.post(true)?;
+    handle.post_fields_copy(body.as_bytes())?;
+    handle.http_headers(headers)?;
+    // token endpoints are reached the same way as the hosts of the requests, e.g. with mutual tls
+    set_connection_options(&mut handle, options, Some(environment))?;
+    if let Some(profile) = find_tls_profile(&options.tls_profiles, &configuration.token_url) {
+        set_tls_profile(&mut handle, profile, options, Some(environment))?;
+    }
+
+    let mut response_body: Vec<u8> = Vec::new();
+    {
+        let mut transfer = handle.transfer();
+        transfer.write_function(|data| {
+            response_body.extend_from_slice(data);
+            Ok(data.len())
+        })?;
+        transfer.perform().map_err(|err| HttpError::Libcurl {
+            code: err.code() as i32,
+            description: err.description().to_string(),
+            url: configuration.token_url.clone(),
+        })?;
+    }
+
+    let status = handle.response_code()?;
+    if !(200..300).contains(&status) {
+        let description = match serde_json::from_slice::<TokenErrorResponse>(&response_body) {
+            Ok(error) => format!(
+                "the token endpoint responded with status {}, error: '{}' {}",
+                status,
+                error.error,
+                error.error_description.unwrap_or_default()
+            ),
+            Err(_) => format!("the token endpoint responded with status {}", status),
+        };
+        return Err(oauth_error(description.trim_end().to_string()));
+    }
+
+    let response = serde_json::from_slice::<TokenResponse>(&response_body)
+        .map_err(|err| oauth_error(format!("the token response could not be parsed: {}", err)))?;
+    let expires_at = response
+        .expires_in
+        .and_then(|expires_in| {
+            expires_in
+                .as_i64()
+                .or_else(|| expires_in.as_str().and_then(|value| value.parse().ok()))
+        })
+        .map(|expires_in| Utc::now() + chrono::Duration::seconds(expires_in));
+    Ok(CachedToken {
+        access_token: response.access_token,
+        refresh_token: response.refresh_token,
+        expires_at,
+    })
+}
+
+struct Authorization {
+    code: String,
+    redirect_uri: String,
+    code_verifier: Option<String>,
+}
+
+/// Opens the authorization url within the browser and waits until the authorization server
+/// redirects the user to our loopback address with the authorization code
+fn authorize(
+    auth_id: &str,
+    configuration: &OAuth2Configuration,
+    logger: &RunLogger,
+) -> Result<Authorization, HttpError> {
+    let oauth_error = |description: String| HttpError::OAuth2 {
+        auth_id: auth_id.to_string(),
+        description,
+    };
+
+    let redirect_url = configuration
+        .redirect_url
+        .as_deref()
+        .unwrap_or(DEFAULT_REDIRECT_URL);
+    let mut redirect_url = url::Url::parse(redirect_url)
+        .map_err(|_| oauth_error(format!("invalid redirect url '{}'", redirect_url)))?;
+    let host = redirect_url.host_str().unwrap_or_default().to_string();
+    if !matches!(host.as_str(), "localhost" | "127.0.0.1" | "[::1]") {
+        return Err(oauth_error(
+            "the redirect url needs to be a loopback address such as 'http://127.0.0.1:8080/callback'"
+                .to_string(),
+        ));
+    }
+    let listener = TcpListener::bind((
+        host.trim_start_matches('[').trim_end_matches(']'),
+        redirect_url.port().unwrap_or(80),
+    ))
+    .map_err(|err| oauth_error(format!("could not listen on the redirect url: {}", err)))?;
+    let port = listener
+        .local_addr()
+        .map_err(|err| oauth_error(err.to_string()))?
+        .port();
+    // a random port is chosen if the url has port 0
+    let _ = redirect_url.set_port(Some(port));
+    let redirect_uri = redirect_url.to_string();
+
+    let state = Alphanumeric.sample_string(&mut rand::thread_rng(), STATE_LEN);
+    let code_verifier = configuration
+        .pkce
+        .then(|| Alphanumeric.sample_string(&mut rand::thread_rng(), CODE_VERIFIER_LEN));
+
+    let auth_url = configuration.auth_url.as_deref().unwrap_or_default();
+    let mut auth_url = url::Url::parse(auth_url)
+        .map_err(|_| oauth_error(format!("invalid auth url '{}'", auth_url)))?;
+    {
+        let mut query = auth_url.query_pairs_mut();
+        query
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &configuration.client_id)
+            .append_pair("redirect_uri", &redirect_uri)
+            .append_pair("state", &state);
+        if let Some(ref scope) = configuration.scope {
+            query.append_pair("scope", scope);
+        }
+        if let Some(ref code_verifier) = code_verifier {
+            let challenge =
+                general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()));
+            query
+                .append_pair("code_challenge", &challenge)
+                .append_pair("code_challenge_method", "S256");
+        }
+        query.extend_pairs(configuration.custom_parameters.iter());
+    }
+
+    logger.log_info(format!(
+        "Opening the browser for the authorization of '{}': {}",
+        auth_id, auth_url
+    ));
+    if let Err(err) = open_browser(auth_url.as_str()) {
+        logger.log_error(format!("Could not open the browser: {:?}", err));
+    }
+
+    let code =
+        wait_for_authorization_code(&listener, redirect_url.path(), &state).map_err(oauth_error)?;
+    Ok(Authorization {
+        code,
+        redirect_uri,
+        code_verifier,
+    })
+}
+
+fn wait_for_authorization_code(
+    listener: &TcpListener,
+    redirect_path: &str,
+    state: &str,
+) -> Result<String, String> {
+    listener
+        .set_nonblocking(true)
+        .map_err(|err| err.to_string())?;
+    let deadline = Instant::now() + AUTHORIZATION_TIMEOUT;
+    while Instant::now() < deadline {
+        let mut stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                std::thread::sleep(Duration::from_millis(100));
+                continue;
+            }
+            Err(err) => return Err(err.to_string()),
+        };
+        let _ = stream.set_nonblocking(false);
+        // connections that send nothing, such as preconnects of the browser, are skipped after a
+        // while so they neither block the authorization nor outlast the deadline
+        let read_timeout = deadline
+            .saturating_duration_since(Instant::now())
+            .min(CALLBACK_READ_TIMEOUT);
+        if read_timeout.is_zero() || stream.set_read_timeout(Some(read_timeout)).is_err() {
+            continue;
+        }
+        let mut request_line = String::new();
+        if BufReader::new(&stream)
+            .read_line(&mut request_line)
+            .is_err()
+        {
+            continue;
+        }
+        // e.g. 'GET /callback?code=abc&state=xyz HTTP/1.1'
+        let target = request_line.split_whitespace().nth(1).unwrap_or_default();
+        let url = match url::Url::parse(&format!("http://localhost{}", target)) {
+            Ok(url) if url.path() == redirect_path => url,
+            _ => {
+                // e.g. the browser requesting the favicon
+                respond(&mut stream, "404 Not Found", "");
+                continue;
+            }
+        };
+        let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+        if let Some(error) = params.get("error") {
+            respond(&mut stream, "200 OK", "The authorization failed.");
+            return Err(format!(
+                "the authorization failed, error: '{}' {}",
+                error,
+                params
+                    .get("error_description")
+                    .map(String::as_str)
+                    .unwrap_or_default()
+            )
+            .trim_end()
+            .to_string());
+        }
+        if params.get("state").map(String::as_str) != Some(state) {
+            respond(&mut stream, "400 Bad Request", "Invalid state.");
+            return Err("the state of the authorization response does not match".to_string());
+        }
+        return match params.get("code") {
+            Some(code) => {
+                respond(
+                    &mut stream,
+                    "200 OK",
+                    "The authorization succeeded, you can close this window and return to relynx.",
+                );
+                Ok(code.clone())
+            }
+            None => {
+                respond(&mut stream, "400 Bad Request", "The code is missing.");
+                Err("the authorization response does not contain a code".to_string())
+            }
+        };
+    }
+    Err("the authorization timed out".to_string())
+}
+
+fn respond(stream: &mut TcpStream, status: &str, message: &str) {
+    let body = format!("<html><body><p>{}</p></body></html>", message);
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+}
+
+fn open_browser(url: &str) -> std::io::Result<()> {
+    #[cfg(target_os = "macos")]
+    let mut command = std::process::Command::new("open");
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = std::process::Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    };
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = std::process::Command::new("xdg-open");
+    command.arg(url).spawn().map(|_| ())
+}
+
+fn form_encode(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use httptest::{matchers::*, responders::*, Expectation, Server};
+
+    use super::*;
+
+    fn configuration(token_url: String) -> OAuth2Configuration {
+        OAuth2Configuration {
+            grant_type: OAuth2GrantType::ClientCredentials,
+            token_url,
+            auth_url: None,
+            redirect_url: None,
+            client_id: "{{client_id}}".to_string(),
+            client_secret: Some("secret".to_string()),
+            client_credentials: OAuth2ClientCredentials::InBody,
+            scope: Some("read".to_string()),
+            username: None,
+            password: None,
+            pkce: false,
+            custom_parameters: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_auth_token_expression() {
+        assert_eq!(
+            parse_auth_token_expression("$auth.token(\"api\")"),
+            Some("api")
+        );
+        assert_eq!(
+            parse_auth_token_expression("$auth.token( 'api' )"),
+            Some("api")
+        );
+        assert_eq!(parse_auth_token_expression("$auth.idToken(\"api\")"), None);
+    }
+
+    #[test]
+    fn test_client_credentials_token_is_cached() {
+        let server = Server::run();
+        server.expect(
+            Expectation::matching(all_of![
+                request::method_path("POST", "/token"),
+                request::body(url_decoded(contains(("grant_type", "client_credentials")))),
+                request::body(url_decoded(contains(("client_id", "relynx")))),
+            ])
+            .times(1)
+            .respond_with(json_encoded(serde_json::json!({
+                "access_token": "access-token-abc",
+                "token_type": "Bearer",
+                "expires_in": 3600
+            }))),
+        );
+
+        let mut environment = Environment::new("oauth-test".to_string());
+        environment
+            .variables
+            .push(crate::model::EnvironmentVariable::new(
+                "client_id",
+                "relynx",
+            ));
+        environment.auth_configurations.insert(
+            "api".to_string(),
+            configuration(server.url("/token").to_string()),
+        );
+        let expressions = vec!["$auth.token(\"api\")".to_string(), "host".to_string()];
+        let logger = RunLogger::new(true, None);
+
+        for _ in 0..2 {
+            let auth_environment = add_auth_tokens(
+                &expressions,
+                Some(&environment),
+                &ClientOptions::default(),
+                &logger,
+            )
+            .unwrap()
+            .unwrap();
+            assert_eq!(
+                auth_environment
+                    .get_value("$auth.token(\"api\")")
+                    .as_deref(),
+                Some("access-token-abc")
+            );
+        }
+        assert_eq!(
+            logger.redactor().redact("Bearer access-token-abc"),
+            "Bearer *****"
+        );
+    }
+}
@@ -19,13 +19,23 @@
 * limitations under the License.
 *
 */
+use std::path::PathBuf;
 use std::time::Duration;
 
+use crate::model::{CertificateFormat, TlsProfile};
+
 #[derive(Debug, Clone)]
 pub struct ClientOptions {
     pub cacert_file: Option<String>,
+    /// warn about server certificates that expire within this number of days
+    pub certificate_expiry_warning_days: u32,
     pub client_cert_file: Option<String>,
+    pub client_cert_format: CertificateFormat,
     pub client_key_file: Option<String>,
+    /// passphrase of the client certificate or its key, may contain environment variables
+    pub client_key_password: Option<String>,
+    /// path of the collection the requests belong to, OAuth 2.0 tokens are cached per collection
+    pub collection_path: Option<PathBuf>,
     pub compressed: bool,
     pub connect_timeout: Duration,
     pub connects_to: Vec<String>,
@@ -39,6 +49,8 @@ pub struct ClientOptions {
     pub retry_max_count: Option<usize>,
     pub ssl_no_revoke: bool,
     pub timeout: Duration,
+    /// certificates for specific hosts, the first matching profile is used
+    pub tls_profiles: Vec<TlsProfile>,
     pub user: Option<String>,
     pub user_agent: Option<String>,
     pub verbosity: Option<Verbosity>,
@@ -55,8 +67,12 @@ impl Default for ClientOptions {
     fn default() -> Self {
         ClientOptions {
             cacert_file: None,
+            certificate_expiry_warning_days: 30,
             client_cert_file: None,
+            client_cert_format: CertificateFormat::Pem,
             client_key_file: None,
+            client_key_password: None,
+            collection_path: None,
             compressed: false,
             connect_timeout: Duration::from_secs(300),
             connects_to: vec![],
@@ -70,6 +86,7 @@ impl Default for ClientOptions {
             retry_max_count: Some(10),
             ssl_no_revoke: false,
             timeout: Duration::from_secs(300),
+            tls_profiles: vec![],
             user: None,
             user_agent: None,
             verbosity: None,
@@ -90,6 +107,10 @@ impl ClientOptions {
         if let Some(ref client_cert_file) = self.client_cert_file {
             arguments.push("--cert".to_string());
             arguments.push(client_cert_file.clone());
+            if self.client_cert_format == CertificateFormat::Pkcs12 {
+                arguments.push("--cert-type".to_string());
+                arguments.push("P12".to_string());
+            }
         }
         if let Some(ref client_key_file) = self.client_key_file {
             arguments.push("--key".to_string());
@@ -160,7 +181,9 @@ mod tests {
             ClientOptions {
                 cacert_file: None,
                 client_cert_file: None,
+                client_cert_format: CertificateFormat::Pem,
                 client_key_file: None,
+                client_key_password: None,
                 connects_to: vec!["example.com:443:host-47.example.com:443".to_string()],
                 follow_location: true,
                 max_redirect: Some(10),
@@ -177,6 +200,8 @@ mod tests {
                 ssl_no_revoke: false,
                 timeout: Duration::from_secs(10),
                 connect_timeout: Duration::from_secs(20),
+                tls_profiles: vec![],
+                certificate_expiry_warning_days: 30,
                 user: Some("user:password".to_string()),
                 user_agent: Some("my-useragent".to_string()),
                 compressed: true,
@@ -0,0 +1,491 @@
+use std::io::Read;
+
+use encoding::label::encoding_from_whatwg_label;
+use encoding::types::EncodingRef;
+use encoding::{all, DecoderTrap, Encoding};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::model::GetHeadersOption;
+
+use super::client_model::Response;
+use super::error::HttpError;
+
+/// Number of bytes at the start of a body in which an encoding declaration is searched
+const SNIFF_LENGTH: usize = 1024;
+
+/// Maximum size of a decompressed body, protects against small responses that expand to an
+/// amount of data that does not fit into memory
+const MAX_DECOMPRESSED_SIZE: u64 = 512 * 1024 * 1024;
+
+const UTF_8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF_16_LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF_16_BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+/// Supported values of the `Content-Encoding` header
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContentEncoding {
+    Brotli,
+    Gzip,
+    Deflate,
+    Zstd,
+    Identity,
+}
+
+impl ContentEncoding {
+    pub fn parse(value: &str) -> Result<ContentEncoding, HttpError> {
+        match value.trim().to_lowercase().as_str() {
+            "br" => Ok(ContentEncoding::Brotli),
+            "gzip" | "x-gzip" => Ok(ContentEncoding::Gzip),
+            "deflate" => Ok(ContentEncoding::Deflate),
+            "zstd" => Ok(ContentEncoding::Zstd),
+            "identity" => Ok(ContentEncoding::Identity),
+            other => Err(HttpError::UnsupportedContentEncoding {
+                description: other.to_string(),
+            }),
+        }
+    }
+
+    /// Decompresses data encoded with this encoding, fails if the decompressed data exceeds
+    /// `max_size` bytes
+    pub fn decode(&self, data: &[u8], max_size: u64) -> Result<Vec<u8>, HttpError> {
+        match self {
+            ContentEncoding::Identity => Ok(data.to_vec()),
+            ContentEncoding::Gzip => {
+                let decoder = libflate::gzip::Decoder::new(data).map_err(uncompress_error)?;
+                read_all(decoder, max_size)
+            }
+            // servers should send zlib wrapped data, but some send raw deflate streams
+            ContentEncoding::Deflate => match libflate::zlib::Decoder::new(data) {
+                Ok(decoder) => read_all(decoder, max_size),
+                Err(_) => read_all(libflate::deflate::Decoder::new(data), max_size),
+            },
+            ContentEncoding::Brotli => read_all(brotli::Decompressor::new(data, 4096), max_size),
+            ContentEncoding::Zstd => {
+                let decoder = zstd::stream::read::Decoder::new(data).map_err(uncompress_error)?;
+                read_all(decoder, max_size)
+            }
+        }
+    }
+}
+
+fn read_all(reader: impl Read, max_size: u64) -> Result<Vec<u8>, HttpError> {
+    let mut buffer = Vec::new();
+    // one more byte than allowed is read to detect that the limit has been exceeded
+    reader
+        .take(max_size + 1)
+        .read_to_end(&mut buffer)
+        .map_err(uncompress_error)?;
+    if buffer.len() as u64 > max_size {
+        return Err(HttpError::CouldNotUncompressResponse {
+            description: format!(
+                "the decompressed body exceeds the maximum size of {} bytes",
+                max_size
+            ),
+        });
+    }
+    Ok(buffer)
+}
+
+fn uncompress_error(err: std::io::Error) -> HttpError {
+    HttpError::CouldNotUncompressResponse {
+        description: err.to_string(),
+    }
+}
+
+lazy_static! {
+    // <?xml version="1.0" encoding="ISO-8859-1"?>
+    static ref XML_ENCODING_RE: Regex =
+        Regex::new(r#"^<\?xml[^>]+encoding=["']([A-Za-z0-9._:-]+)["']"#).unwrap();
+    // <meta charset="shift_jis"> or <meta http-equiv="Content-Type" content="text/html; charset=shift_jis">
+    static ref HTML_META_CHARSET_RE: Regex =
+        Regex::new(r#"(?i)<meta[^>]+charset=["']?([A-Za-z0-9._:-]+)"#).unwrap();
+}
+
+impl Response {
+    /// Returns the encodings of the `Content-Encoding` headers in the order they have been
+    /// applied by the server
+    pub fn content_encodings(&self) -> Result<Vec<ContentEncoding>, HttpError> {
+        self.get_header_values("Content-Encoding", GetHeadersOption::SplitMultiple)
+            .iter()
+            .filter(|value| !value.trim().is_empty())
+            .map(|value| ContentEncoding::parse(value))
+            .collect()
+    }
+
+    /// Decompresses the body along its content encodings, the last applied encoding is decoded
+    /// first. The body is left as received if it can not be decompressed.
+    pub fn decompress(&mut self) -> Result<(), HttpError> {
+        let encodings = self.content_encodings()?;
+        if self.body.is_empty()
+            || encodings
+                .iter()
+                .all(|encoding| *encoding == ContentEncoding::Identity)
+        {
+            return Ok(());
+        }
+        let mut body = self.body.clone();
+        for encoding in encodings.iter().rev() {
+            body = encoding.decode(&body, MAX_DECOMPRESSED_SIZE)?;
+        }
+        self.body = body;
+        Ok(())
+    }
+
+    /// Returns the value of the first `Content-Type` header
+    pub fn content_type(&self) -> Option<String> {
+        self.get_header_values("Content-Type", GetHeadersOption::JustValues)
+            .into_iter()
+            .next()
+    }
+
+    /// Returns the mime type of the `Content-Type` header without its parameters, in lowercase
+    pub fn mime_type(&self) -> Option<String> {
+        self.content_type().map(|content_type| {
+            content_type
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_lowercase()
+        })
+    }
+
+    /// Returns the character encoding of the body. The charset of the `Content-Type` header is
+    /// used if present, otherwise a byte order mark or an encoding declaration of html and xml
+    /// documents. Bodies without any of them are decoded as UTF-8 if valid, as Windows-1252
+    /// otherwise.
+    pub fn character_encoding(&self) -> Result<EncodingRef, HttpError> {
+        if let Some(charset) = self.content_type().as_deref().and_then(mime_charset) {
+            return encoding_from_whatwg_label(&charset)
+                .ok_or(HttpError::InvalidCharset { charset });
+        }
+        if let Some(encoding) = bom_encoding(&self.body) {
+            return Ok(encoding);
+        }
+        if let Some(encoding) = declared_encoding(&self.body) {
+            return Ok(encoding);
+        }
+        if std::str::from_utf8(&self.body).is_ok() {
+            Ok(all::UTF_8 as EncodingRef)
+        } else {
+            Ok(all::WINDOWS_1252 as EncodingRef)
+        }
+    }
+
+    /// Returns the name of the character encoding of the body, such as `utf-8`
+    pub fn charset(&self) -> Option<String> {
+        self.character_encoding().ok().map(encoding_name)
+    }
+
+    /// Returns the body decoded with its character encoding, see [`Response::character_encoding`]
+    pub fn text(&self) -> Result<String, HttpError> {
+        let encoding = self.character_encoding()?;
+        encoding
+            .decode(strip_bom(&self.body), DecoderTrap::Strict)
+            .map_err(|_| HttpError::InvalidDecoding {
+                charset: encoding_name(encoding),
+            })
+    }
+
+    /// Returns true if the body is not meant to be displayed as text, such as images or archives.
+    /// Responses without a content type are binary if they contain null bytes.
+    pub fn is_binary(&self) -> bool {
+        match self.mime_type() {
+            Some(mime_type) if !mime_type.is_empty() => !is_text_mime_type(&mime_type),
+            _ => {
+                bom_encoding(&self.body).is_none()
+                    && self.body.iter().take(SNIFF_LENGTH).any(|byte| *byte == 0)
+            }
+        }
+    }
+}
+
+/// Returns the name of an encoding as used within a `Content-Type` header
+fn encoding_name(encoding: EncodingRef) -> String {
+    encoding
+        .whatwg_name()
+        .unwrap_or_else(|| encoding.name())
+        .to_string()
+}
+
+/// Returns the charset parameter of a `Content-Type` header value
+fn mime_charset(content_type: &str) -> Option<String> {
+    content_type
+        .split(';')
+        .skip(1)
+        .filter_map(|parameter| parameter.split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("charset"))
+        .map(|(_, value)| value.trim().trim_matches('"').to_string())
+}
+
+fn bom_encoding(body: &[u8]) -> Option<EncodingRef> {
+    if body.starts_with(UTF_8_BOM) {
+        Some(all::UTF_8 as EncodingRef)
+    } else if body.starts_with(UTF_16_LE_BOM) {
+        Some(all::UTF_16LE as EncodingRef)
+    } else if body.starts_with(UTF_16_BE_BOM) {
+        Some(all::UTF_16BE as EncodingRef)
+    } else {
+        None
+    }
+}
+
+fn strip_bom(body: &[u8]) -> &[u8] {
+    [UTF_8_BOM, UTF_16_LE_BOM, UTF_16_BE_BOM]
+        .into_iter()
+        .find(|bom| body.starts_with(bom))
+        .map(|bom| &body[bom.len()..])
+        .unwrap_or(body)
+}
+
+/// Returns the encoding declared within an xml prolog or an html meta tag
+fn declared_encoding(body: &[u8]) -> Option<EncodingRef> {
+    let start = String::from_utf8_lossy(&body[..body.len().min(SNIFF_LENGTH)]).to_string();
+    XML_ENCODING_RE
+        .captures(start.trim_start())
+        .or_else(|| HTML_META_CHARSET_RE.captures(&start))
+        .and_then(|captures| encoding_from_whatwg_label(&captures[1]))
+}
+
+fn is_text_mime_type(mime_type: &str) -> bool {
+    const TEXT_MIME_TYPES: &[&str] = &[
+        "application/json",
+        "application/xml",
+        "application/javascript",
+        "application/ecmascript",
+        "application/x-www-form-urlencoded",
+        "application/graphql",
+        "application/yaml",
+        "application/x-yaml",
+        "application/x-ndjson",
+        "application/sql",
+    ];
+    mime_type.starts_with("text/")
+        || mime_type.ends_with("+json")
+        || mime_type.ends_with("+xml")
+        || TEXT_MIME_TYPES.contains(&mime_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use http_rest_file::model::HttpVersion;
+
+    use crate::model::Header;
+
+    use super::*;
+
+    fn response(content_type: Option<&str>, body: &[u8]) -> Response {
+        Response {
+            version: HttpVersion { major: 1, minor: 1 },
+            status: 200,
+            headers: content_type
+                .map(|value| Header {
+                    key: "Content-Type".to_string(),
+                    value: value.to_string(),
+                    active: true,
+                })
+                .into_iter()
+                .collect(),
+            body: body.to_vec(),
+            duration: Duration::default(),
+            url: "http://localhost".to_string(),
+            certificate_chain: vec![],
+            tls_session: None,
+            raw_body_size: body.len(),
+        }
+    }
+
+    fn encoded_response(content_encoding: &str, body: &[u8]) -> Response {
+        let mut response = response(Some("text/plain"), body);
+        response.headers.push(Header {
+            key: "Content-Encoding".to_string(),
+            value: content_encoding.to_string(),
+            active: true,
+        });
+        response
+    }
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = libflate::gzip::Encoder::new(Vec::new()).unwrap();
+        std::io::Write::write_all(&mut encoder, data).unwrap();
+        encoder.finish().into_result().unwrap()
+    }
+
+    fn brotli(data: &[u8]) -> Vec<u8> {
+        let mut compressed = Vec::new();
+        {
+            let mut writer = brotli::CompressorWriter::new(&mut compressed, 4096, 11, 22);
+            std::io::Write::write_all(&mut writer, data).unwrap();
+        }
+        compressed
+    }
+
+    #[test]
+    fn test_text_with_charset() {
+        let body = [0x63, 0x61, 0x66, 0xe9]; // café in ISO-8859-1
+        assert_eq!(
+            response(Some("text/plain; charset=ISO-8859-1"), &body)
+                .text()
+                .unwrap(),
+            "café"
+        );
+        assert_eq!(
+            response(Some("text/html; charset=\"utf-8\""), "café".as_bytes())
+                .text()
+                .unwrap(),
+            "café"
+        );
+        // Shift-JIS: こんにちは
+        let body = [0x82, 0xb1, 0x82, 0xf1, 0x82, 0xc9, 0x82, 0xbf, 0x82, 0xcd];
+        assert_eq!(
+            response(Some("text/plain;charset=Shift_JIS"), &body)
+                .text()
+                .unwrap(),
+            "こんにちは"
+        );
+    }
+
+    #[test]
+    fn test_text_errors() {
+        assert_eq!(
+            response(Some("text/plain; charset=unknown"), b"hello")
+                .text()
+                .unwrap_err(),
+            HttpError::InvalidCharset {
+                charset: "unknown".to_string()
+            }
+        );
+        assert_eq!(
+            response(Some("text/plain; charset=utf-8"), &[0x63, 0xe9])
+                .text()
+                .unwrap_err(),
+            HttpError::InvalidDecoding {
+                charset: "utf-8".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_text_sniffing() {
+        assert_eq!(
+            response(Some("text/plain"), &[0xEF, 0xBB, 0xBF, 0x68, 0x69])
+                .text()
+                .unwrap(),
+            "hi"
+        );
+        assert_eq!(
+            response(None, &[0xFF, 0xFE, 0x68, 0x00, 0x69, 0x00])
+                .text()
+                .unwrap(),
+            "hi"
+        );
+        let mut body = b"<html><head><meta charset=\"iso-8859-1\"></head><body>caf".to_vec();
+        body.push(0xe9);
+        assert!(response(Some("text/html"), &body)
+            .text()
+            .unwrap()
+            .ends_with("café"));
+        let mut body = b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?><name>caf".to_vec();
+        body.push(0xe9);
+        assert!(response(Some("application/xml"), &body)
+            .text()
+            .unwrap()
+            .ends_with("café"));
+        // invalid utf-8 without any declaration
+        assert_eq!(
+            response(Some("text/plain"), &[0x63, 0x61, 0x66, 0xe9])
+                .text()
+                .unwrap(),
+            "café"
+        );
+    }
+
+    #[test]
+    fn test_is_binary() {
+        assert!(!response(Some("application/json"), b"{}").is_binary());
+        assert!(!response(Some("application/problem+json; charset=utf-8"), b"{}").is_binary());
+        assert!(!response(Some("image/svg+xml"), b"<svg/>").is_binary());
+        assert!(!response(Some("Text/CSV"), b"a,b").is_binary());
+        assert!(response(Some("image/png"), &[0x89, 0x50, 0x4e, 0x47]).is_binary());
+        assert!(response(Some("application/octet-stream"), b"abc").is_binary());
+        assert!(response(None, &[0x50, 0x4b, 0x03, 0x04, 0x00]).is_binary());
+        assert!(!response(None, b"plain").is_binary());
+    }
+
+    #[test]
+    fn test_decompress() {
+        let mut response = encoded_response("gzip", &gzip(b"hello"));
+        response.decompress().unwrap();
+        assert_eq!(response.body, b"hello");
+
+        let mut encoder = libflate::zlib::Encoder::new(Vec::new()).unwrap();
+        std::io::Write::write_all(&mut encoder, b"hello").unwrap();
+        let mut response = encoded_response("deflate", &encoder.finish().into_result().unwrap());
+        response.decompress().unwrap();
+        assert_eq!(response.body, b"hello");
+
+        let mut response = encoded_response("br", &brotli(b"hello"));
+        response.decompress().unwrap();
+        assert_eq!(response.body, b"hello");
+
+        let mut response =
+            encoded_response("zstd", &zstd::stream::encode_all(&b"hello"[..], 3).unwrap());
+        response.decompress().unwrap();
+        assert_eq!(response.body, b"hello");
+
+        // gzip has been applied first, so it is decoded last
+        let mut response = encoded_response("gzip, br", &brotli(&gzip(b"hello")));
+        response.decompress().unwrap();
+        assert_eq!(response.body, b"hello");
+
+        let mut response = encoded_response("identity", b"hello");
+        response.decompress().unwrap();
+        assert_eq!(response.body, b"hello");
+    }
+
+    #[test]
+    fn test_decompress_errors() {
+        let mut response = encoded_response("compress", b"hello");
+        assert_eq!(
+            response.decompress().unwrap_err(),
+            HttpError::UnsupportedContentEncoding {
+                description: "compress".to_string()
+            }
+        );
+        assert_eq!(response.body, b"hello");
+
+        let mut response = encoded_response("gzip", b"hello");
+        assert!(matches!(
+            response.decompress().unwrap_err(),
+            HttpError::CouldNotUncompressResponse { .. }
+        ));
+        assert_eq!(response.body, b"hello");
+    }
+
+    #[test]
+    fn test_decode_max_size() {
+        let data = vec![b'a'; 1000];
+        assert_eq!(
+            ContentEncoding::Gzip.decode(&gzip(&data), 1000).unwrap(),
+            data
+        );
+        assert!(matches!(
+            ContentEncoding::Gzip.decode(&gzip(&data), 999).unwrap_err(),
+            HttpError::CouldNotUncompressResponse { .. }
+        ));
+        assert!(matches!(
+            ContentEncoding::Brotli
+                .decode(&brotli(&data), 999)
+                .unwrap_err(),
+            HttpError::CouldNotUncompressResponse { .. }
+        ));
+        let compressed = zstd::stream::encode_all(&data[..], 3).unwrap();
+        assert!(matches!(
+            ContentEncoding::Zstd.decode(&compressed, 999).unwrap_err(),
+            HttpError::CouldNotUncompressResponse { .. }
+        ));
+    }
+}
@@ -29,7 +29,8 @@ use std::time::Duration;
 ///
 /// See [`easy_ext::namelookup_time_t`], [`easy_ext::connect_time_t`], [`easy_ext::app_connect_time_t`],
 /// [`easy_ext::pre_transfert_time_t`], [`easy_ext::start_transfert_time_t`] and [`easy_ext::total_time_t`]
-/// for [`TimingInfo`] fields definition.
+/// for [`TimingInfo`] fields definition. The sizes and speeds of the same transfer are captured
+/// alongside, see [`easy_ext::size_upload_t`] and the related functions.
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct Timings {
     pub begin_call: DateTime<Utc>,
@@ -40,6 +41,14 @@ pub struct Timings {
     pub pre_transfer: Duration,
     pub start_transfer: Duration,
     pub total: Duration,
+    /// uploaded bytes, without the headers
+    pub size_upload: u64,
+    /// downloaded bytes, without the headers
+    pub size_download: u64,
+    /// average upload speed in bytes per second
+    pub speed_upload: u64,
+    /// average download speed in bytes per second
+    pub speed_download: u64,
 }
 
 impl Timings {
@@ -65,6 +74,10 @@ impl Timings {
         let total = easy_ext::total_time_t(easy)
             .or(easy.total_time())
             .unwrap_or(Duration::default());
+        let size_upload = easy_ext::size_upload_t(easy).unwrap_or_default();
+        let size_download = easy_ext::size_download_t(easy).unwrap_or_default();
+        let speed_upload = easy_ext::speed_upload_t(easy).unwrap_or_default();
+        let speed_download = easy_ext::speed_download_t(easy).unwrap_or_default();
         Timings {
             begin_call,
             end_call,
@@ -74,6 +87,10 @@ impl Timings {
             pre_transfer,
             start_transfer,
             total,
+            size_upload,
+            size_download,
+            speed_upload,
+            speed_download,
         }
     }
 }
@@ -0,0 +1,253 @@
+use chrono::{DateTime, Utc};
+use glob::Pattern;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::model::{CertificateInfo, TlsProfile};
+
+use super::certificate::Certificate;
+
+const SHA256_PREFIX: &str = "sha256//";
+
+lazy_static! {
+    // OpenSSL, GnuTLS, wolfSSL: "SSL connection using TLSv1.3 / TLS_AES_256_GCM_SHA384"
+    static ref SSL_CONNECTION_RE: Regex =
+        Regex::new(r"SSL connection using ([^\s/]+) / ([^\s/]+)").unwrap();
+    // Schannel, Secure Transport: "TLS 1.2 connection using TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256"
+    static ref TLS_CONNECTION_RE: Regex =
+        Regex::new(r"(TLS ?\d\.\d) connection using (\S+)").unwrap();
+}
+
+/// The negotiated protocol version and cipher of a tls connection
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TlsSession {
+    pub version: String,
+    pub cipher: String,
+}
+
+/// Parses the negotiated tls version and cipher from a libcurl debug text line, the format
+/// depends on the tls backend curl has been built with
+pub fn parse_tls_session(text: &str) -> Option<TlsSession> {
+    SSL_CONNECTION_RE
+        .captures(text)
+        .or_else(|| TLS_CONNECTION_RE.captures(text))
+        .map(|captures| TlsSession {
+            version: captures[1].to_string(),
+            cipher: captures[2].to_string(),
+        })
+}
+
+/// Returns a warning for every certificate that has expired or expires within the given number
+/// of days
+pub fn certificate_expiry_warnings(
+    certificates: &[Certificate],
+    days: u32,
+    now: DateTime<Utc>,
+) -> Vec<String> {
+    let threshold = now + chrono::Duration::days(days as i64);
+    certificates
+        .iter()
+        .filter(|certificate| certificate.expire_date <= threshold)
+        .map(|certificate| {
+            let expire_date = certificate.expire_date.format("%Y-%m-%d %H:%M:%S UTC");
+            if certificate.expire_date <= now {
+                format!(
+                    "The certificate '{}' has expired on {}.",
+                    certificate.subject, expire_date
+                )
+            } else {
+                format!(
+                    "The certificate '{}' expires on {}, within the next {} days.",
+                    certificate.subject, expire_date, days
+                )
+            }
+        })
+        .collect()
+}
+
+impl From<&Certificate> for CertificateInfo {
+    fn from(certificate: &Certificate) -> Self {
+        CertificateInfo {
+            subject: certificate.subject.clone(),
+            issuer: certificate.issuer.clone(),
+            start_date: certificate.start_date.to_rfc3339(),
+            expire_date: certificate.expire_date.to_rfc3339(),
+            serial_number: certificate.serial_number.clone(),
+        }
+    }
+}
+
+/// Returns the first profile whose host pattern matches the host of the url
+pub fn find_tls_profile<'a>(profiles: &'a [TlsProfile], url: &str) -> Option<&'a TlsProfile> {
+    let url = url::Url::parse(url).ok()?;
+    let host = url.host_str()?.to_lowercase();
+    let host_with_port = match url.port_or_known_default() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.clone(),
+    };
+    profiles.iter().find(|profile| {
+        let pattern = profile.host.trim().to_lowercase();
+        let target = if pattern.contains(':') {
+            &host_with_port
+        } else {
+            &host
+        };
+        match Pattern::new(&pattern) {
+            Ok(pattern) => pattern.matches(target),
+            Err(err) => {
+                log::warn!("Invalid host pattern of tls profile: '{}'", profile.host);
+                log::warn!("Pattern Error: {:?}", err);
+                false
+            }
+        }
+    })
+}
+
+/// Returns the pinned public keys in the format expected by curl
+pub fn pinned_public_keys(profile: &TlsProfile) -> Option<String> {
+    let keys: Vec<String> = profile
+        .pinned_public_keys
+        .iter()
+        .map(|key| key.trim())
+        .filter(|key| !key.is_empty())
+        .map(|key| match key.starts_with(SHA256_PREFIX) {
+            true => key.to_string(),
+            false => format!("{}{}", SHA256_PREFIX, key),
+        })
+        .collect();
+    if keys.is_empty() {
+        None
+    } else {
+        Some(keys.join(";"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use crate::model::CertificateFormat;
+
+    use super::*;
+
+    fn profile(host: &str) -> TlsProfile {
+        TlsProfile {
+            host: host.to_string(),
+            client_certificate: None,
+            client_certificate_format: CertificateFormat::Pem,
+            client_key: None,
+            passphrase: None,
+            ca_bundles: vec![],
+            pinned_public_keys: vec![],
+        }
+    }
+
+    #[test]
+    fn test_find_tls_profile() {
+        let profiles = vec![
+            profile("api.example.com:8443"),
+            profile("*.Internal.example.com"),
+        ];
+
+        let found = find_tls_profile(&profiles, "https://api.example.com:8443/users");
+        assert_eq!(
+            found.map(|profile| profile.host.as_str()),
+            Some("api.example.com:8443")
+        );
+        assert!(find_tls_profile(&profiles, "https://api.example.com/users").is_none());
+
+        let found = find_tls_profile(&profiles, "https://billing.internal.example.com/");
+        assert_eq!(
+            found.map(|profile| profile.host.as_str()),
+            Some("*.Internal.example.com")
+        );
+        assert!(find_tls_profile(&profiles, "https://example.com/").is_none());
+    }
+
+    #[test]
+    fn test_pinned_public_keys() {
+        let mut profile = profile("example.com");
+        assert_eq!(pinned_public_keys(&profile), None);
+
+        profile.pinned_public_keys = vec![
+            "sha256//YhKJKSzoTt2b5FP18fvpHo7fJYqQCjAa3HWY3tvRMwE=".to_string(),
+            "LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ=".to_string(),
+        ];
+        assert_eq!(
+            pinned_public_keys(&profile).unwrap(),
+            "sha256//YhKJKSzoTt2b5FP18fvpHo7fJYqQCjAa3HWY3tvRMwE=;sha256//LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ="
+        );
+    }
+
+    #[test]
+    fn test_parse_tls_session() {
+        assert_eq!(
+            parse_tls_session("SSL connection using TLSv1.3 / TLS_AES_256_GCM_SHA384"),
+            Some(TlsSession {
+                version: "TLSv1.3".to_string(),
+                cipher: "TLS_AES_256_GCM_SHA384".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_tls_session(
+                "SSL connection using TLSv1.2 / ECDHE-RSA-AES128-GCM-SHA256 / X25519 / RSASSA-PSS"
+            ),
+            Some(TlsSession {
+                version: "TLSv1.2".to_string(),
+                cipher: "ECDHE-RSA-AES128-GCM-SHA256".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_tls_session("TLS 1.2 connection using TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256"),
+            Some(TlsSession {
+                version: "TLS 1.2".to_string(),
+                cipher: "TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_tls_session("Connected to localhost (::1) port 443"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_certificate_expiry_warnings() {
+        let now = Utc.with_ymd_and_hms(2023, 6, 1, 12, 0, 0).unwrap();
+        let certificate = |subject: &str, expire_date: DateTime<Utc>| Certificate {
+            subject: subject.to_string(),
+            issuer: "CN=Root".to_string(),
+            start_date: Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap(),
+            expire_date,
+            serial_number: "01".to_string(),
+        };
+        let certificates = vec![
+            certificate(
+                "CN=soon",
+                Utc.with_ymd_and_hms(2023, 6, 20, 0, 0, 0).unwrap(),
+            ),
+            certificate(
+                "CN=later",
+                Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            ),
+            certificate(
+                "CN=expired",
+                Utc.with_ymd_and_hms(2023, 5, 1, 0, 0, 0).unwrap(),
+            ),
+        ];
+
+        assert_eq!(
+            certificate_expiry_warnings(&certificates, 30, now),
+            vec![
+                "The certificate 'CN=soon' expires on 2023-06-20 00:00:00 UTC, within the next 30 days."
+                    .to_string(),
+                "The certificate 'CN=expired' has expired on 2023-05-01 00:00:00 UTC.".to_string(),
+            ]
+        );
+        assert_eq!(
+            certificate_expiry_warnings(&certificates, 10, now),
+            vec![
+                "The certificate 'CN=expired' has expired on 2023-05-01 00:00:00 UTC.".to_string()
+            ]
+        );
+    }
+}
@@ -1,28 +1,41 @@
 mod drag_and_drop;
+use crate::client::client_model::Response;
 use crate::client::error::HttpError;
+use crate::client::oauth::clear_token_cache;
 use crate::client::options::ClientOptions;
 use crate::client::Client;
 use crate::config::{
-    get_log_filepath, load_collection_config, save_collection_config, save_workspace,
+    get_log_filepath, load_client_settings, load_client_settings_or_default,
+    load_collection_config, save_client_settings, save_collection_config, save_workspace,
 };
 use crate::cookie_jar::{
     load_cookie_jar, save_cookie_jar, update_cookie_jar, update_request_with_cookie_jar,
     CookieJarPath, GetCookieJarParams, SaveCookieJarParams,
 };
+use crate::data_file::{
+    apply_data_row, load_data_rows, remove_data_row, remove_data_row_values, DataRow,
+};
 use crate::error::RelynxError;
+use crate::history::{self, HistoryEntry, HistoryEntrySummary, HistoryQuery, HistorySettings};
 use crate::import::{
     create_jetbrains_collection, import_jetbrains_folder, postman, LoadRequestsResult,
     RELYNX_IGNORE_FILE,
 };
 use crate::license::{self, verify_signature};
 use crate::model::{
-    AddCollectionsResult, AppEnvironment, Collection, CollectionConfig, CookieJar, Environment,
-    ImportCollectionResult, RequestModel, RequestResult, RunLogger, RunRequestCommand,
-    SaveRequestCommand, Workspace,
+    AddCollectionsResult, AppEnvironment, BinaryContent, BinaryPayload, CallTimings,
+    ClientSettings, Collection, CollectionConfig, CookieJar, Environment, ImportCollectionResult,
+    RedirectHop, RequestAuth, RequestModel, RequestResult, RunCollectionCommand,
+    RunCollectionIteration, RunCollectionProgress, RunCollectionResult, RunLogger,
+    RunRequestCommand, SaveRequestCommand, SentRequest, TlsProfile, Workspace,
 };
 use crate::pathdiff::diff_paths;
+use crate::runner::execute_and_handle;
 use crate::sanitize::sanitize_filename_with_options;
 use crate::tree::{GroupOptions, RequestTreeNode, DEFAULT_OPTIONS};
+use crate::vault::{self, VaultStatus};
+use base64::engine::general_purpose;
+use base64::Engine;
 pub use drag_and_drop::{
     drag_and_drop, reorder_nodes_within_parent, DragAndDropParams, DragAndDropResult,
     ReorderNodesParams,
@@ -31,6 +44,7 @@ use http_rest_file::{
     model::{HttpRestFile, HttpRestFileExtension, Request},
     Serializer,
 };
+use lazy_static::lazy_static;
 use license::LicenseData;
 use serde::{Deserialize, Serialize};
 
@@ -265,20 +279,57 @@ pub fn import_jetbrains_folder_command(
 }
 
 #[tauri::command]
-pub fn run_request(mut request_command: RunRequestCommand) -> Result<RequestResult, rspc::Error> {
+pub fn run_request(request_command: RunRequestCommand) -> Result<RequestResult, rspc::Error> {
+    execute_request(request_command, None).map_err(Into::into)
+}
+
+/// Sends a request and records it within the history. The `variables` are the data row and the
+/// globals of a collection run without environment. They are resolved like environment variables
+/// but are neither recorded nor returned, globals set by the scripts are written back into them.
+fn execute_request(
+    mut request_command: RunRequestCommand,
+    variables: Option<&mut DataRow>,
+) -> Result<RequestResult, RelynxError> {
     // @TODO: cookie input file...
     // @TODO: handle intellij redirect options
     let mut client = Client::new(None);
+    // the history keeps the request as defined, before auth and cookies are applied
+    let history_request = request_command.request.clone();
     let follow_location = !request_command
         .request
         .settings
         .no_redirect
         .unwrap_or(false);
 
-    let options = ClientOptions {
+    let mut options = ClientOptions {
         follow_location,
+        collection_path: Some(request_command.collection.path.clone()),
         ..Default::default()
     };
+    let (client_settings, client_settings_warning) =
+        load_client_settings_or_default(&request_command.collection.path);
+    client_settings.defaults.apply_to(&mut options);
+    options.tls_profiles = client_settings.tls_profiles;
+    if let Some(ssl_configuration) = request_command
+        .environment
+        .as_ref()
+        .and_then(|environment| environment.ssl_configuration.as_ref())
+    {
+        ssl_configuration.apply_to(&mut options);
+    }
+    request_command
+        .request
+        .client_settings
+        .apply_to(&mut options);
+
+    // requests without an auth of their own use the one of their closest group
+    if request_command.request.auth.is_none() {
+        let collection_config =
+            load_collection_config(&request_command.collection.get_config_file_path())
+                .unwrap_or_default();
+        request_command.request.auth = collection_config
+            .resolve_auth(&request_command.request, &request_command.collection.path);
+    }
 
     let mut cookie_jar: Option<CookieJar> = None;
 
@@ -289,7 +340,7 @@ pub fn run_request(mut request_command: RunRequestCommand) -> Result<RequestResu
         .unwrap_or(false);
 
     let no_log = request_command.request.settings.no_log.unwrap_or(false);
-    let logger = RunLogger::new(no_log);
+    let logger = RunLogger::new(no_log, request_command.environment.as_ref());
 
     if allow_cookie_jar {
         cookie_jar = load_cookie_jar(&CookieJarPath::CollectionFolderPath(
@@ -306,43 +357,59 @@ pub fn run_request(mut request_command: RunRequestCommand) -> Result<RequestResu
         }
     }
 
-    let calls = client
-        .execute(
-            &request_command.request,
-            &options,
-            request_command.environment.as_ref(),
-            &logger,
-        )
-        .map_err(|http_err: HttpError| {
-            if !no_log {
-                logger.log_error(format!("Http error occurred: {:?}", http_err));
-                logger.log_error(format!("Option: {:?}", options));
-                logger.log_error(format!("Request: {:?}", request_command.request));
-                if let Some(environment) = request_command.environment {
-                    // @TODO
-                    log::error!("Environment name: {}", environment.name);
-                    log::error!("Public environment values: {:?}", environment.variables);
-                    log::error!(
-                        "Secret names (values are not logged!) {:?}",
-                        environment.secrets.into_iter().map(|secret| secret.name)
-                    );
-                } else {
-                    log::error!("Environment: None");
-                }
+    let mut variables_environment = variables
+        .as_ref()
+        .map(|variables| apply_data_row(None, variables));
+    let handled = execute_and_handle(
+        &mut client,
+        &request_command.request,
+        &options,
+        request_command
+            .environment
+            .as_mut()
+            .or(variables_environment.as_mut()),
+        &logger,
+    )
+    .map_err(|http_err: HttpError| {
+        if !no_log {
+            logger.log_error(format!("Http error occurred: {:?}", http_err));
+            logger.log_error(format!("Option: {:?}", options));
+            logger.log_error(format!("Request: {:?}", request_command.request));
+            if let Some(ref environment) = request_command.environment {
+                logger.log_error(format!("Environment name: {}", environment.name));
+                logger.log_error(format!(
+                    "Public environment values: {:?}",
+                    environment.variables
+                ));
+                logger.log_error(format!(
+                    "Secret names (values are not logged!) {:?}",
+                    environment
+                        .secrets
+                        .iter()
+                        .map(|secret| &secret.name)
+                        .collect::<Vec<&String>>()
+                ));
+            } else {
+                logger.log_error("Environment: None");
             }
-            if !no_log {
-                match http_err {
-                    HttpError::InvalidUrl(url) => RelynxError::RequestSendErrorWithMsg(format!(
-                        "Something is wrong with the url: {}",
-                        url
-                    )),
-                    _ => RelynxError::RequestSendErrorGeneric,
+        }
+        if !no_log {
+            match http_err {
+                HttpError::InvalidUrl(url) => RelynxError::RequestSendErrorWithMsg(format!(
+                    "Something is wrong with the url: {}",
+                    url
+                )),
+                HttpError::PreRequestScript(_) => {
+                    RelynxError::RequestSendErrorWithMsg(http_err.to_string())
                 }
-            } else {
-                RelynxError::RequestSendErrorGeneric
+                _ => RelynxError::RequestSendErrorGeneric,
             }
-        })?;
+        } else {
+            RelynxError::RequestSendErrorGeneric
+        }
+    })?;
 
+    let calls = handled.calls;
     let call = calls.last().unwrap();
 
     #[allow(clippy::unnecessary_unwrap)]
@@ -365,14 +432,13 @@ pub fn run_request(mut request_command: RunRequestCommand) -> Result<RequestResu
         }
     }
 
-    // @TODO: what if it is not utf, what if the result is just binary, then displaying it would
-    // not make sense, maybe add an option that you don't want to see the actual result together
-    // with the redirect options
     let mut request_result = RequestResult {
         id: uuid::Uuid::new_v4().to_string(),
-        result: String::from_utf8(call.response.body.to_vec()).unwrap_or_default(), // @TODO: handle non
-        // utf8 result
+        result: String::new(),
+        charset: None,
+        binary: None,
         status_code: call.response.status.to_string(),
+        http_version: call.response.http_version(),
         // @TODO @CHECK is it secs or millis?
         total_time: call.timings.total.as_secs_f64(), // @TODO check how fine grained this should be
         content_type: call
@@ -383,10 +449,56 @@ pub fn run_request(mut request_command: RunRequestCommand) -> Result<RequestResu
             .map(|h| h.value.clone()),
         // @TODO: @CHECK why is it f64?
         total_result_size: call.response.body.len() as f64,
-        warnings: vec![],
+        raw_result_size: call.response.raw_body_size as f64,
+        warnings: client_settings_warning
+            .into_iter()
+            .chain(handled.warnings)
+            .collect(),
         result_file: None,
         result_file_folder: None,
+        environment: None,
+        tests: handled.tests,
+        tls: call.response.tls_info(),
+        timings: calls.iter().map(CallTimings::from).collect(),
+        headers: call.response.headers.clone(),
+        redirects: calls[..calls.len() - 1]
+            .iter()
+            .map(RedirectHop::from)
+            .collect(),
+        sent_request: SentRequest::from(&call.request),
     };
+    if call.response.is_binary() {
+        request_result.binary = Some(binary_payload(&call.response));
+    } else {
+        match call.response.text() {
+            Ok(text) => {
+                request_result.result = text;
+                request_result.charset = call.response.charset();
+            }
+            Err(err) => {
+                logger.log_error(format!("Could not decode response: {:?}", err));
+                request_result.result = String::from_utf8_lossy(&call.response.body).to_string();
+                request_result.warnings.push(format!(
+                    "{}. The response has been decoded as UTF-8 with invalid characters replaced.",
+                    err
+                ));
+            }
+        }
+    }
+    if let Some(handler_error) = handled.handler_error {
+        request_result.warnings.push(handler_error);
+    }
+    if let (Some(variables), Some(variables_environment)) = (variables, variables_environment) {
+        let updated: DataRow = variables_environment
+            .variables
+            .into_iter()
+            .filter_map(|variable| Some((variable.name, variable.current_value?)))
+            .collect();
+        if updated != *variables {
+            request_result.warnings.push("The scripts set global variables, they are passed on to the following requests of the run but not stored as no environment is selected.".to_string());
+        }
+        *variables = updated;
+    }
 
     if let Some(ref save_response) = request_command.request.save_response {
         if save_response.is_path_empty() {
@@ -398,7 +510,10 @@ pub fn run_request(mut request_command: RunRequestCommand) -> Result<RequestResu
                 .unwrap_or(PathBuf::from("request_result"));
 
             // @TODO: emit a warning if we could not save the file
-            let result = std::fs::write(&absolute_path, &call.response.body);
+            let result = std::fs::write(
+                &absolute_path,
+                logger.redactor().redact_bytes(&call.response.body),
+            );
             request_result.result_file = Some(absolute_path.clone());
             let parent_path = absolute_path.parent();
             request_result.result_file_folder = parent_path.map(|p| p.to_path_buf());
@@ -416,9 +531,208 @@ pub fn run_request(mut request_command: RunRequestCommand) -> Result<RequestResu
             }
         }
     }
+    if !no_log {
+        record_history(
+            &request_command.collection,
+            &history_request,
+            request_command.environment.as_ref(),
+            &mut request_result,
+            &logger,
+        );
+    }
+    request_result.environment = request_command.environment;
     Ok(request_result)
 }
 
+/// Adds the result to the history of the collection, a failure only results in a warning as the
+/// request itself succeeded
+fn record_history(
+    collection: &Collection,
+    request: &RequestModel,
+    environment: Option<&Environment>,
+    request_result: &mut RequestResult,
+    logger: &RunLogger,
+) {
+    let recorded = history::load_history_settings(collection).and_then(|settings| {
+        if !settings.enabled {
+            return Ok(());
+        }
+        let entry = history::history_entry(
+            request,
+            environment,
+            request_result,
+            &settings,
+            logger.redactor(),
+        );
+        history::record_history_entry(collection, &entry, &settings)
+    });
+    if let Err(err) = recorded {
+        logger.log_error(format!("Could not record history entry: {:?}", err));
+        request_result
+            .warnings
+            .push("Could not add the request to the history.".to_string());
+    }
+}
+
+/// Binary bodies up to this size are passed base64 encoded to the frontend
+const MAX_INLINE_BINARY_SIZE: usize = 5 * 1024 * 1024;
+
+lazy_static! {
+    /// Folder of this session for the bodies of large binary responses, it is removed when the
+    /// app exits
+    static ref RESPONSE_TEMP_DIR: PathBuf =
+        std::env::temp_dir().join(format!("relynx-responses-{}", uuid::Uuid::new_v4()));
+}
+
+/// Returns the body of a binary response, large bodies are written to a temporary file
+fn binary_payload(response: &Response) -> BinaryPayload {
+    let inline_content = || BinaryContent::Base64(general_purpose::STANDARD.encode(&response.body));
+    let content = if response.body.len() > MAX_INLINE_BINARY_SIZE {
+        let path = RESPONSE_TEMP_DIR.join(uuid::Uuid::new_v4().to_string());
+        match std::fs::create_dir_all(&*RESPONSE_TEMP_DIR)
+            .and_then(|_| std::fs::write(&path, &response.body))
+        {
+            Ok(_) => BinaryContent::File(path),
+            Err(err) => {
+                log::error!("Could not write binary response to: {}", path.display());
+                log::error!("Io Error: {:?}", err);
+                inline_content()
+            }
+        }
+    } else {
+        inline_content()
+    };
+    BinaryPayload {
+        mime_type: response.mime_type(),
+        size: response.body.len() as f64,
+        content,
+    }
+}
+
+/// Removes the temporary files of large binary responses, called when the app exits
+pub fn remove_response_temp_files() {
+    if !RESPONSE_TEMP_DIR.exists() {
+        return;
+    }
+    if let Err(err) = std::fs::remove_dir_all(&*RESPONSE_TEMP_DIR) {
+        log::error!(
+            "Could not remove temporary response files: {}",
+            RESPONSE_TEMP_DIR.display()
+        );
+        log::error!("Io Error: {:?}", err);
+    }
+}
+
+pub const RUN_COLLECTION_PROGRESS_EVENT: &str = "run_collection_progress";
+
+/// Runs all requests of a collection or one of its groups one after another in the order in which
+/// they are displayed. The requests share the collection's cookie jar and global variables set by
+/// a response handler are visible to the following requests. If a data file is given the requests
+/// are run once for each of its rows with the row's values as additional variables.
+/// After each request a [`RunCollectionProgress`] event is emitted.
+#[tauri::command]
+pub fn run_collection(command: RunCollectionCommand) -> Result<RunCollectionResult, rspc::Error> {
+    let RunCollectionCommand {
+        collection,
+        group_path,
+        mut environment,
+        data_file,
+    } = command;
+
+    let load_result = crate::import::load_requests_for_collection(&collection)?;
+    let root = &load_result.request_tree.root;
+    let group = match group_path {
+        Some(ref group_path) => root.find_by_path(group_path).ok_or_else(|| {
+            log::error!(
+                "Could not find group to run: '{}' in collection: '{}'",
+                group_path.display(),
+                collection.path.display()
+            );
+            RelynxError::RunCollectionGroupNotFound(group_path.to_string_lossy().to_string())
+        })?,
+        None => root,
+    };
+    let requests: Vec<RequestModel> = group.requests().into_iter().cloned().collect();
+    let total = requests.len() as u32;
+
+    let rows: Vec<DataRow> = match data_file {
+        Some(ref data_file) => load_data_rows(data_file)?,
+        None => vec![DataRow::new()],
+    };
+    let total_iterations = rows.len() as u32;
+
+    // without an environment the globals set by scripts are kept in a map of their own so they
+    // are passed on to the following requests without being stored
+    let mut globals = DataRow::new();
+
+    let mut iterations: Vec<RunCollectionIteration> = Vec::new();
+    for (iteration, row) in rows.into_iter().enumerate() {
+        let mut runs: Vec<RunCollectionProgress> = Vec::new();
+        for (index, request) in requests.iter().enumerate() {
+            let mut progress = RunCollectionProgress {
+                iteration: iteration as u32,
+                total_iterations,
+                index: index as u32,
+                total,
+                request_id: request.id.clone(),
+                request_name: request.name.clone(),
+                rest_file_path: request.rest_file_path.clone(),
+                result: None,
+                error: None,
+            };
+            let (request_environment, mut variables) = match environment {
+                Some(ref environment) if row.is_empty() => (Some(environment.clone()), None),
+                Some(ref environment) => (Some(apply_data_row(Some(environment), &row)), None),
+                None => {
+                    let mut variables = globals.clone();
+                    variables.extend(row.clone());
+                    (None, Some(variables))
+                }
+            };
+            let request_command = RunRequestCommand {
+                collection: collection.clone(),
+                request: request.clone(),
+                environment: request_environment,
+            };
+            match execute_request(request_command, variables.as_mut()) {
+                Ok(mut result) => {
+                    // the environment is only returned once with the result of the whole run
+                    if let (Some(updated), Some(environment)) =
+                        (result.environment.take(), environment.as_mut())
+                    {
+                        *environment = remove_data_row(updated, environment, &row);
+                    }
+                    if let Some(variables) = variables {
+                        globals = remove_data_row_values(variables, &globals, &row);
+                    }
+                    progress.result = Some(result);
+                }
+                Err(err) => progress.error = Some(err.to_string()),
+            }
+
+            if let Some(ref app_handle) = RELYNX_CONTEXT.lock().unwrap().app_handle {
+                let _ = app_handle
+                    .emit_all(RUN_COLLECTION_PROGRESS_EVENT, progress.clone())
+                    .map_err(|err| {
+                        log::error!("Could not emit collection run progress");
+                        log::error!("Tauri Error: {:?}", err);
+                    });
+            }
+            runs.push(progress);
+        }
+        iterations.push(RunCollectionIteration {
+            index: iteration as u32,
+            data: row,
+            runs,
+        });
+    }
+
+    Ok(RunCollectionResult {
+        iterations,
+        environment,
+    })
+}
+
 #[tauri::command]
 pub fn save_request(command: SaveRequestCommand) -> Result<PathBuf, rspc::Error> {
     let SaveRequestCommand {
@@ -474,6 +788,31 @@ pub fn save_request(command: SaveRequestCommand) -> Result<PathBuf, rspc::Error>
         return Err(RelynxError::SaveRequestError.into());
     }
 
+    if requests
+        .iter()
+        .filter_map(|request| request.auth.as_ref())
+        .any(RequestAuth::has_plaintext_credentials)
+    {
+        log::error!("Could not save request as its auth contains plaintext credentials");
+        return Err(RelynxError::PlaintextAuthCredentials.into());
+    }
+
+    // the auth is not part of the request file, it is stored within the collection config
+    let mut collection_config =
+        load_collection_config(&collection.get_config_file_path()).unwrap_or_default();
+    collection_config.auth.requests.remove(&old_path);
+    collection_config.set_request_auth(&file_path, &requests);
+    collection_config.request_ids.remove(&old_path);
+    collection_config.set_request_ids(&file_path, &requests);
+    // a malformed client settings file is not overwritten
+    let client_settings = load_client_settings(&collection.path)
+        .ok()
+        .map(|mut client_settings| {
+            client_settings.requests.remove(&old_path);
+            client_settings.set_request_settings(&file_path, &requests);
+            client_settings
+        });
+
     let requests: Vec<Request> = requests.into_iter().map(Into::into).collect();
 
     let file_model = http_rest_file::model::HttpRestFile {
@@ -505,6 +844,21 @@ pub fn save_request(command: SaveRequestCommand) -> Result<PathBuf, rspc::Error>
         })?;
     }
 
+    // the request itself is saved, so we do not return an error if only the auth could not be
+    // stored
+    let ignored = save_collection_config(&collection_config, &collection.get_config_file_path());
+    if ignored.is_err() {
+        log::error!("Could not save collection config after saving request!");
+        log::error!("Io Error: {:?}", ignored.unwrap_err());
+    }
+    if let Some(client_settings) = client_settings {
+        let ignored = save_client_settings(client_settings, &collection.path);
+        if ignored.is_err() {
+            log::error!("Could not save client settings after saving request!");
+            log::error!("Io Error: {:?}", ignored.unwrap_err());
+        }
+    }
+
     Ok(dbg!(file_path))
 }
 
@@ -902,6 +1256,9 @@ pub fn delete_node(params: DeleteNodeParams) -> Result<(), rspc::Error> {
     let mut collection_config =
         load_collection_config(&collection.get_config_file_path()).unwrap_or_default();
     collection_config.path_orders.remove(&node.filepath);
+    collection_config.auth.groups.remove(&node.filepath);
+    collection_config.auth.requests.remove(&node.filepath);
+    collection_config.request_ids.remove(&node.filepath);
     // here we do not want to return an error as the actual deletion worked, just the config could
     // not be updated which is not critical
     let ignored = save_collection_config(&collection_config, &collection.get_config_file_path());
@@ -909,10 +1266,134 @@ pub fn delete_node(params: DeleteNodeParams) -> Result<(), rspc::Error> {
         log::error!("Could not save collection config after deleting node!");
         log::error!("Io Error: {:?}", ignored.unwrap_err());
     }
+    if let Ok(mut client_settings) = load_client_settings(&collection.path) {
+        if client_settings.requests.remove(&node.filepath).is_some() {
+            let ignored = save_client_settings(client_settings, &collection.path);
+            if ignored.is_err() {
+                log::error!("Could not save client settings after deleting node!");
+                log::error!("Io Error: {:?}", ignored.unwrap_err());
+            }
+        }
+    }
 
     Ok(())
 }
 
+/// Returns the client settings of a collection which are used for all of its requests unless a
+/// request overrides them
+#[tauri::command]
+pub fn get_client_settings(collection: Collection) -> Result<ClientSettings, rspc::Error> {
+    load_client_settings(&collection.path)
+        .map(|settings| settings.defaults)
+        .map_err(Into::into)
+}
+
+#[derive(Serialize, Deserialize, rspc::Type, Debug)]
+pub struct SaveClientSettingsParams {
+    collection: Collection,
+    settings: ClientSettings,
+}
+
+#[tauri::command]
+pub fn save_client_settings_command(params: SaveClientSettingsParams) -> Result<(), rspc::Error> {
+    let mut client_settings = load_client_settings(&params.collection.path)?;
+    client_settings.defaults = params.settings;
+    save_client_settings(client_settings, &params.collection.path).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn get_tls_profiles(collection: Collection) -> Result<Vec<TlsProfile>, rspc::Error> {
+    load_client_settings(&collection.path)
+        .map(|settings| settings.tls_profiles)
+        .map_err(Into::into)
+}
+
+#[derive(Serialize, Deserialize, rspc::Type, Debug)]
+pub struct SaveTlsProfilesParams {
+    collection: Collection,
+    tls_profiles: Vec<TlsProfile>,
+}
+
+#[tauri::command]
+pub fn save_tls_profiles(params: SaveTlsProfilesParams) -> Result<(), rspc::Error> {
+    if params
+        .tls_profiles
+        .iter()
+        .any(TlsProfile::has_plaintext_passphrase)
+    {
+        log::error!("Could not save tls profiles as a passphrase is a plaintext value");
+        return Err(RelynxError::PlaintextTlsPassphrase.into());
+    }
+    let mut client_settings = load_client_settings(&params.collection.path)?;
+    client_settings.tls_profiles = params.tls_profiles;
+    save_client_settings(client_settings, &params.collection.path).map_err(Into::into)
+}
+
+#[derive(Serialize, Deserialize, rspc::Type, Debug)]
+pub struct GetGroupAuthParams {
+    collection: Collection,
+    group_path: PathBuf,
+}
+
+/// Returns the auth stored for a folder or file group, requests within it use this auth unless
+/// they define one themselves
+#[tauri::command]
+pub fn get_group_auth(params: GetGroupAuthParams) -> Result<Option<RequestAuth>, rspc::Error> {
+    let collection_config = load_collection_config(&params.collection.get_config_file_path())?;
+    Ok(collection_config
+        .auth
+        .groups
+        .get(&params.group_path)
+        .cloned())
+}
+
+#[derive(Serialize, Deserialize, rspc::Type, Debug)]
+pub struct SaveGroupAuthParams {
+    collection: Collection,
+    group_path: PathBuf,
+    auth: Option<RequestAuth>,
+}
+
+#[tauri::command]
+pub fn save_group_auth(params: SaveGroupAuthParams) -> Result<(), rspc::Error> {
+    let SaveGroupAuthParams {
+        collection,
+        group_path,
+        auth,
+    } = params;
+    if !group_path.starts_with(&collection.path) {
+        log::error!(
+            "Could not save auth of group: '{}' as it is not within collection: '{}'",
+            group_path.display(),
+            collection.path.display()
+        );
+        return Err(RelynxError::InvalidCollectionConfig(
+            collection
+                .get_config_file_path()
+                .to_string_lossy()
+                .to_string(),
+        )
+        .into());
+    }
+
+    if auth
+        .as_ref()
+        .map(RequestAuth::has_plaintext_credentials)
+        .unwrap_or(false)
+    {
+        log::error!("Could not save auth of group as it contains plaintext credentials");
+        return Err(RelynxError::PlaintextAuthCredentials.into());
+    }
+
+    let mut collection_config = load_collection_config(&collection.get_config_file_path())?;
+    match auth {
+        Some(auth) => collection_config.auth.groups.insert(group_path, auth),
+        None => collection_config.auth.groups.remove(&group_path),
+    };
+    save_collection_config(&collection_config, &collection.get_config_file_path())
+        .map_err(Into::into)
+}
+
 #[tauri::command]
 pub fn load_environments(collection_path: PathBuf) -> Result<Vec<Environment>, rspc::Error> {
     crate::environment::load_environments(collection_path).map_err(Into::into)
@@ -930,6 +1411,109 @@ pub fn save_environments(params: SaveEnvironmentsParams) -> Result<(), rspc::Err
         .map_err(Into::into)
 }
 
+#[derive(Serialize, Deserialize, rspc::Type, Debug)]
+pub struct UnlockVaultParams {
+    passphrase: String,
+}
+
+#[tauri::command]
+pub fn get_vault_status() -> Result<VaultStatus, rspc::Error> {
+    Ok(vault::get_vault_status())
+}
+
+#[tauri::command]
+pub fn unlock_vault(params: UnlockVaultParams) -> Result<(), rspc::Error> {
+    vault::unlock_vault(&params.passphrase).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn lock_vault() -> Result<(), rspc::Error> {
+    vault::lock_vault();
+    Ok(())
+}
+
+/// Forgets all acquired OAuth 2.0 tokens, e.g. to log in with another user
+#[tauri::command]
+pub fn clear_auth_tokens() -> Result<(), rspc::Error> {
+    clear_token_cache();
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize, rspc::Type, Debug)]
+pub struct ListHistoryParams {
+    collection: Collection,
+    query: HistoryQuery,
+}
+
+#[tauri::command]
+pub fn list_history(params: ListHistoryParams) -> Result<Vec<HistoryEntrySummary>, rspc::Error> {
+    history::list_history(&params.collection, &params.query).map_err(Into::into)
+}
+
+#[derive(Serialize, Deserialize, rspc::Type, Debug)]
+pub struct HistoryEntryParams {
+    collection: Collection,
+    entry_id: String,
+}
+
+#[tauri::command]
+pub fn get_history_entry(params: HistoryEntryParams) -> Result<HistoryEntry, rspc::Error> {
+    history::load_history_entry(&params.collection, &params.entry_id).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn delete_history_entry(params: HistoryEntryParams) -> Result<(), rspc::Error> {
+    history::delete_history_entry(&params.collection, &params.entry_id).map_err(Into::into)
+}
+
+#[derive(Serialize, Deserialize, rspc::Type, Debug)]
+pub struct RerunHistoryEntryParams {
+    collection: Collection,
+    entry_id: String,
+    /// the environment to run the request with, the one of the entry may have changed since
+    environment: Option<Environment>,
+}
+
+/// Runs the request of a history entry again, as it has been at the time of the entry. The auth
+/// is not part of the history, the current auth of the request or its group is used.
+#[tauri::command]
+pub fn rerun_history_entry(params: RerunHistoryEntryParams) -> Result<RequestResult, rspc::Error> {
+    let mut request = history::load_history_entry(&params.collection, &params.entry_id)?.request;
+    let collection_config =
+        load_collection_config(&params.collection.get_config_file_path()).unwrap_or_default();
+    request.auth = collection_config.request_auth(&request);
+    execute_request(
+        RunRequestCommand {
+            collection: params.collection,
+            request,
+            environment: params.environment,
+        },
+        None,
+    )
+    .map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn clear_history(collection: Collection) -> Result<(), rspc::Error> {
+    history::clear_history(&collection).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn get_history_settings(collection: Collection) -> Result<HistorySettings, rspc::Error> {
+    history::load_history_settings(&collection).map_err(Into::into)
+}
+
+#[derive(Serialize, Deserialize, rspc::Type, Debug)]
+pub struct SaveHistorySettingsParams {
+    collection: Collection,
+    settings: HistorySettings,
+}
+
+#[tauri::command]
+pub fn save_history_settings(params: SaveHistorySettingsParams) -> Result<(), rspc::Error> {
+    history::save_history_settings(&params.collection, &params.settings).map_err(Into::into)
+}
+
 #[tauri::command]
 pub fn get_response_filepath(request_path: PathBuf) -> Result<Option<PathBuf>, rspc::Error> {
     let request_folder = request_path.parent().ok_or({
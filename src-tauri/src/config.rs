@@ -2,11 +2,13 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::error::RelynxError;
-use crate::model::{CollectionConfig, Workspace};
+use crate::model::{CollectionClientSettings, CollectionConfig, Workspace};
 use directories::ProjectDirs;
 
 pub const WORKSPACE_FILENAME: &str = "workspace.json";
 pub const COLLECTION_CONFIGFILE: &str = "relynx.collection.json";
+/// Client settings of a collection and its requests, stored next to the collection config
+pub const CLIENT_SETTINGS_FILE: &str = "relynx.client.json";
 
 fn get_dirs() -> Option<ProjectDirs> {
     ProjectDirs::from("app", "relynx", "relynx")
@@ -169,3 +171,71 @@ pub fn save_collection_config(
         RelynxError::SerializeCollectionConfigError(collection_config.name.clone())
     })
 }
+
+/// Loads the client settings of a collection, all paths are resolved against the collection
+/// folder. If the collection has no client settings file the default settings are returned.
+pub fn load_client_settings(
+    collection_path: &Path,
+) -> Result<CollectionClientSettings, RelynxError> {
+    let settings_path = collection_path.join(CLIENT_SETTINGS_FILE);
+    if !settings_path.exists() {
+        return Ok(CollectionClientSettings::default());
+    }
+    let content = std::fs::read_to_string(&settings_path).map_err(|err| {
+        log::error!(
+            "Could not read client settings file: '{}'",
+            settings_path.display()
+        );
+        log::error!("Io Error: {:?}", err);
+        RelynxError::LoadClientSettingsError(settings_path.to_string_lossy().to_string())
+    })?;
+    let settings: CollectionClientSettings = serde_json::from_str(&content).map_err(|err| {
+        log::error!(
+            "Could not deserialize client settings file: '{}'",
+            settings_path.display()
+        );
+        log::error!("Serde Error: {:?}", err);
+        RelynxError::InvalidClientSettings(
+            settings_path.to_string_lossy().to_string(),
+            err.to_string(),
+        )
+    })?;
+    Ok(settings.with_absolute_paths(collection_path))
+}
+
+/// Same as [`load_client_settings`] but the default settings are used if the file cannot be
+/// loaded, the error is returned as a warning so requests can still be sent
+pub fn load_client_settings_or_default(
+    collection_path: &Path,
+) -> (CollectionClientSettings, Option<String>) {
+    match load_client_settings(collection_path) {
+        Ok(settings) => (settings, None),
+        Err(err) => (
+            CollectionClientSettings::default(),
+            Some(format!("{} The default client settings are used.", err)),
+        ),
+    }
+}
+
+/// Saves the client settings of a collection, paths within the collection are stored relative to
+/// it so the file can be shared
+pub fn save_client_settings(
+    settings: CollectionClientSettings,
+    collection_path: &Path,
+) -> Result<(), RelynxError> {
+    let settings_path = collection_path.join(CLIENT_SETTINGS_FILE);
+    let settings = settings.with_relative_paths(collection_path);
+    let content = serde_json::to_string_pretty(&settings).map_err(|err| {
+        log::error!("Could not serialize client settings: {:?}", settings);
+        log::error!("Serde Error: {:?}", err);
+        RelynxError::SaveClientSettingsError(settings_path.to_string_lossy().to_string())
+    })?;
+    std::fs::write(&settings_path, content).map_err(|err| {
+        log::error!(
+            "Could not write client settings file: '{}'",
+            settings_path.display()
+        );
+        log::error!("Io Error: {:?}", err);
+        RelynxError::SaveClientSettingsError(settings_path.to_string_lossy().to_string())
+    })
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::{error::RelynxError, model::Environment};
+
+/// The variables of a single iteration, read from one row of a csv or json data file
+pub type DataRow = HashMap<String, String>;
+
+/// Loads the rows of a data file used for running requests once per row. Csv files need a header
+/// line with the variable names, json files contain an array of objects.
+pub fn load_data_rows(path: &Path) -> Result<Vec<DataRow>, RelynxError> {
+    let content = std::fs::read_to_string(path).map_err(|err| {
+        log::error!("Could not read data file: '{}'", path.display());
+        log::error!("Io Error: {:?}", err);
+        RelynxError::LoadDataFileError(path.to_string_lossy().to_string())
+    })?;
+
+    let is_json = path
+        .extension()
+        .map(|extension| extension.eq_ignore_ascii_case("json"))
+        .unwrap_or(false);
+    let rows = if is_json {
+        parse_json_rows(&content)
+    } else {
+        parse_csv_rows(&content)
+    };
+    rows.map_err(|err| {
+        log::error!("Could not parse data file: '{}'", path.display());
+        log::error!("Parse Error: {}", err);
+        RelynxError::InvalidDataFile(path.to_string_lossy().to_string())
+    })
+}
+
+fn parse_csv_rows(content: &str) -> Result<Vec<DataRow>, String> {
+    let mut reader = csv::Reader::from_reader(content.as_bytes());
+    let headers = reader.headers().map_err(|err| err.to_string())?.clone();
+    reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|err| err.to_string())?;
+            Ok(headers
+                .iter()
+                .zip(record.iter())
+                .map(|(name, value)| (name.trim().to_string(), value.to_string()))
+                .collect())
+        })
+        .collect()
+}
+
+fn parse_json_rows(content: &str) -> Result<Vec<DataRow>, String> {
+    let rows: Vec<serde_json::Map<String, serde_json::Value>> =
+        serde_json::from_str(content).map_err(|err| err.to_string())?;
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|(name, value)| {
+                    let value = match value {
+                        serde_json::Value::String(value) => value,
+                        other => other.to_string(),
+                    };
+                    (name, value)
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Adds the values of a data row on top of the environment, they take precedence over the
+/// environment's own values
+pub fn apply_data_row(environment: Option<&Environment>, row: &DataRow) -> Environment {
+    let mut environment = environment
+        .cloned()
+        .unwrap_or_else(|| Environment::new(String::new()));
+    for (name, value) in row.iter() {
+        environment.set_variable(name, value.clone());
+    }
+    environment
+}
+
+/// Removes the values of a data row again after a request has run so they are not stored within
+/// the environment. Variables that have been changed by a response handler keep their new value.
+pub fn remove_data_row(
+    mut environment: Environment,
+    base: &Environment,
+    row: &DataRow,
+) -> Environment {
+    for (name, value) in row.iter() {
+        let unchanged = environment.variables.iter().any(|variable| {
+            &variable.name == name && variable.current_value.as_ref() == Some(value)
+        });
+        if !unchanged {
+            continue;
+        }
+        match base
+            .variables
+            .iter()
+            .find(|variable| &variable.name == name)
+        {
+            Some(base_variable) => environment.variables.iter_mut().for_each(|variable| {
+                if &variable.name == name {
+                    variable.current_value = base_variable.current_value.clone();
+                }
+            }),
+            None => environment
+                .variables
+                .retain(|variable| &variable.name != name),
+        }
+    }
+    environment
+}
+
+/// Removes the values of a data row from the variables of a run without environment, like
+/// [`remove_data_row`] does for an environment
+pub fn remove_data_row_values(mut variables: DataRow, base: &DataRow, row: &DataRow) -> DataRow {
+    for (name, value) in row.iter() {
+        if variables.get(name) != Some(value) {
+            continue;
+        }
+        match base.get(name) {
+            Some(base_value) => variables.insert(name.clone(), base_value.clone()),
+            None => variables.remove(name),
+        };
+    }
+    variables
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::EnvironmentVariable;
+
+    #[test]
+    fn test_parse_csv_rows() {
+        let rows = parse_csv_rows("user, password\nalice,secret\nbob,\"with,comma\"\n").unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                DataRow::from([
+                    ("user".to_string(), "alice".to_string()),
+                    ("password".to_string(), "secret".to_string()),
+                ]),
+                DataRow::from([
+                    ("user".to_string(), "bob".to_string()),
+                    ("password".to_string(), "with,comma".to_string()),
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_json_rows() {
+        let rows = parse_json_rows(r#"[{"user": "alice", "age": 30, "admin": true}]"#).unwrap();
+        assert_eq!(
+            rows,
+            vec![DataRow::from([
+                ("user".to_string(), "alice".to_string()),
+                ("age".to_string(), "30".to_string()),
+                ("admin".to_string(), "true".to_string()),
+            ])]
+        );
+        assert!(parse_json_rows(r#"{"user": "alice"}"#).is_err());
+    }
+
+    #[test]
+    fn test_data_row_is_removed_after_run() {
+        let mut base = Environment::new("dev".to_string());
+        base.variables = vec![EnvironmentVariable::new("user", "default")];
+        let row = DataRow::from([
+            ("user".to_string(), "alice".to_string()),
+            ("token".to_string(), "from_row".to_string()),
+            ("id".to_string(), "1".to_string()),
+        ]);
+
+        let mut environment = apply_data_row(Some(&base), &row);
+        assert_eq!(environment.replace_values_in_str("{{user}}"), "alice");
+
+        // a response handler changed the token
+        environment.set_variable("token", "from_handler".to_string());
+        let environment = remove_data_row(environment, &base, &row);
+
+        assert_eq!(environment.replace_values_in_str("{{user}}"), "default");
+        assert_eq!(
+            environment.replace_values_in_str("{{token}}"),
+            "from_handler"
+        );
+        assert!(!environment.variables.iter().any(|var| var.name == "id"));
+    }
+
+    #[test]
+    fn test_data_row_values_are_removed_after_run() {
+        let base = DataRow::from([("user".to_string(), "default".to_string())]);
+        let row = DataRow::from([
+            ("user".to_string(), "alice".to_string()),
+            ("token".to_string(), "from_row".to_string()),
+            ("id".to_string(), "1".to_string()),
+        ]);
+
+        let mut variables = base.clone();
+        variables.extend(row.clone());
+        // a response handler changed the token
+        variables.insert("token".to_string(), "from_handler".to_string());
+        let variables = remove_data_row_values(variables, &base, &row);
+
+        assert_eq!(
+            variables,
+            DataRow::from([
+                ("user".to_string(), "default".to_string()),
+                ("token".to_string(), "from_handler".to_string()),
+            ])
+        );
+    }
+}
@@ -1,44 +1,222 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
 
 use crate::{
     config::{load_collection_config, save_collection_config},
     error::RelynxError,
     model::{
-        Collection, EnvVarDescriptions, Environment, EnvironmentSecret, EnvironmentVariable,
-        SingleEnvVarDescription,
+        CertificateFormat, Collection, EnvVarDescriptions, Environment, EnvironmentSecret,
+        EnvironmentVariable, SingleEnvVarDescription, SslConfiguration,
+    },
+    vault::{
+        get_vault_secrets, is_vault_unlocked, store_vault_secrets, VaultEnvironments, VaultSecrets,
     },
 };
+
+mod dynamic_variables;
+mod security;
+mod template;
+pub use dynamic_variables::{
+    is_dynamic_expression, replace_dynamic_variables, resolve_dynamic_expression,
+};
+use security::parse_auth_configurations;
+pub use template::{render_template, unresolved_placeholders, RenderedTemplate};
+
 pub const HTTP_ENV_FILENAME: &str = "http-client.env.json";
 pub const PRIVATE_HTTP_ENV_FILENAME: &str = "http-client.private.env.json";
-
-// values could be anything in the json, but when we load the environment all variables are strings
-pub type EnvKeyValues = HashMap<String, String>;
+/// Variables and secrets of this environment are inherited by all other environments
+pub const SHARED_ENV_NAME: &str = "$shared";
+/// Key within an environment of the env file that names the environment it extends
+pub const EXTENDS_KEY: &str = "$extends";
+
+/// Key within an environment of the env file for the client certificate settings
+pub const SSL_CONFIGURATION_KEY: &str = "SSLConfiguration";
+/// Key within an environment of the env file for the authentication configurations
+pub const SECURITY_KEY: &str = "Security";
+
+/// Secret that holds the passphrase of the client certificate of an environment whose
+/// `SSLConfiguration` has `hasCertificatePassphrase`, JetBrains asks for the passphrase instead
+pub const CERTIFICATE_PASSPHRASE_SECRET: &str = "certificatePassphrase";
+
+/// Sections of an environment within the env file that are not variables, they are kept as they
+/// are when the environments are saved
+const SPECIAL_KEYS: &[&str] = &[SSL_CONFIGURATION_KEY, SECURITY_KEY];
+
+// values may be any json value, strings are loaded as they are, all others as json
+pub type EnvKeyValues = HashMap<String, serde_json::Value>;
 pub type EnvFileStructure = HashMap<String, EnvKeyValues>;
 
+/// The `SSLConfiguration` section as it is written within the env file
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct SslConfigurationSection {
+    client_certificate: Option<CertificateFile>,
+    client_certificate_key: Option<CertificateFile>,
+    has_certificate_passphrase: Option<bool>,
+    verify_host_certificate: Option<bool>,
+}
+
+/// A certificate is either given by its path or by an object such as
+/// `{"path": "cert.p12", "format": "P12"}`
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+enum CertificateFile {
+    Path(String),
+    WithFormat {
+        path: String,
+        format: Option<String>,
+    },
+}
+
+impl CertificateFile {
+    fn path(&self) -> &str {
+        match self {
+            CertificateFile::Path(path) => path,
+            CertificateFile::WithFormat { path, .. } => path,
+        }
+    }
+
+    /// Certificates without a format are PEM files
+    fn format(&self) -> CertificateFormat {
+        match self {
+            CertificateFile::WithFormat {
+                format: Some(format),
+                ..
+            } if format.eq_ignore_ascii_case("P12") || format.eq_ignore_ascii_case("PKCS12") => {
+                CertificateFormat::Pkcs12
+            }
+            _ => CertificateFormat::Pem,
+        }
+    }
+}
+
+impl SslConfigurationSection {
+    fn parse(value: serde_json::Value, env_name: &str) -> Option<Self> {
+        serde_json::from_value(value)
+            .map_err(|err| {
+                log::warn!(
+                    "Ignoring malformed '{}' of environment '{}'",
+                    SSL_CONFIGURATION_KEY,
+                    env_name
+                );
+                log::warn!("Serde Error: {:?}", err);
+            })
+            .ok()
+    }
+
+    /// Values of the private env file take precedence over the ones of the public file
+    fn merge(self, private: SslConfigurationSection) -> Self {
+        SslConfigurationSection {
+            client_certificate: private.client_certificate.or(self.client_certificate),
+            client_certificate_key: private
+                .client_certificate_key
+                .or(self.client_certificate_key),
+            has_certificate_passphrase: private
+                .has_certificate_passphrase
+                .or(self.has_certificate_passphrase),
+            verify_host_certificate: private
+                .verify_host_certificate
+                .or(self.verify_host_certificate),
+        }
+    }
+
+    /// Relative certificate paths are resolved against the folder of the env files
+    fn into_ssl_configuration(self, base: Option<&Path>) -> SslConfiguration {
+        let resolve = |file: CertificateFile| {
+            let path = PathBuf::from(file.path());
+            match base {
+                Some(base) if path.is_relative() => base.join(path),
+                _ => path,
+            }
+        };
+        SslConfiguration {
+            client_certificate_format: self
+                .client_certificate
+                .as_ref()
+                .map(CertificateFile::format)
+                .unwrap_or_default(),
+            client_certificate: self.client_certificate.map(resolve),
+            client_certificate_key: self.client_certificate_key.map(resolve),
+            has_certificate_passphrase: self.has_certificate_passphrase.unwrap_or(false),
+            verify_host_certificate: self.verify_host_certificate.unwrap_or(true),
+        }
+    }
+}
+
+/// Returns the value of an env file entry as string and if it is a json value other than a string
+fn from_env_value(value: serde_json::Value) -> (String, bool) {
+    match value {
+        serde_json::Value::String(value) => (value, false),
+        value => (value.to_string(), true),
+    }
+}
+
+/// Converts a value back to its representation within the env file. Values that have been json
+/// but are no valid json anymore after editing are written as string.
+fn to_env_value(value: &str, is_json: bool) -> serde_json::Value {
+    if is_json {
+        if let Ok(json) = serde_json::from_str::<serde_json::Value>(value) {
+            return json;
+        }
+    }
+    serde_json::Value::String(value.to_string())
+}
+
 pub fn load_environments_from_files(
     env_file_path: Option<&std::path::PathBuf>,
     private_env_file_path: Option<&std::path::PathBuf>,
     collection_path: Option<&std::path::PathBuf>,
 ) -> Result<Vec<Environment>, RelynxError> {
     let mut environments: HashMap<String, Environment> = HashMap::new();
+    let mut ssl_sections: HashMap<String, SslConfigurationSection> = HashMap::new();
+    // public and private security section of each environment
+    let mut security_sections: HashMap<
+        String,
+        (Option<serde_json::Value>, Option<serde_json::Value>),
+    > = HashMap::new();
 
     if let Some(env_file_path) = env_file_path {
         let env_structure = load_env_structure(env_file_path)?;
 
-        for (env_name, key_val_map) in env_structure.iter() {
+        for (env_name, mut key_val_map) in env_structure.into_iter() {
+            let extends = key_val_map
+                .remove(EXTENDS_KEY)
+                .and_then(|value| value.as_str().map(str::to_string));
+            if let Some(ssl_section) = key_val_map
+                .remove(SSL_CONFIGURATION_KEY)
+                .and_then(|value| SslConfigurationSection::parse(value, &env_name))
+            {
+                ssl_sections.insert(env_name.clone(), ssl_section);
+            }
+            if let Some(security_section) = key_val_map.remove(SECURITY_KEY) {
+                security_sections.entry(env_name.clone()).or_default().0 = Some(security_section);
+            }
             let variables: Vec<EnvironmentVariable> = key_val_map
-                .iter()
-                .map(|(var_name, value)| EnvironmentVariable {
-                    name: var_name.clone(),
-                    initial_value: value.clone(),
-                    current_value: None,
-                    description: None, // @TODO: get description from collectionconfig :(
+                .into_iter()
+                .map(|(var_name, value)| {
+                    let (value, is_json) = from_env_value(value);
+                    EnvironmentVariable {
+                        name: var_name,
+                        initial_value: value,
+                        current_value: None,
+                        description: None, // @TODO: get description from collectionconfig :(
+                        source: None,
+                        is_json,
+                    }
                 })
                 .collect();
             let environment = Environment {
                 name: env_name.clone(),
                 variables,
                 secrets: vec![],
+                extends,
+                ssl_configuration: None,
+                auth_configurations: HashMap::new(),
+                vault_loaded: false,
             };
             environments.insert(env_name.to_string(), environment);
         }
@@ -46,25 +224,59 @@ pub fn load_environments_from_files(
     if let Some(private_env_file_path) = private_env_file_path {
         let private_env_structure = load_env_structure(private_env_file_path)?;
 
-        for (env_name, key_val_map) in private_env_structure.into_iter() {
+        for (env_name, mut key_val_map) in private_env_structure.into_iter() {
+            key_val_map.remove(EXTENDS_KEY);
+            if let Some(private_ssl_section) = key_val_map
+                .remove(SSL_CONFIGURATION_KEY)
+                .and_then(|value| SslConfigurationSection::parse(value, &env_name))
+            {
+                let ssl_section = ssl_sections.remove(&env_name).unwrap_or_default();
+                ssl_sections.insert(env_name.clone(), ssl_section.merge(private_ssl_section));
+            }
+            if let Some(security_section) = key_val_map.remove(SECURITY_KEY) {
+                security_sections.entry(env_name.clone()).or_default().1 = Some(security_section);
+            }
             let environment = environments
                 .entry(env_name.clone())
                 .or_insert(Environment::new(env_name.clone()));
 
             let secrets: Vec<EnvironmentSecret> = key_val_map
                 .into_iter()
-                .map(|(var_name, value)| EnvironmentSecret {
-                    name: var_name,
-                    initial_value: value,
-                    current_value: None,
-                    description: None, // @TODO: get description from collectionconfig :(
-                    persist_to_file: true,
+                .map(|(var_name, value)| {
+                    let (value, is_json) = from_env_value(value);
+                    EnvironmentSecret {
+                        name: var_name,
+                        initial_value: value,
+                        current_value: None,
+                        description: None, // @TODO: get description from collectionconfig :(
+                        persist_to_file: true,
+                        source: None,
+                        is_json,
+                    }
                 })
                 .collect();
             environment.secrets = secrets;
         }
     }
 
+    let env_folder = env_file_path
+        .or(private_env_file_path)
+        .and_then(|path| path.parent());
+    for (env_name, ssl_section) in ssl_sections {
+        if let Some(environment) = environments.get_mut(&env_name) {
+            environment.ssl_configuration = Some(ssl_section.into_ssl_configuration(env_folder));
+        }
+    }
+    for (env_name, (public, private)) in security_sections {
+        if let Some(environment) = environments.get_mut(&env_name) {
+            environment.auth_configurations = parse_auth_configurations(public, private, &env_name);
+        }
+    }
+
+    if let Some(collection_path) = collection_path {
+        add_vault_secrets(collection_path, &mut environments);
+    }
+
     if let Some(collection_path) = collection_path {
         if let Ok(mut collection_config) = load_collection_config(collection_path) {
             let mut to_remove_env: Vec<String> = Vec::new();
@@ -113,9 +325,157 @@ pub fn load_environments_from_files(
         }
     }
 
+    resolve_inheritance(&mut environments);
+
     Ok(environments.into_values().collect())
 }
 
+/// Adds the variables and secrets an environment inherits from the environments it extends and
+/// from the `$shared` environment. Values defined by the environment itself take precedence,
+/// followed by its parent, the parent's parent and so on with `$shared` being the least specific.
+fn resolve_inheritance(environments: &mut HashMap<String, Environment>) {
+    let own_environments = environments.clone();
+    for environment in environments.values_mut() {
+        for parent_name in inheritance_chain(&environment.name, &own_environments) {
+            let parent = &own_environments[&parent_name];
+            if environment.ssl_configuration.is_none() {
+                environment.ssl_configuration = parent.ssl_configuration.clone();
+            }
+            for (auth_id, configuration) in parent.auth_configurations.iter() {
+                environment
+                    .auth_configurations
+                    .entry(auth_id.clone())
+                    .or_insert_with(|| configuration.clone());
+            }
+            for variable in parent.variables.iter() {
+                if !environment.is_defined(&variable.name) {
+                    environment.variables.push(EnvironmentVariable {
+                        source: Some(parent_name.clone()),
+                        ..variable.clone()
+                    });
+                }
+            }
+            for secret in parent.secrets.iter() {
+                if !environment.is_defined(&secret.name) {
+                    environment.secrets.push(EnvironmentSecret {
+                        source: Some(parent_name.clone()),
+                        ..secret.clone()
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Returns the names of the environments the given one inherits from, the nearest parent first
+/// and `$shared` last
+fn inheritance_chain(name: &str, environments: &HashMap<String, Environment>) -> Vec<String> {
+    let mut chain: Vec<String> = Vec::new();
+    if name == SHARED_ENV_NAME {
+        return chain;
+    }
+    let mut current = environments.get(name).and_then(|env| env.extends.clone());
+    while let Some(parent_name) = current {
+        if parent_name == name || chain.contains(&parent_name) {
+            log::warn!("Environment '{}' has a cyclic extends chain", name);
+            break;
+        }
+        match environments.get(&parent_name) {
+            Some(parent) => {
+                current = parent.extends.clone();
+                chain.push(parent_name);
+            }
+            None => {
+                log::warn!(
+                    "Environment '{}' extends the unknown environment '{}'",
+                    name,
+                    parent_name
+                );
+                break;
+            }
+        }
+    }
+    if environments.contains_key(SHARED_ENV_NAME) && !chain.iter().any(|n| n == SHARED_ENV_NAME) {
+        chain.push(SHARED_ENV_NAME.to_string());
+    }
+    chain
+}
+
+/// Adds the secrets that are stored within the vault instead of the private env file. Secrets
+/// are only present if the vault has been unlocked in the current session.
+fn add_vault_secrets(collection_path: &Path, environments: &mut HashMap<String, Environment>) {
+    let vault_environments = match get_vault_secrets(collection_path) {
+        Some(vault_environments) => vault_environments,
+        None => return,
+    };
+    for (env_name, secrets) in vault_environments {
+        // secrets of environments that have been removed are not added again
+        let environment = match environments.get_mut(&env_name) {
+            Some(environment) => environment,
+            None => continue,
+        };
+        for (name, value) in secrets {
+            // the private env file takes precedence for secrets that are persisted to file
+            if environment.secrets.iter().any(|secret| secret.name == name) {
+                continue;
+            }
+            environment.secrets.push(EnvironmentSecret {
+                name,
+                initial_value: value,
+                current_value: None,
+                description: None,
+                persist_to_file: false,
+                source: None,
+                is_json: false,
+            });
+        }
+    }
+    for environment in environments.values_mut() {
+        environment.vault_loaded = true;
+    }
+}
+
+/// Replaces the variables of the environment, if one is given, as well as dynamic variables such
+/// as `{{$uuid}}` within a string and returns the placeholders that could not be resolved
+pub fn render_variables(str: &str, environment: Option<&Environment>) -> RenderedTemplate {
+    render_template(str, &|name: &str| {
+        if name.starts_with('$') {
+            // acquired OAuth tokens are provided as variables named after their expression
+            resolve_dynamic_expression(name)
+                .or_else(|| environment.and_then(|environment| environment.get_value(name)))
+        } else {
+            environment.and_then(|environment| environment.get_value(name))
+        }
+    })
+}
+
+/// Returns the placeholders within a string that cannot be resolved with the environment, the
+/// same as the `unresolved` names of [`render_variables`]. Dynamic variables are only checked and
+/// not evaluated.
+pub fn unresolved_variables(str: &str, environment: Option<&Environment>) -> Vec<String> {
+    unresolved_placeholders(str, &|name: &str| {
+        if name.starts_with('$') && is_dynamic_expression(name) {
+            // the value of a dynamic variable never contains placeholders
+            return Some(String::new());
+        }
+        environment.and_then(|environment| environment.get_value(name))
+    })
+}
+
+/// Same as [`render_variables`], unresolved placeholders are kept as they are
+pub fn replace_variables_in_str(str: &str, environment: Option<&Environment>) -> String {
+    render_variables(str, environment).value
+}
+
+/// Replaces variables within body data read from a file. Only text is changed, binary data such
+/// as images is returned as it is.
+pub fn replace_variables_in_bytes(bytes: Vec<u8>, environment: Option<&Environment>) -> Vec<u8> {
+    match String::from_utf8(bytes) {
+        Ok(text) => replace_variables_in_str(&text, environment).into_bytes(),
+        Err(err) => err.into_bytes(),
+    }
+}
+
 pub fn load_environments(collection_path: PathBuf) -> Result<Vec<Environment>, RelynxError> {
     let env_path = collection_path.join(HTTP_ENV_FILENAME);
     let private_env_path = collection_path.join(PRIVATE_HTTP_ENV_FILENAME);
@@ -173,12 +533,61 @@ pub fn save_environments(
     let mut env_file_structure = EnvFileStructure::new();
     let mut private_env_file_structure = EnvFileStructure::new();
 
+    // sections such as 'SSLConfiguration' are not edited within relynx, they are taken over from
+    // the existing files
+    let existing_env_file_structure = load_env_structure(&env_path).unwrap_or_default();
+    let existing_private_env_file_structure =
+        load_env_structure(&private_env_path).unwrap_or_default();
+
     let config_file_path = Collection::config_file_path(&collection_path);
 
     let mut collection_config = load_collection_config(&config_file_path).unwrap_or_default();
 
     collection_config.env_var_descriptions = EnvVarDescriptions::new();
 
+    // secrets that are not persisted to the private env file are kept within the vault, if it is
+    // locked they are only kept for the current session
+    let vault_unlocked = is_vault_unlocked();
+    let vault_secrets: VaultEnvironments = environments
+        .iter()
+        .map(|environment| {
+            let secrets: VaultSecrets = environment
+                .secrets
+                .iter()
+                .filter(|secret| !secret.persist_to_file && is_stored_secret(secret))
+                // secrets of environments loaded while the vault was locked have no values yet
+                .filter(|secret| environment.vault_loaded || !secret.initial_value.is_empty())
+                .map(|secret| (secret.name.clone(), secret.initial_value.clone()))
+                .collect();
+            (environment.name.clone(), secrets)
+        })
+        .collect();
+    // only environments that contain the secrets of the vault are replaced, otherwise the secrets
+    // are merged so that secrets missing from the environment are not lost
+    let mut replaced_vault_environments: Vec<String> = environments
+        .iter()
+        .filter(|environment| environment.vault_loaded)
+        .map(|environment| environment.name.clone())
+        .collect();
+    if vault_unlocked
+        && !environments.is_empty()
+        && environments
+            .iter()
+            .all(|environment| environment.vault_loaded)
+    {
+        // environments that have been removed or renamed
+        replaced_vault_environments.extend(
+            get_vault_secrets(&collection_path)
+                .unwrap_or_default()
+                .into_keys()
+                .filter(|name| {
+                    !environments
+                        .iter()
+                        .any(|environment| environment.name == *name)
+                }),
+        );
+    }
+
     for environment in environments {
         let env_key_values = env_file_structure
             .entry(environment.name.clone())
@@ -186,22 +595,49 @@ pub fn save_environments(
         let private_env_key_values = private_env_file_structure
             .entry(environment.name.clone())
             .or_default();
-        for variable in environment.variables.iter() {
-            env_key_values.insert(variable.name.clone(), variable.initial_value.clone());
+        if let Some(ref extends) = environment.extends {
+            env_key_values.insert(
+                EXTENDS_KEY.to_string(),
+                serde_json::Value::String(extends.clone()),
+            );
+        }
+        copy_special_sections(
+            existing_env_file_structure.get(&environment.name),
+            env_key_values,
+        );
+        copy_special_sections(
+            existing_private_env_file_structure.get(&environment.name),
+            private_env_key_values,
+        );
+
+        // inherited values are stored within the environment they are defined in
+        for variable in environment
+            .variables
+            .iter()
+            .filter(|variable| variable.source.is_none())
+        {
+            env_key_values.insert(
+                variable.name.clone(),
+                to_env_value(&variable.initial_value, variable.is_json),
+            );
         }
 
         for secret in environment
             .secrets
             .iter()
-            .filter(|secret| secret.persist_to_file)
+            .filter(|secret| secret.persist_to_file && secret.source.is_none())
         {
-            private_env_key_values.insert(secret.name.clone(), secret.initial_value.clone());
+            private_env_key_values.insert(
+                secret.name.clone(),
+                to_env_value(&secret.initial_value, secret.is_json),
+            );
         }
 
         // update descriptions
         let mut var_descriptions: Vec<SingleEnvVarDescription> = environment
             .variables
             .iter()
+            .filter(|variable| variable.source.is_none())
             .map(TryInto::<SingleEnvVarDescription>::try_into)
             .filter_map(Result::ok)
             .collect();
@@ -210,6 +646,7 @@ pub fn save_environments(
             environment
                 .secrets
                 .iter()
+                .filter(|secret| secret.source.is_none())
                 .map(TryInto::<SingleEnvVarDescription>::try_into)
                 .filter_map(Result::ok),
         );
@@ -234,6 +671,15 @@ pub fn save_environments(
             RelynxError::SaveEnvironmentsError
         })?;
 
+    // the vault is written first, the environment files are left as they are if that fails
+    if vault_unlocked {
+        store_vault_secrets(
+            &collection_path,
+            vault_secrets,
+            &replaced_vault_environments,
+        )?;
+    }
+
     std::fs::write(env_path, env_file_content).map_err(|err| {
         log::error!("Could not write environment content to file");
         log::error!("Io Error: {:?}", err);
@@ -258,6 +704,20 @@ pub fn save_environments(
     Ok(())
 }
 
+fn is_stored_secret(secret: &EnvironmentSecret) -> bool {
+    !secret.name.is_empty() && secret.source.is_none()
+}
+
+fn copy_special_sections(existing: Option<&EnvKeyValues>, env_key_values: &mut EnvKeyValues) {
+    if let Some(existing) = existing {
+        for key in SPECIAL_KEYS {
+            if let Some(value) = existing.get(*key) {
+                env_key_values.insert(key.to_string(), value.clone());
+            }
+        }
+    }
+}
+
 fn load_env_structure(env_path: &PathBuf) -> Result<EnvFileStructure, RelynxError> {
     // the file does not need to exist, this is not an error
     if !env_path.exists() {
@@ -280,3 +740,167 @@ fn load_env_structure(env_path: &PathBuf) -> Result<EnvFileStructure, RelynxErro
 
     Ok(env_structure)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::options::ClientOptions;
+    use crate::model::EnvironmentVariable;
+
+    #[test]
+    fn test_render_variables() {
+        let mut environment = Environment::new("dev".to_string());
+        environment.variables = vec![
+            EnvironmentVariable::new("host", "localhost"),
+            EnvironmentVariable::new("url", "https://{{host}}/{{$uuid}}"),
+        ];
+        environment.secrets = vec![EnvironmentSecret {
+            name: "token".to_string(),
+            initial_value: "secret".to_string(),
+            current_value: None,
+            description: None,
+            persist_to_file: true,
+            source: None,
+            is_json: false,
+        }];
+        let rendered =
+            render_variables("{{ url }}?token={{token}}&{{missing}}", Some(&environment));
+        assert!(rendered.value.starts_with("https://localhost/"));
+        assert!(rendered.value.ends_with("?token=secret&{{missing}}"));
+        assert_eq!(rendered.unresolved, vec!["missing".to_string()]);
+    }
+
+    #[test]
+    fn test_resolve_inheritance() {
+        let mut shared = Environment::new(SHARED_ENV_NAME.to_string());
+        shared.variables = vec![
+            EnvironmentVariable::new("host", "shared.example.com"),
+            EnvironmentVariable::new("timeout", "30"),
+        ];
+        let mut base = Environment::new("base".to_string());
+        base.variables = vec![
+            EnvironmentVariable::new("host", "base.example.com"),
+            EnvironmentVariable::new("user", "base"),
+        ];
+        let mut staging = Environment::new("staging".to_string());
+        staging.extends = Some("base".to_string());
+        staging.variables = vec![EnvironmentVariable::new("user", "staging")];
+
+        let mut environments: HashMap<String, Environment> = [shared, base, staging]
+            .into_iter()
+            .map(|env| (env.name.clone(), env))
+            .collect();
+        resolve_inheritance(&mut environments);
+
+        let staging = &environments["staging"];
+        let sources: Vec<(&str, &str, Option<&str>)> = staging
+            .variables
+            .iter()
+            .map(|var| {
+                (
+                    var.name.as_str(),
+                    var.initial_value.as_str(),
+                    var.source.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            sources,
+            vec![
+                ("user", "staging", None),
+                ("host", "base.example.com", Some("base")),
+                ("timeout", "30", Some(SHARED_ENV_NAME)),
+            ]
+        );
+        assert_eq!(environments[SHARED_ENV_NAME].variables.len(), 2);
+    }
+
+    #[test]
+    fn test_cyclic_inheritance_chain() {
+        let mut a = Environment::new("a".to_string());
+        a.extends = Some("b".to_string());
+        let mut b = Environment::new("b".to_string());
+        b.extends = Some("a".to_string());
+        let environments: HashMap<String, Environment> = [a, b]
+            .into_iter()
+            .map(|env| (env.name.clone(), env))
+            .collect();
+        assert_eq!(inheritance_chain("a", &environments), vec!["b".to_string()]);
+    }
+
+    #[test]
+    fn test_json_values_and_special_sections() {
+        let collection_path =
+            std::env::temp_dir().join(format!("relynx-env-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&collection_path).unwrap();
+        let env_content = serde_json::json!({
+            "dev": {
+                "host": "localhost",
+                "port": 8080,
+                "debug": true,
+                "db": {"host": "db.local", "replicas": ["r1", "r2"]},
+                "SSLConfiguration": {
+                    "clientCertificate": {"path": "cert.p12", "format": "P12"},
+                    "clientCertificateKey": "/keys/key.pem",
+                    "hasCertificatePassphrase": true,
+                    "verifyHostCertificate": false
+                },
+                "Security": {"Auth": {"auth-id": {"Type": "OAuth2"}}}
+            }
+        });
+        std::fs::write(
+            collection_path.join(HTTP_ENV_FILENAME),
+            env_content.to_string(),
+        )
+        .unwrap();
+
+        let environments = load_environments(collection_path.clone()).unwrap();
+        let dev = &environments[0];
+        assert_eq!(dev.variables.len(), 4);
+        assert_eq!(dev.get_value("port").as_deref(), Some("8080"));
+        assert_eq!(dev.get_value("db.host").as_deref(), Some("db.local"));
+        assert_eq!(dev.get_value("db.replicas.1").as_deref(), Some("r2"));
+        assert_eq!(dev.get_value("host.name"), None);
+        assert_eq!(
+            dev.ssl_configuration,
+            Some(SslConfiguration {
+                client_certificate: Some(collection_path.join("cert.p12")),
+                client_certificate_format: CertificateFormat::Pkcs12,
+                client_certificate_key: Some(PathBuf::from("/keys/key.pem")),
+                has_certificate_passphrase: true,
+                verify_host_certificate: false,
+            })
+        );
+        let mut options = ClientOptions::default();
+        dev.ssl_configuration
+            .as_ref()
+            .unwrap()
+            .apply_to(&mut options);
+        assert_eq!(options.client_cert_format, CertificateFormat::Pkcs12);
+        assert_eq!(
+            options.client_key_password.as_deref(),
+            Some("{{certificatePassphrase}}")
+        );
+
+        save_environments(collection_path.clone(), environments).unwrap();
+        let saved = load_env_structure(&collection_path.join(HTTP_ENV_FILENAME)).unwrap();
+        assert_eq!(serde_json::to_value(saved).unwrap(), env_content);
+        std::fs::remove_dir_all(collection_path).unwrap();
+    }
+
+    #[test]
+    fn test_replace_variables_in_bytes() {
+        let mut environment = Environment::new("dev".to_string());
+        environment.variables = vec![EnvironmentVariable::new("id", "42")];
+        assert_eq!(
+            replace_variables_in_bytes(b"{\"id\": {{id}}}".to_vec(), Some(&environment)),
+            b"{\"id\": 42}".to_vec()
+        );
+
+        let binary = vec![0xff, 0xfe, b'{', b'{', b'i', b'd', b'}', b'}'];
+        assert_eq!(
+            replace_variables_in_bytes(binary.clone(), Some(&environment)),
+            binary
+        );
+    }
+}
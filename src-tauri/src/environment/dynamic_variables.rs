@@ -0,0 +1,175 @@
+use chrono::{SecondsFormat, Utc};
+use lazy_static::lazy_static;
+use rand::distributions::{Alphanumeric, Distribution, Uniform};
+use rand::Rng;
+use regex::Regex;
+
+use super::template::render_template;
+
+lazy_static! {
+    /// Matches the expression of a dynamic variable with optional arguments such as `$uuid` or
+    /// `$random.integer(1, 10)`
+    static ref DYNAMIC_VARIABLE_REGEX: Regex =
+        Regex::new(r"^\$([A-Za-z][A-Za-z.]*)\s*(?:\(([^)]*)\))?$").unwrap();
+}
+
+const ALPHABETIC: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const HEXADECIMAL: &[u8] = b"0123456789abcdef";
+
+/// Replaces the dynamic variables of the JetBrains http client within a string. Each occurrence
+/// gets a new value, unknown variables are kept as they are.
+pub fn replace_dynamic_variables(str: &str) -> String {
+    render_template(str, &resolve_dynamic_expression).value
+}
+
+/// Returns a new value for the expression of a dynamic variable (the part between the braces) or
+/// `None` if it is not a known dynamic variable.
+///
+/// Supported are `$uuid`, `$timestamp`, `$isoTimestamp`, `$randomInt`, `$random.uuid`,
+/// `$random.integer(from, to)`, `$random.float(from, to)`, `$random.alphabetic(length)`,
+/// `$random.alphanumeric(length)`, `$random.hexadecimal(length)` and `$random.email`.
+pub fn resolve_dynamic_expression(expression: &str) -> Option<String> {
+    let (name, args) = parse_expression(expression)?;
+    resolve_dynamic_variable(name, &args)
+}
+
+/// Returns if the expression is a known dynamic variable with valid arguments, without
+/// generating a value for it
+pub fn is_dynamic_expression(expression: &str) -> bool {
+    let (name, args) = match parse_expression(expression) {
+        Some(parsed) => parsed,
+        None => return false,
+    };
+    match name {
+        "uuid" | "random.uuid" | "timestamp" | "isoTimestamp" | "randomInt" | "random.email" => {
+            true
+        }
+        "random.integer" => range_args::<i64>(&args, (0, 1000)).is_some(),
+        "random.float" => range_args::<f64>(&args, (0.0, 1000.0)).is_some(),
+        "random.alphabetic" | "random.alphanumeric" | "random.hexadecimal" => {
+            length_arg(&args).is_some()
+        }
+        _ => false,
+    }
+}
+
+/// Splits an expression such as `$random.integer(1, 10)` into its name and arguments
+fn parse_expression(expression: &str) -> Option<(&str, Vec<&str>)> {
+    let captures = DYNAMIC_VARIABLE_REGEX.captures(expression.trim())?;
+    let name = captures.get(1)?.as_str();
+    let args: Vec<&str> = captures
+        .get(2)
+        .map(|args| {
+            args.as_str()
+                .split(',')
+                .map(str::trim)
+                .filter(|arg| !arg.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    Some((name, args))
+}
+
+fn resolve_dynamic_variable(name: &str, args: &[&str]) -> Option<String> {
+    let mut rng = rand::thread_rng();
+    let value = match name {
+        "uuid" | "random.uuid" => uuid::Uuid::new_v4().to_string(),
+        "timestamp" => Utc::now().timestamp().to_string(),
+        "isoTimestamp" => Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+        "randomInt" => rng.gen_range(0..1000).to_string(),
+        "random.integer" => {
+            let (from, to) = range_args::<i64>(args, (0, 1000))?;
+            rng.gen_range(from..to).to_string()
+        }
+        "random.float" => {
+            let (from, to) = range_args::<f64>(args, (0.0, 1000.0))?;
+            rng.gen_range(from..to).to_string()
+        }
+        "random.alphabetic" => random_string(ALPHABETIC, length_arg(args)?),
+        "random.alphanumeric" => Alphanumeric
+            .sample_iter(&mut rng)
+            .take(length_arg(args)?)
+            .map(char::from)
+            .collect(),
+        "random.hexadecimal" => random_string(HEXADECIMAL, length_arg(args)?),
+        "random.email" => format!(
+            "{}@{}.com",
+            random_string(ALPHABETIC, 8).to_lowercase(),
+            random_string(ALPHABETIC, 6).to_lowercase()
+        ),
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn range_args<T: std::str::FromStr + PartialOrd>(args: &[&str], default: (T, T)) -> Option<(T, T)> {
+    let (from, to) = match args {
+        [] => default,
+        [from, to] => (from.parse().ok()?, to.parse().ok()?),
+        _ => return None,
+    };
+    if from < to {
+        Some((from, to))
+    } else {
+        None
+    }
+}
+
+fn length_arg(args: &[&str]) -> Option<usize> {
+    match args {
+        [] => Some(10),
+        [length] => length.parse().ok(),
+        _ => None,
+    }
+}
+
+fn random_string(charset: &[u8], length: usize) -> String {
+    let index = Uniform::from(0..charset.len());
+    let mut rng = rand::thread_rng();
+    (0..length)
+        .map(|_| charset[index.sample(&mut rng)] as char)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_dynamic_variables() {
+        let uuid = replace_dynamic_variables("{{$uuid}}");
+        assert!(uuid::Uuid::parse_str(&uuid).is_ok());
+
+        let timestamp = replace_dynamic_variables("{{ $timestamp }}");
+        assert!(timestamp.parse::<i64>().is_ok());
+
+        let number = replace_dynamic_variables("{{$random.integer(5, 7)}}")
+            .parse::<i64>()
+            .unwrap();
+        assert!((5..7).contains(&number));
+
+        let hex = replace_dynamic_variables("{{$random.hexadecimal(12)}}");
+        assert_eq!(hex.len(), 12);
+        assert!(hex.chars().all(|c| c.is_ascii_hexdigit()));
+
+        assert!(replace_dynamic_variables("{{$random.email}}").contains('@'));
+    }
+
+    #[test]
+    fn test_unknown_dynamic_variables_are_kept() {
+        assert_eq!(
+            replace_dynamic_variables("{{$unknown}} {{host}} {{$random.integer(9, 1)}}"),
+            "{{$unknown}} {{host}} {{$random.integer(9, 1)}}"
+        );
+    }
+
+    #[test]
+    fn test_is_dynamic_expression() {
+        assert!(is_dynamic_expression("$uuid"));
+        assert!(is_dynamic_expression(" $random.integer(1, 10) "));
+        assert!(is_dynamic_expression("$random.hexadecimal(8)"));
+        assert!(!is_dynamic_expression("$random.integer(9, 1)"));
+        assert!(!is_dynamic_expression("$unknown"));
+        assert!(!is_dynamic_expression("host"));
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::model::{OAuth2ClientCredentials, OAuth2Configuration, OAuth2GrantType};
+
+use super::SECURITY_KEY;
+
+const AUTH_KEY: &str = "Auth";
+const OAUTH2_TYPE: &str = "OAuth2";
+
+/// An entry of the `Security.Auth` section as it is written within the env file
+#[derive(Deserialize, Debug)]
+struct AuthSection {
+    #[serde(rename = "Type")]
+    auth_type: String,
+    #[serde(rename = "Grant Type")]
+    grant_type: Option<String>,
+    #[serde(rename = "Token URL")]
+    token_url: Option<String>,
+    #[serde(rename = "Auth URL")]
+    auth_url: Option<String>,
+    #[serde(rename = "Redirect URL")]
+    redirect_url: Option<String>,
+    #[serde(rename = "Client ID")]
+    client_id: Option<String>,
+    #[serde(rename = "Client Secret")]
+    client_secret: Option<String>,
+    #[serde(rename = "Client Credentials")]
+    client_credentials: Option<String>,
+    #[serde(rename = "Scope")]
+    scope: Option<String>,
+    #[serde(rename = "Username")]
+    username: Option<String>,
+    #[serde(rename = "Password")]
+    password: Option<String>,
+    /// either `true` or an object with the challenge method
+    #[serde(rename = "PKCE")]
+    pkce: Option<serde_json::Value>,
+    #[serde(rename = "Custom Request Parameters")]
+    custom_request_parameters: Option<HashMap<String, serde_json::Value>>,
+}
+
+/// Returns the OAuth 2.0 configurations of the `Security` sections of the public and private env
+/// file. Values within the private file, such as the client secret, are merged into the entries
+/// of the public one.
+pub fn parse_auth_configurations(
+    public: Option<serde_json::Value>,
+    private: Option<serde_json::Value>,
+    env_name: &str,
+) -> HashMap<String, OAuth2Configuration> {
+    let mut entries = auth_entries(public);
+    for (auth_id, private_entry) in auth_entries(private) {
+        match entries.get_mut(&auth_id) {
+            Some(serde_json::Value::Object(entry)) => match private_entry {
+                serde_json::Value::Object(private_entry) => entry.extend(private_entry),
+                _ => log::warn!(
+                    "Ignoring malformed private auth configuration '{}' of environment '{}'",
+                    auth_id,
+                    env_name
+                ),
+            },
+            // a malformed public entry is reported when it is parsed
+            Some(_) => (),
+            None => {
+                entries.insert(auth_id, private_entry);
+            }
+        }
+    }
+
+    entries
+        .into_iter()
+        .filter_map(|(auth_id, entry)| {
+            let section = serde_json::from_value::<AuthSection>(entry)
+                .map_err(|err| {
+                    log::warn!(
+                        "Ignoring malformed auth configuration '{}' of environment '{}'",
+                        auth_id,
+                        env_name
+                    );
+                    log::warn!("Serde Error: {:?}", err);
+                })
+                .ok()?;
+            match section.into_configuration() {
+                Ok(configuration) => Some((auth_id, configuration)),
+                Err(message) => {
+                    log::warn!(
+                        "Ignoring auth configuration '{}' of environment '{}': {}",
+                        auth_id,
+                        env_name,
+                        message
+                    );
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+fn auth_entries(security: Option<serde_json::Value>) -> serde_json::Map<String, serde_json::Value> {
+    match security.and_then(|mut security| security.get_mut(AUTH_KEY).map(|auth| auth.take())) {
+        Some(serde_json::Value::Object(entries)) => entries,
+        Some(_) => {
+            log::warn!(
+                "The '{}.{}' section is not an object",
+                SECURITY_KEY,
+                AUTH_KEY
+            );
+            serde_json::Map::new()
+        }
+        None => serde_json::Map::new(),
+    }
+}
+
+impl AuthSection {
+    fn into_configuration(self) -> Result<OAuth2Configuration, String> {
+        if self.auth_type != OAUTH2_TYPE {
+            return Err(format!("unsupported type '{}'", self.auth_type));
+        }
+        let grant_type = match self.grant_type.as_deref().map(str::to_lowercase).as_deref() {
+            Some("client credentials") => OAuth2GrantType::ClientCredentials,
+            Some("password") => OAuth2GrantType::Password,
+            Some("authorization code") | None => OAuth2GrantType::AuthorizationCode,
+            Some(other) => return Err(format!("unsupported grant type '{}'", other)),
+        };
+        let client_credentials = match self
+            .client_credentials
+            .as_deref()
+            .map(str::to_lowercase)
+            .as_deref()
+        {
+            Some("basic") | None => OAuth2ClientCredentials::Basic,
+            Some("in body") => OAuth2ClientCredentials::InBody,
+            Some("none") => OAuth2ClientCredentials::None,
+            Some(other) => return Err(format!("unsupported client credentials '{}'", other)),
+        };
+        let token_url = self.token_url.ok_or("the token url is missing")?;
+        let client_id = self.client_id.ok_or("the client id is missing")?;
+        if grant_type == OAuth2GrantType::AuthorizationCode && self.auth_url.is_none() {
+            return Err("the auth url is missing".to_string());
+        }
+        let pkce = match self.pkce {
+            Some(serde_json::Value::Bool(pkce)) => pkce,
+            Some(serde_json::Value::Object(_)) => true,
+            _ => false,
+        };
+        let custom_parameters = self
+            .custom_request_parameters
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, value)| match value {
+                serde_json::Value::String(value) => (name, value),
+                value => (name, value.to_string()),
+            })
+            .collect();
+
+        Ok(OAuth2Configuration {
+            grant_type,
+            token_url,
+            auth_url: self.auth_url,
+            redirect_url: self.redirect_url,
+            client_id,
+            client_secret: self.client_secret,
+            client_credentials,
+            scope: self.scope,
+            username: self.username,
+            password: self.password,
+            pkce,
+            custom_parameters,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_auth_configurations() {
+        let public = serde_json::json!({
+            "Auth": {
+                "api": {
+                    "Type": "OAuth2",
+                    "Grant Type": "Client Credentials",
+                    "Token URL": "https://auth.example.com/token",
+                    "Client ID": "{{client_id}}",
+                    "Scope": "read write"
+                },
+                "login": {
+                    "Type": "OAuth2",
+                    "Token URL": "https://auth.example.com/token",
+                    "Auth URL": "https://auth.example.com/authorize",
+                    "Client ID": "app",
+                    "PKCE": {"Code Challenge Method": "S256"}
+                },
+                "unsupported": {"Type": "Basic"}
+            }
+        });
+        let private = serde_json::json!({
+            "Auth": {"api": {"Client Secret": "secret", "Client Credentials": "in body"}}
+        });
+
+        let configurations = parse_auth_configurations(Some(public), Some(private), "dev");
+        assert_eq!(configurations.len(), 2);
+
+        let api = &configurations["api"];
+        assert_eq!(api.grant_type, OAuth2GrantType::ClientCredentials);
+        assert_eq!(api.client_id, "{{client_id}}");
+        assert_eq!(api.client_secret.as_deref(), Some("secret"));
+        assert_eq!(api.client_credentials, OAuth2ClientCredentials::InBody);
+
+        let login = &configurations["login"];
+        assert_eq!(login.grant_type, OAuth2GrantType::AuthorizationCode);
+        assert!(login.pkce);
+    }
+}
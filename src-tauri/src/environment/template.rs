@@ -0,0 +1,300 @@
+/// Maximum depth of variables referencing other variables, protects against deeply nested or
+/// endless definitions
+const MAX_DEPTH: usize = 10;
+
+/// A string with all resolvable `{{...}}` placeholders replaced
+#[derive(Debug, PartialEq)]
+pub struct RenderedTemplate {
+    pub value: String,
+    /// names of placeholders that could not be resolved, they are kept as they are in the value
+    pub unresolved: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Text(&'a str),
+    /// `raw` is the whole placeholder including the braces, `inner` the part between them
+    Placeholder {
+        raw: &'a str,
+        inner: &'a str,
+    },
+}
+
+/// Replaces the `{{...}}` placeholders within a template with the values returned by `lookup`.
+///
+/// Whitespace around a name is ignored (`{{ host }}`), placeholders may contain other
+/// placeholders (`{{ {{stage}}_host }}`) and values may reference other variables which are
+/// resolved as well. A placeholder is escaped with a backslash (`\{{host}}`) and is then kept
+/// without the backslash.
+pub fn render_template<F>(template: &str, lookup: &F) -> RenderedTemplate
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut unresolved = Vec::new();
+    let value = render(template, lookup, &mut Vec::new(), &mut unresolved);
+    RenderedTemplate { value, unresolved }
+}
+
+/// Returns the names of the placeholders within a template that `lookup` cannot resolve, the
+/// values returned by `lookup` are checked as well. Unlike [`render_template`] the rendered
+/// value is not built, only nested placeholder names are rendered.
+pub fn unresolved_placeholders<F>(template: &str, lookup: &F) -> Vec<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut unresolved = Vec::new();
+    collect_unresolved(template, lookup, &mut Vec::new(), &mut unresolved);
+    unresolved
+}
+
+fn collect_unresolved<F>(
+    template: &str,
+    lookup: &F,
+    stack: &mut Vec<String>,
+    unresolved: &mut Vec<String>,
+) where
+    F: Fn(&str) -> Option<String>,
+{
+    if !template.contains("{{") {
+        return;
+    }
+    for token in tokenize(template) {
+        let inner = match token {
+            Token::Text(_) => continue,
+            Token::Placeholder { inner, .. } => inner,
+        };
+        // the name of a placeholder such as '{{ {{stage}}_host }}' is only known once rendered
+        let name = if inner.contains("{{") {
+            render(inner, lookup, stack, unresolved)
+        } else {
+            inner.to_string()
+        };
+        let name = name.trim();
+        if !is_placeholder_name(name) {
+            continue;
+        }
+
+        let value = if stack.len() >= MAX_DEPTH || stack.iter().any(|entry| entry == name) {
+            None
+        } else {
+            lookup(name)
+        };
+        match value {
+            Some(value) => {
+                stack.push(name.to_string());
+                collect_unresolved(&value, lookup, stack, unresolved);
+                stack.pop();
+            }
+            None => {
+                if !unresolved.iter().any(|entry| entry == name) {
+                    unresolved.push(name.to_string());
+                }
+            }
+        }
+    }
+}
+
+fn render<F>(
+    template: &str,
+    lookup: &F,
+    stack: &mut Vec<String>,
+    unresolved: &mut Vec<String>,
+) -> String
+where
+    F: Fn(&str) -> Option<String>,
+{
+    if !template.contains("{{") {
+        return template.to_string();
+    }
+    let mut result = String::with_capacity(template.len());
+    for token in tokenize(template) {
+        let (raw, inner) = match token {
+            Token::Text(text) => {
+                result.push_str(text);
+                continue;
+            }
+            Token::Placeholder { raw, inner } => (raw, inner),
+        };
+
+        let name = render(inner, lookup, stack, unresolved);
+        let name = name.trim();
+        if !is_placeholder_name(name) {
+            // not meant as a placeholder, e.g. nested json objects '{{"id": 1}}'
+            result.push_str(raw);
+            continue;
+        }
+
+        let value = if stack.len() >= MAX_DEPTH || stack.iter().any(|entry| entry == name) {
+            None
+        } else {
+            lookup(name)
+        };
+        match value {
+            Some(value) => {
+                stack.push(name.to_string());
+                result.push_str(&render(&value, lookup, stack, unresolved));
+                stack.pop();
+            }
+            None => {
+                if !unresolved.iter().any(|entry| entry == name) {
+                    unresolved.push(name.to_string());
+                }
+                result.push_str(raw);
+            }
+        }
+    }
+    result
+}
+
+fn tokenize(template: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut text_start = 0;
+    let mut index = 0;
+    while index < template.len() {
+        let rest = &template[index..];
+        if rest.starts_with("\\{{") {
+            push_text(&mut tokens, &template[text_start..index]);
+            tokens.push(Token::Text("{{"));
+            index += 3;
+            text_start = index;
+        } else if rest.starts_with("{{{") {
+            // a literal brace directly in front of a placeholder, e.g. '{{{id}}}' in json
+            index += 1;
+        } else if rest.starts_with("{{") {
+            match find_placeholder_end(template, index + 2) {
+                Some(end) => {
+                    push_text(&mut tokens, &template[text_start..index]);
+                    tokens.push(Token::Placeholder {
+                        raw: &template[index..end + 2],
+                        inner: &template[index + 2..end],
+                    });
+                    index = end + 2;
+                    text_start = index;
+                }
+                None => break,
+            }
+        } else {
+            index += rest.chars().next().map(char::len_utf8).unwrap_or(1);
+        }
+    }
+    push_text(&mut tokens, &template[text_start..]);
+    tokens
+}
+
+/// Returns the index of the closing braces that belong to the placeholder starting before `start`
+fn find_placeholder_end(template: &str, start: usize) -> Option<usize> {
+    let bytes = template.as_bytes();
+    let mut depth = 1;
+    let mut index = start;
+    while index + 1 < bytes.len() {
+        if bytes[index] == b'\\' && template[index..].starts_with("\\{{") {
+            index += 3;
+        } else if bytes[index] == b'{' && bytes[index + 1] == b'{' {
+            depth += 1;
+            index += 2;
+        } else if bytes[index] == b'}' && bytes[index + 1] == b'}' {
+            depth -= 1;
+            if depth == 0 {
+                return Some(index);
+            }
+            index += 2;
+        } else {
+            index += 1;
+        }
+    }
+    None
+}
+
+fn push_text<'a>(tokens: &mut Vec<Token<'a>>, text: &'a str) {
+    if !text.is_empty() {
+        tokens.push(Token::Text(text));
+    }
+}
+
+/// Variable names consist of letters, digits, '_', '-' and '.', dynamic variables start with a
+/// '$' and may contain arguments such as `$random.integer(1, 10)`
+fn is_placeholder_name(name: &str) -> bool {
+    if name.starts_with('$') {
+        return name.len() > 1 && !name.contains('\n');
+    }
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn render_with(template: &str, values: &[(&str, &str)]) -> RenderedTemplate {
+        let values: HashMap<String, String> = values
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        render_template(template, &|name| values.get(name).cloned())
+    }
+
+    #[test]
+    fn test_render_template() {
+        let values = [
+            ("host", "localhost"),
+            ("port", "8080"),
+            ("base", "http://{{host}}:{{ port }}"),
+            ("stage", "dev"),
+            ("dev_path", "/api"),
+        ];
+        assert_eq!(
+            render_with("{{base}}{{ {{stage}}_path }}/users", &values),
+            RenderedTemplate {
+                value: "http://localhost:8080/api/users".to_string(),
+                unresolved: vec![],
+            }
+        );
+        assert_eq!(
+            render_with("{\"host\": \"{{ host }}\", \"id\": {{{id}}}}", &values),
+            RenderedTemplate {
+                value: "{\"host\": \"localhost\", \"id\": {{{id}}}}".to_string(),
+                unresolved: vec!["id".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_escaped_and_invalid_placeholders_are_kept() {
+        let rendered = render_with(
+            "\\{{host}} {{\"a\": 1}} {{host}} {{ missing",
+            &[("host", "localhost")],
+        );
+        assert_eq!(rendered.value, "{{host}} {{\"a\": 1}} localhost {{ missing");
+        assert!(rendered.unresolved.is_empty());
+    }
+
+    #[test]
+    fn test_cyclic_variables_are_unresolved() {
+        let rendered = render_with("{{a}}", &[("a", "{{b}}"), ("b", "{{a}}")]);
+        assert_eq!(rendered.value, "{{a}}");
+        assert_eq!(rendered.unresolved, vec!["a".to_string()]);
+    }
+
+    #[test]
+    fn test_unresolved_placeholders() {
+        let values: HashMap<String, String> = [
+            ("base", "http://{{host}}:{{port}}"),
+            ("host", "localhost"),
+            ("stage", "dev"),
+        ]
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+        assert_eq!(
+            unresolved_placeholders(
+                "{{base}}{{ {{stage}}_path }}/{{id}}?q={{port}} \\{{escaped}}",
+                &|name| values.get(name).cloned()
+            ),
+            vec!["port".to_string(), "dev_path".to_string(), "id".to_string()]
+        );
+    }
+}
@@ -32,6 +32,15 @@ pub enum RelynxError {
     #[error("Could not serialize collection configuration for collection: {0}")]
     SerializeCollectionConfigError(String),
 
+    #[error("Could not load client settings from file: '{0}'")]
+    LoadClientSettingsError(String),
+
+    #[error("The client settings file '{0}' is malformed: {1}.")]
+    InvalidClientSettings(String, String),
+
+    #[error("Could not save client settings to file: '{0}'")]
+    SaveClientSettingsError(String),
+
     #[error("Could not save environments to file")]
     SaveEnvironmentsError,
 
@@ -131,6 +140,42 @@ pub enum RelynxError {
 
     #[error("Could not use cookies from the cookie jar when sending a request")]
     UpdateRequestWithCookieJar,
+
+    #[error("Could not create the run report")]
+    CreateReportError,
+
+    #[error("Could not find the group to run: '{0}'")]
+    RunCollectionGroupNotFound(String),
+
+    #[error("Could not read data file: '{0}'")]
+    LoadDataFileError(String),
+
+    #[error("The data file has an invalid format: '{0}'. Use a csv file with a header line or a json file with an array of objects.")]
+    InvalidDataFile(String),
+
+    #[error("Could not open the secret vault")]
+    LoadVaultError,
+
+    #[error("Could not save secrets to the vault")]
+    SaveVaultError,
+
+    #[error("Could not unlock the secret vault, the passphrase is wrong")]
+    InvalidVaultPassphrase,
+
+    #[error("The secret vault is locked, unlock it to save secrets")]
+    VaultLocked,
+
+    #[error("The credentials of an auth have to reference a secret of the environment, such as '{{{{password}}}}', as the auth is stored within the collection folder")]
+    PlaintextAuthCredentials,
+
+    #[error("The passphrase of a TLS profile has to reference a secret of the environment, such as '{{{{passphrase}}}}', as the profiles are stored within the collection folder")]
+    PlaintextTlsPassphrase,
+
+    #[error("Could not access the request history")]
+    HistoryError,
+
+    #[error("Could not find the history entry: '{0}'")]
+    HistoryEntryNotFound(String),
 }
 
 impl From<RelynxError> for rspc::Error {
@@ -0,0 +1,697 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use rsa::sha2::{Digest, Sha256};
+use rspc::Type;
+use serde::{Deserialize, Serialize};
+
+use crate::model::{
+    BinaryContent, Collection, Environment, RequestModel, RequestResult, SentRequest,
+};
+use crate::{config::get_data_dir, error::RelynxError, redaction::Redactor};
+
+const HISTORY_FOLDER: &str = "history";
+const INDEX_FILENAME: &str = "index.jsonl";
+const SETTINGS_FILENAME: &str = "settings.json";
+const ENTRIES_FOLDER: &str = "entries";
+
+lazy_static! {
+    /// Serializes the changes of the history, the index is appended to and rewritten by requests
+    /// that may run at the same time
+    static ref HISTORY_LOCK: Mutex<()> = Mutex::new(());
+}
+
+fn lock_history() -> MutexGuard<'static, ()> {
+    // the lock guards no data, a panic while holding it leaves nothing inconsistent behind
+    HISTORY_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Retention limits of the history of a collection, the history is stored per user and not
+/// shared with the collection
+#[derive(Serialize, Deserialize, Type, Debug, Clone, PartialEq, Eq)]
+pub struct HistorySettings {
+    pub enabled: bool,
+    /// older entries are removed once there are more entries
+    pub max_entries: u32,
+    /// entries older than this are removed, 0 keeps them regardless of their age
+    pub max_age_days: u32,
+    /// response and request bodies are cut off after this number of bytes
+    pub max_body_size: u32,
+}
+
+impl Default for HistorySettings {
+    fn default() -> Self {
+        HistorySettings {
+            enabled: true,
+            max_entries: 500,
+            max_age_days: 30,
+            max_body_size: 256 * 1024,
+        }
+    }
+}
+
+/// A single execution of a request. Secret values and credential headers of the result are
+/// redacted, the request is stored as defined within the collection, with its variables
+/// unresolved, so it can be run again. Its auth is not stored, a re-run uses the current auth of
+/// the request.
+#[derive(Serialize, Deserialize, Type, Debug, Clone)]
+pub struct HistoryEntry {
+    pub id: String,
+    /// RFC 3339 date
+    pub executed_at: String,
+    pub environment_name: Option<String>,
+    pub request: RequestModel,
+    pub result: RequestResult,
+    /// true if a body has been cut off due to [`HistorySettings::max_body_size`]
+    pub truncated: bool,
+}
+
+/// The part of a [`HistoryEntry`] that is kept within the index to list and search the history
+/// without loading every entry
+#[derive(Serialize, Deserialize, Type, Debug, Clone, PartialEq)]
+pub struct HistoryEntrySummary {
+    pub id: String,
+    /// RFC 3339 date
+    pub executed_at: String,
+    pub environment_name: Option<String>,
+    pub request_name: String,
+    pub rest_file_path: PathBuf,
+    pub method: String,
+    /// the url as it has been sent
+    pub url: String,
+    pub status_code: String,
+    pub total_time: f64,
+}
+
+#[derive(Serialize, Deserialize, Type, Debug, Clone, Default)]
+pub struct HistoryQuery {
+    /// case insensitive text that has to be part of the name, method, url, status code or
+    /// environment of an entry
+    pub search: Option<String>,
+    /// only entries of the request with this file and name
+    pub rest_file_path: Option<PathBuf>,
+    pub request_name: Option<String>,
+    pub limit: Option<u32>,
+}
+
+impl From<&HistoryEntry> for HistoryEntrySummary {
+    fn from(entry: &HistoryEntry) -> Self {
+        HistoryEntrySummary {
+            id: entry.id.clone(),
+            executed_at: entry.executed_at.clone(),
+            environment_name: entry.environment_name.clone(),
+            request_name: entry.request.name.clone(),
+            rest_file_path: entry.request.rest_file_path.clone(),
+            method: entry.result.sent_request.method.clone(),
+            url: entry.result.sent_request.url.clone(),
+            status_code: entry.result.status_code.clone(),
+            total_time: entry.result.total_time,
+        }
+    }
+}
+
+impl HistoryEntrySummary {
+    fn matches(&self, query: &HistoryQuery) -> bool {
+        if let Some(ref rest_file_path) = query.rest_file_path {
+            if self.rest_file_path != *rest_file_path {
+                return false;
+            }
+        }
+        if let Some(ref request_name) = query.request_name {
+            if self.request_name != *request_name {
+                return false;
+            }
+        }
+        match query.search.as_deref().map(str::trim) {
+            Some(search) if !search.is_empty() => {
+                let search = search.to_lowercase();
+                [
+                    Some(&self.request_name),
+                    Some(&self.method),
+                    Some(&self.url),
+                    Some(&self.status_code),
+                    self.environment_name.as_ref(),
+                ]
+                .into_iter()
+                .flatten()
+                .any(|value| value.to_lowercase().contains(&search))
+            }
+            _ => true,
+        }
+    }
+
+    fn is_older_than(&self, max_age_days: u32, now: DateTime<Utc>) -> bool {
+        if max_age_days == 0 {
+            return false;
+        }
+        match DateTime::parse_from_rfc3339(&self.executed_at) {
+            Ok(executed_at) => {
+                now.signed_duration_since(executed_at) > chrono::Duration::days(max_age_days as i64)
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+/// Creates the history entry of an executed request. `request` is the request before variables,
+/// cookies and the auth of its group have been applied.
+pub fn history_entry(
+    request: &RequestModel,
+    environment: Option<&Environment>,
+    result: &RequestResult,
+    settings: &HistorySettings,
+    redactor: &Redactor,
+) -> HistoryEntry {
+    let max_body_size = settings.max_body_size as usize;
+    let mut truncated = false;
+    let mut result = result.clone();
+
+    // bodies are redacted before they are cut off, so no part of a secret remains at the end
+    let body = redactor.redact(&result.result);
+    let (body, body_truncated) = truncate(&body, max_body_size);
+    result.result = body.to_string();
+    truncated |= body_truncated;
+
+    truncated |= redact_sent_request(&mut result.sent_request, max_body_size, redactor);
+    for header in result.headers.iter_mut() {
+        header.value = redactor.redact_header(&header.key, &header.value);
+    }
+    for redirect in result.redirects.iter_mut() {
+        redirect.url = redactor.redact(&redirect.url);
+        redirect.location = redirect
+            .location
+            .as_ref()
+            .map(|location| redactor.redact(location));
+        truncated |= redact_sent_request(&mut redirect.request, max_body_size, redactor);
+    }
+    for timings in result.timings.iter_mut() {
+        timings.url = redactor.redact(&timings.url);
+    }
+    result.warnings = result
+        .warnings
+        .iter()
+        .map(|warning| redactor.redact(warning))
+        .collect();
+
+    // large bodies are not kept, their temporary files are removed when the app exits
+    result.binary = result.binary.filter(|binary| match binary.content {
+        BinaryContent::Base64(ref data) => data.len() <= max_body_size,
+        BinaryContent::File(_) => false,
+    });
+    if result.binary.is_none() && result.result.is_empty() && result.total_result_size > 0.0 {
+        truncated = true;
+    }
+    // the environment contains the values of secrets
+    result.environment = None;
+
+    HistoryEntry {
+        id: result.id.clone(),
+        executed_at: Utc::now().to_rfc3339(),
+        environment_name: environment.map(|environment| environment.name.clone()),
+        // the auth may contain credentials
+        request: RequestModel {
+            auth: None,
+            ..request.clone()
+        },
+        result,
+        truncated,
+    }
+}
+
+/// Redacts the url, headers and body of a sent request and truncates its body, returns whether
+/// the body has been truncated
+fn redact_sent_request(
+    request: &mut SentRequest,
+    max_body_size: usize,
+    redactor: &Redactor,
+) -> bool {
+    let body = redactor.redact(&request.body);
+    let (body, truncated) = truncate(&body, max_body_size);
+    request.body = body.to_string();
+    request.url = redactor.redact(&request.url);
+    for header in request.headers.iter_mut() {
+        header.value = redactor.redact_header(&header.key, &header.value);
+    }
+    truncated
+}
+
+/// Cuts off a text after the given number of bytes at a character boundary
+fn truncate(text: &str, max_size: usize) -> (&str, bool) {
+    if text.len() <= max_size {
+        return (text, false);
+    }
+    let mut end = max_size;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    (&text[..end], true)
+}
+
+fn get_history_dir(collection: &Collection) -> Option<PathBuf> {
+    get_data_dir().map(|dir| {
+        dir.join(HISTORY_FOLDER)
+            .join(collection_key(&collection.path))
+    })
+}
+
+/// Collections are identified by their path, it is hashed to get a valid folder name
+fn collection_key(collection_path: &Path) -> String {
+    hex::encode(Sha256::digest(collection_path.to_string_lossy().as_bytes()))
+}
+
+fn history_dir_or_error(collection: &Collection) -> Result<PathBuf, RelynxError> {
+    get_history_dir(collection).ok_or_else(|| {
+        log::error!("Could not access history as the data dir cannot be determined");
+        RelynxError::HistoryError
+    })
+}
+
+pub fn load_history_settings(collection: &Collection) -> Result<HistorySettings, RelynxError> {
+    let settings_path = history_dir_or_error(collection)?.join(SETTINGS_FILENAME);
+    if !settings_path.exists() {
+        return Ok(HistorySettings::default());
+    }
+    let content = std::fs::read_to_string(&settings_path).map_err(|err| {
+        log::error!(
+            "Could not read history settings: '{}'",
+            settings_path.display()
+        );
+        log::error!("Io Error: {:?}", err);
+        RelynxError::HistoryError
+    })?;
+    serde_json::from_str(&content).map_err(|err| {
+        log::error!(
+            "Could not deserialize history settings: '{}'",
+            settings_path.display()
+        );
+        log::error!("Serde Error: {:?}", err);
+        RelynxError::HistoryError
+    })
+}
+
+/// Saves the settings and removes the entries that exceed the new limits
+pub fn save_history_settings(
+    collection: &Collection,
+    settings: &HistorySettings,
+) -> Result<(), RelynxError> {
+    let history_dir = history_dir_or_error(collection)?;
+    let _lock = lock_history();
+    create_dir(&history_dir)?;
+    let settings_path = history_dir.join(SETTINGS_FILENAME);
+    let content = serde_json::to_string_pretty(settings).map_err(|err| {
+        log::error!("Could not serialize history settings: {:?}", settings);
+        log::error!("Serde Error: {:?}", err);
+        RelynxError::HistoryError
+    })?;
+    std::fs::write(&settings_path, content).map_err(|err| {
+        log::error!(
+            "Could not write history settings: '{}'",
+            settings_path.display()
+        );
+        log::error!("Io Error: {:?}", err);
+        RelynxError::HistoryError
+    })?;
+    apply_retention(&history_dir, settings, Utc::now())
+}
+
+/// Stores an entry within the history of the collection and removes the entries that exceed the
+/// retention limits
+pub fn record_history_entry(
+    collection: &Collection,
+    entry: &HistoryEntry,
+    settings: &HistorySettings,
+) -> Result<(), RelynxError> {
+    let history_dir = history_dir_or_error(collection)?;
+    let _lock = lock_history();
+    write_entry(&history_dir, entry)?;
+    apply_retention(&history_dir, settings, Utc::now())
+}
+
+/// Returns the matching entries of a collection, the latest first
+pub fn list_history(
+    collection: &Collection,
+    query: &HistoryQuery,
+) -> Result<Vec<HistoryEntrySummary>, RelynxError> {
+    let history_dir = history_dir_or_error(collection)?;
+    let limit = query
+        .limit
+        .map(|limit| limit as usize)
+        .unwrap_or(usize::MAX);
+    Ok(read_index(&history_dir)?
+        .into_iter()
+        .rev()
+        .filter(|summary| summary.matches(query))
+        .take(limit)
+        .collect())
+}
+
+pub fn load_history_entry(
+    collection: &Collection,
+    entry_id: &str,
+) -> Result<HistoryEntry, RelynxError> {
+    let history_dir = history_dir_or_error(collection)?;
+    read_entry(&history_dir, entry_id)
+}
+
+pub fn delete_history_entry(collection: &Collection, entry_id: &str) -> Result<(), RelynxError> {
+    let history_dir = history_dir_or_error(collection)?;
+    let _lock = lock_history();
+    let summaries: Vec<HistoryEntrySummary> = read_index(&history_dir)?
+        .into_iter()
+        .filter(|summary| summary.id != entry_id)
+        .collect();
+    remove_entry_file(&history_dir, entry_id);
+    write_index(&history_dir, &summaries)
+}
+
+/// Removes all entries of a collection, the settings are kept
+pub fn clear_history(collection: &Collection) -> Result<(), RelynxError> {
+    let history_dir = history_dir_or_error(collection)?;
+    let _lock = lock_history();
+    let entries_dir = history_dir.join(ENTRIES_FOLDER);
+    if entries_dir.exists() {
+        std::fs::remove_dir_all(&entries_dir).map_err(|err| {
+            log::error!("Could not remove history: '{}'", entries_dir.display());
+            log::error!("Io Error: {:?}", err);
+            RelynxError::HistoryError
+        })?;
+    }
+    write_index(&history_dir, &[])
+}
+
+fn create_dir(dir: &Path) -> Result<(), RelynxError> {
+    std::fs::create_dir_all(dir).map_err(|err| {
+        log::error!("Could not create history folder: '{}'", dir.display());
+        log::error!("Io Error: {:?}", err);
+        RelynxError::HistoryError
+    })
+}
+
+fn entry_path(history_dir: &Path, entry_id: &str) -> PathBuf {
+    // ids are generated uuids, anything else could point outside of the history folder
+    let file_name: String = entry_id
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect();
+    history_dir
+        .join(ENTRIES_FOLDER)
+        .join(format!("{}.json", file_name))
+}
+
+/// The entry is written on its own and its summary appended to the index
+fn write_entry(history_dir: &Path, entry: &HistoryEntry) -> Result<(), RelynxError> {
+    create_dir(&history_dir.join(ENTRIES_FOLDER))?;
+    let path = entry_path(history_dir, &entry.id);
+    let content = serde_json::to_string(entry).map_err(|err| {
+        log::error!("Could not serialize history entry: '{}'", entry.id);
+        log::error!("Serde Error: {:?}", err);
+        RelynxError::HistoryError
+    })?;
+    std::fs::write(&path, content).map_err(|err| {
+        log::error!("Could not write history entry: '{}'", path.display());
+        log::error!("Io Error: {:?}", err);
+        RelynxError::HistoryError
+    })?;
+
+    let index_path = history_dir.join(INDEX_FILENAME);
+    let line = serde_json::to_string(&HistoryEntrySummary::from(entry)).map_err(|err| {
+        log::error!("Could not serialize history summary: '{}'", entry.id);
+        log::error!("Serde Error: {:?}", err);
+        RelynxError::HistoryError
+    })?;
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&index_path)
+        .and_then(|mut file| writeln!(file, "{}", line))
+        .map_err(|err| {
+            log::error!(
+                "Could not append to history index: '{}'",
+                index_path.display()
+            );
+            log::error!("Io Error: {:?}", err);
+            RelynxError::HistoryError
+        })
+}
+
+fn read_entry(history_dir: &Path, entry_id: &str) -> Result<HistoryEntry, RelynxError> {
+    let path = entry_path(history_dir, entry_id);
+    let content = std::fs::read_to_string(&path).map_err(|err| {
+        log::error!("Could not read history entry: '{}'", path.display());
+        log::error!("Io Error: {:?}", err);
+        RelynxError::HistoryEntryNotFound(entry_id.to_string())
+    })?;
+    serde_json::from_str(&content).map_err(|err| {
+        log::error!("Could not deserialize history entry: '{}'", path.display());
+        log::error!("Serde Error: {:?}", err);
+        RelynxError::HistoryError
+    })
+}
+
+fn remove_entry_file(history_dir: &Path, entry_id: &str) {
+    let path = entry_path(history_dir, entry_id);
+    if let Err(err) = std::fs::remove_file(&path) {
+        log::warn!("Could not remove history entry: '{}'", path.display());
+        log::warn!("Io Error: {:?}", err);
+    }
+}
+
+/// Returns the summaries in the order they have been recorded, lines that can not be parsed are
+/// skipped
+fn read_index(history_dir: &Path) -> Result<Vec<HistoryEntrySummary>, RelynxError> {
+    let index_path = history_dir.join(INDEX_FILENAME);
+    if !index_path.exists() {
+        return Ok(vec![]);
+    }
+    let content = std::fs::read_to_string(&index_path).map_err(|err| {
+        log::error!("Could not read history index: '{}'", index_path.display());
+        log::error!("Io Error: {:?}", err);
+        RelynxError::HistoryError
+    })?;
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(summary) => Some(summary),
+            Err(err) => {
+                log::warn!("Skipping invalid line within history index");
+                log::warn!("Serde Error: {:?}", err);
+                None
+            }
+        })
+        .collect())
+}
+
+fn write_index(history_dir: &Path, summaries: &[HistoryEntrySummary]) -> Result<(), RelynxError> {
+    create_dir(history_dir)?;
+    let index_path = history_dir.join(INDEX_FILENAME);
+    let mut content = String::new();
+    for summary in summaries {
+        let line = serde_json::to_string(summary).map_err(|err| {
+            log::error!("Could not serialize history summary: '{}'", summary.id);
+            log::error!("Serde Error: {:?}", err);
+            RelynxError::HistoryError
+        })?;
+        content.push_str(&line);
+        content.push('\n');
+    }
+    std::fs::write(&index_path, content).map_err(|err| {
+        log::error!("Could not write history index: '{}'", index_path.display());
+        log::error!("Io Error: {:?}", err);
+        RelynxError::HistoryError
+    })
+}
+
+/// Removes the entries that are too old and the oldest ones beyond the maximum number of entries
+fn apply_retention(
+    history_dir: &Path,
+    settings: &HistorySettings,
+    now: DateTime<Utc>,
+) -> Result<(), RelynxError> {
+    let summaries = read_index(history_dir)?;
+    let max_entries = settings.max_entries as usize;
+    let skip = summaries.len().saturating_sub(max_entries);
+    let (kept, removed): (Vec<_>, Vec<_>) =
+        summaries
+            .into_iter()
+            .enumerate()
+            .partition(|(index, summary)| {
+                *index >= skip && !summary.is_older_than(settings.max_age_days, now)
+            });
+    if removed.is_empty() {
+        return Ok(());
+    }
+    for (_, summary) in removed.iter() {
+        remove_entry_file(history_dir, &summary.id);
+    }
+    let kept: Vec<HistoryEntrySummary> = kept.into_iter().map(|(_, summary)| summary).collect();
+    write_index(history_dir, &kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{EnvironmentSecret, Header, RequestAuth};
+
+    fn request_result(body: &str) -> RequestResult {
+        RequestResult {
+            id: uuid::Uuid::new_v4().to_string(),
+            result: body.to_string(),
+            charset: Some("utf-8".to_string()),
+            binary: None,
+            status_code: "200".to_string(),
+            http_version: "HTTP/1.1".to_string(),
+            total_time: 0.1,
+            total_result_size: body.len() as f64,
+            raw_result_size: body.len() as f64,
+            content_type: Some("application/json".to_string()),
+            warnings: vec![],
+            result_file: None,
+            result_file_folder: None,
+            environment: None,
+            tests: vec![],
+            tls: None,
+            timings: vec![],
+            headers: vec![Header {
+                key: "Set-Cookie".to_string(),
+                value: "session=abc".to_string(),
+                active: true,
+            }],
+            redirects: vec![],
+            sent_request: SentRequest {
+                method: "GET".to_string(),
+                url: "https://example.com/users?token=hunter22".to_string(),
+                headers: vec![Header {
+                    key: "Authorization".to_string(),
+                    value: "Bearer hunter22".to_string(),
+                    active: true,
+                }],
+                body: String::new(),
+            },
+        }
+    }
+
+    fn environment() -> Environment {
+        let mut environment = Environment::new("dev".to_string());
+        environment.secrets = vec![EnvironmentSecret {
+            name: "token".to_string(),
+            initial_value: "hunter22".to_string(),
+            current_value: None,
+            description: None,
+            persist_to_file: false,
+            source: None,
+            is_json: false,
+        }];
+        environment
+    }
+
+    fn summary(id: &str, executed_at: &str) -> HistoryEntrySummary {
+        HistoryEntrySummary {
+            id: id.to_string(),
+            executed_at: executed_at.to_string(),
+            environment_name: Some("dev".to_string()),
+            request_name: "Get users".to_string(),
+            rest_file_path: PathBuf::from("/collection/users.http"),
+            method: "GET".to_string(),
+            url: "https://example.com/users".to_string(),
+            status_code: "200".to_string(),
+            total_time: 0.1,
+        }
+    }
+
+    #[test]
+    fn test_history_entry_is_redacted_and_truncated() {
+        let environment = environment();
+        let settings = HistorySettings {
+            max_body_size: 12,
+            ..Default::default()
+        };
+        let request = RequestModel {
+            auth: Some(RequestAuth::Bearer {
+                token: "{{token}}".to_string(),
+            }),
+            ..Default::default()
+        };
+        let entry = history_entry(
+            &request,
+            Some(&environment),
+            &request_result("{\"token\": \"hunter22\"}"),
+            &settings,
+            &Redactor::new(Some(&environment)),
+        );
+
+        assert_eq!(entry.environment_name, Some("dev".to_string()));
+        assert!(entry.truncated);
+        assert_eq!(entry.result.result, "{\"token\": \"*");
+        assert_eq!(
+            entry.result.sent_request.url,
+            "https://example.com/users?token=*****"
+        );
+        assert_eq!(entry.result.sent_request.headers[0].value, "*****");
+        assert_eq!(entry.result.headers[0].value, "*****");
+        assert!(entry.result.environment.is_none());
+        assert!(entry.request.auth.is_none());
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("hello", 10), ("hello", false));
+        assert_eq!(truncate("hello", 3), ("hel", true));
+        // 'é' takes two bytes and is not split
+        assert_eq!(truncate("café", 4), ("caf", true));
+    }
+
+    #[test]
+    fn test_summary_matches() {
+        let summary = summary("1", "2023-06-01T12:00:00+00:00");
+        let query = |search: &str| HistoryQuery {
+            search: Some(search.to_string()),
+            ..Default::default()
+        };
+        assert!(summary.matches(&query("USERS")));
+        assert!(summary.matches(&query("200")));
+        assert!(summary.matches(&query(" ")));
+        assert!(!summary.matches(&query("orders")));
+        assert!(!summary.matches(&HistoryQuery {
+            request_name: Some("Get orders".to_string()),
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn test_apply_retention() {
+        let history_dir =
+            std::env::temp_dir().join(format!("relynx-history-{}", uuid::Uuid::new_v4()));
+        let summaries = vec![
+            summary("1", "2023-04-01T12:00:00+00:00"),
+            summary("2", "2023-05-30T12:00:00+00:00"),
+            summary("3", "2023-05-31T12:00:00+00:00"),
+            summary("4", "2023-06-01T11:00:00+00:00"),
+        ];
+        write_index(&history_dir, &summaries).unwrap();
+        let now = DateTime::parse_from_rfc3339("2023-06-01T12:00:00+00:00")
+            .unwrap()
+            .with_timezone(&Utc);
+        let settings = HistorySettings {
+            max_entries: 2,
+            max_age_days: 30,
+            ..Default::default()
+        };
+
+        apply_retention(&history_dir, &settings, now).unwrap();
+        let ids: Vec<String> = read_index(&history_dir)
+            .unwrap()
+            .into_iter()
+            .map(|summary| summary.id)
+            .collect();
+        assert_eq!(ids, vec!["3".to_string(), "4".to_string()]);
+
+        std::fs::remove_dir_all(&history_dir).unwrap();
+    }
+}
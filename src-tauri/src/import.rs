@@ -5,7 +5,10 @@ use serde::{Deserialize, Serialize};
 use walkdir::{DirEntry, WalkDir};
 
 use crate::{
-    config::{load_collection_config, save_collection_config, save_workspace},
+    config::{
+        load_client_settings_or_default, load_collection_config, save_collection_config,
+        save_workspace,
+    },
     error::{ParseErrorMsg, RelynxError},
     model::{
         request_to_request_model, Collection, CollectionConfig, ImportWarning, RequestModel,
@@ -30,6 +33,8 @@ pub struct ImportCollectionResult {
 pub struct LoadRequestsResult {
     pub request_tree: RequestTree,
     pub errs: Vec<ParseErrorMsg>,
+    /// problems that did not prevent the requests from being loaded, such as malformed settings
+    pub warnings: Vec<String>,
 }
 
 pub const RELYNX_IGNORE_FILE: &str = ".relynxignore";
@@ -97,6 +102,11 @@ pub fn load_requests_for_collection(
     let mut nodes: HashMap<PathBuf, Vec<RefCell<RequestTreeNode>>> = HashMap::new();
     let mut root = RequestTreeNode::new_group(GroupOptions::FullPath(collection.path.clone()));
 
+    let collection_config =
+        load_collection_config(&collection.get_config_file_path()).unwrap_or_default();
+    let (client_settings, client_settings_warning) =
+        load_client_settings_or_default(&collection.path);
+
     let walker = WalkDir::new(&collection.path).into_iter();
 
     for entry in walker.filter_entry(|e| !hidden_relynx_folder(e)).flatten() {
@@ -112,6 +122,25 @@ pub fn load_requests_for_collection(
                     Ok((mut request_models, errs)) => {
                         parse_errs.extend(errs);
                         let path = entry.path().to_owned();
+                        collection_config.assign_request_ids(&path, &mut request_models);
+                        // configs written before requests had stored ids use the request name
+                        if let Some(auths) = collection_config.auth.requests.get(&path) {
+                            for request_model in request_models.iter_mut() {
+                                request_model.auth = auths
+                                    .get(&request_model.id)
+                                    .or_else(|| auths.get(&request_model.name))
+                                    .cloned();
+                            }
+                        }
+                        if let Some(settings) = client_settings.requests.get(&path) {
+                            for request_model in request_models.iter_mut() {
+                                request_model.client_settings = settings
+                                    .get(&request_model.id)
+                                    .or_else(|| settings.get(&request_model.name))
+                                    .cloned()
+                                    .unwrap_or_default();
+                            }
+                        }
 
                         let node = if request_models.len() == 1 {
                             RequestTreeNode::new_request_node(
@@ -130,9 +159,7 @@ pub fn load_requests_for_collection(
                         let elements = entry.or_insert(Vec::new());
                         elements.push(RefCell::new(node));
                     }
-                    Err(err) => {
-                        parse_errs.push(err)
-                    }
+                    Err(err) => parse_errs.push(err),
                 }
             }
         } else {
@@ -144,9 +171,6 @@ pub fn load_requests_for_collection(
         }
     }
 
-    let collection_config =
-        load_collection_config(&collection.get_config_file_path()).unwrap_or_default();
-
     let mut parents: Vec<&mut RequestTreeNode> = vec![&mut root];
     while !parents.is_empty() {
         let parent = parents.pop().unwrap();
@@ -176,6 +200,7 @@ pub fn load_requests_for_collection(
     Ok(LoadRequestsResult {
         request_tree: RequestTree { root },
         errs: parse_errs,
+        warnings: client_settings_warning.into_iter().collect(),
     })
 }
 
@@ -4,8 +4,8 @@ use std::path::{Path, PathBuf};
 use crate::config::save_workspace;
 use crate::error::RelynxError;
 use crate::model::{
-    query_params_from_url, Collection, DataSource, ImportCollectionResult, ImportWarning,
-    MessageSeverity, Multipart, Replaced, RequestBody, RequestModel, Workspace,
+    query_params_from_url, ClientSettings, Collection, DataSource, ImportCollectionResult,
+    ImportWarning, MessageSeverity, Multipart, Replaced, RequestBody, RequestModel, Workspace,
 };
 use crate::sanitize::sanitize_filename;
 use crate::tree::{GroupOptions, RequestTreeNode};
@@ -456,6 +456,8 @@ fn transform_request(
                 pre_request_script: None,
                 response_handler: None,
                 save_response: None,
+                auth: None,
+                client_settings: ClientSettings::default(),
             })
         }
     }
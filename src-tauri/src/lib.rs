@@ -0,0 +1,35 @@
+pub mod client;
+pub mod commands;
+pub mod config;
+pub mod cookie_jar;
+pub mod data_file;
+pub mod environment;
+pub mod error;
+pub mod history;
+pub mod import;
+pub mod license;
+pub mod model;
+pub mod pathdiff;
+pub mod redaction;
+pub mod report;
+pub mod runner;
+pub mod sanitize;
+pub mod scripting;
+pub mod tree;
+pub mod vault;
+
+pub static mut LICENSE_PUB_KEY: String = String::new();
+pub static mut LICENSE_PRIV_KEY: String = String::new();
+
+#[allow(clippy::result_unit_err)]
+pub fn get_license_pub_key() -> Result<String, ()> {
+    let key = unsafe { LICENSE_PUB_KEY.clone() };
+    if key.is_empty() {
+        return Err(());
+    }
+    Ok(key)
+}
+
+pub fn get_license_priv_key() -> String {
+    unsafe { LICENSE_PRIV_KEY.clone() }
+}
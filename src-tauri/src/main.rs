@@ -1,61 +1,41 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-mod client;
-mod commands;
-mod config;
-mod cookie_jar;
-mod environment;
-mod error;
-mod import;
-mod license;
-mod model;
-mod pathdiff;
-mod runner;
-mod sanitize;
-mod tree;
-
-use commands::{
+use log::LevelFilter;
+use relynx_lib::commands::{
     add_existing_collections, add_group_node, add_request_node, choose_file_relative_to,
-    copy_logfile_content_to_clipboard, copy_to_clipboard, delete_node, drag_and_drop,
-    get_app_environment, get_cookie_jar_command, get_log_path_command, get_response_filepath,
-    hide_group, import_jetbrains_folder_command, import_postman_collection, is_directory_empty,
-    is_signature_valid, load_environments, load_license_data_command, load_requests_for_collection,
-    load_workspace, open_folder_native, remove_collection, rename_group,
-    reorder_nodes_within_parent, run_request, save_cookie_jar_command, save_environments,
-    save_license_data_command, save_request, select_directory, select_file, update_workspace,
-    validate_group_name, validate_response_filepath, AddExistingCollectionsParams,
-    AddGroupNodeParams, AddRequestNodeParams, ChooseFileRelativeToParams, DeleteNodeParams,
-    DragAndDropParams, ImportJetbrainsHttpFolderParams, ImportPostmanCommandParams,
-    RenameGroupParams, ReorderNodesParams, SaveEnvironmentsParams, ValidateGroupNameParams,
+    clear_auth_tokens, clear_history, copy_logfile_content_to_clipboard, copy_to_clipboard,
+    delete_history_entry, delete_node, drag_and_drop, get_app_environment, get_client_settings,
+    get_cookie_jar_command, get_group_auth, get_history_entry, get_history_settings,
+    get_log_path_command, get_response_filepath, get_tls_profiles, get_vault_status, hide_group,
+    import_jetbrains_folder_command, import_postman_collection, is_directory_empty,
+    is_signature_valid, list_history, load_environments, load_license_data_command,
+    load_requests_for_collection, load_workspace, lock_vault, open_folder_native,
+    remove_collection, remove_response_temp_files, rename_group, reorder_nodes_within_parent,
+    rerun_history_entry, run_collection, run_request, save_client_settings_command,
+    save_cookie_jar_command, save_environments, save_group_auth, save_history_settings,
+    save_license_data_command, save_request, save_tls_profiles, select_directory, select_file,
+    unlock_vault, update_workspace, validate_group_name, validate_response_filepath,
+    AddExistingCollectionsParams, AddGroupNodeParams, AddRequestNodeParams,
+    ChooseFileRelativeToParams, DeleteNodeParams, DragAndDropParams, GetGroupAuthParams,
+    HistoryEntryParams, ImportJetbrainsHttpFolderParams, ImportPostmanCommandParams,
+    ListHistoryParams, RenameGroupParams, ReorderNodesParams, RerunHistoryEntryParams,
+    SaveClientSettingsParams, SaveEnvironmentsParams, SaveGroupAuthParams,
+    SaveHistorySettingsParams, SaveTlsProfilesParams, UnlockVaultParams, ValidateGroupNameParams,
     RELYNX_CONTEXT,
 };
-use config::get_data_dir;
-use cookie_jar::{GetCookieJarParams, SaveCookieJarParams};
-use license::LicenseData;
-use log::LevelFilter;
-use model::{Collection, RunRequestCommand, SaveRequestCommand, Workspace};
+use relynx_lib::config::get_data_dir;
+use relynx_lib::cookie_jar::{GetCookieJarParams, SaveCookieJarParams};
+use relynx_lib::license::LicenseData;
+use relynx_lib::model::{
+    Collection, RunCollectionCommand, RunRequestCommand, SaveRequestCommand, Workspace,
+};
+use relynx_lib::LICENSE_PUB_KEY;
 use rspc::Router;
 use std::{path::PathBuf, sync::Arc};
 use tauri::Manager;
 use tauri_plugin_log::LogTarget;
 
-static mut LICENSE_PUB_KEY: String = String::new();
-static mut LICENSE_PRIV_KEY: String = String::new();
-
-#[allow(clippy::result_unit_err)]
-pub fn get_license_pub_key() -> Result<String, ()> {
-    let key = unsafe { LICENSE_PUB_KEY.clone() };
-    if key.is_empty() {
-        return Err(());
-    }
-    Ok(key)
-}
-
-pub fn get_license_priv_key() -> String {
-    unsafe { LICENSE_PRIV_KEY.clone() }
-}
-
 fn router() -> Arc<Router> {
     let router =
         Router::new()
@@ -99,6 +79,9 @@ fn router() -> Arc<Router> {
             .query("run_request", |t| {
                 t(|_, command: RunRequestCommand| run_request(command))
             })
+            .query("run_collection", |t| {
+                t(|_, command: RunCollectionCommand| run_collection(command))
+            })
             .query("save_request", |t| {
                 t(|_, command: SaveRequestCommand| save_request(command))
             })
@@ -133,6 +116,33 @@ fn router() -> Arc<Router> {
             .query("save_environments", |t| {
                 t(|_, params: SaveEnvironmentsParams| save_environments(params))
             })
+            .query("get_vault_status", |t| t(|_, ()| get_vault_status()))
+            .mutation("unlock_vault", |t| {
+                t(|_, params: UnlockVaultParams| unlock_vault(params))
+            })
+            .mutation("lock_vault", |t| t(|_, ()| lock_vault()))
+            .mutation("clear_auth_tokens", |t| t(|_, ()| clear_auth_tokens()))
+            .query("list_history", |t| {
+                t(|_, params: ListHistoryParams| list_history(params))
+            })
+            .query("get_history_entry", |t| {
+                t(|_, params: HistoryEntryParams| get_history_entry(params))
+            })
+            .mutation("rerun_history_entry", |t| {
+                t(|_, params: RerunHistoryEntryParams| rerun_history_entry(params))
+            })
+            .mutation("delete_history_entry", |t| {
+                t(|_, params: HistoryEntryParams| delete_history_entry(params))
+            })
+            .mutation("clear_history", |t| {
+                t(|_, collection: Collection| clear_history(collection))
+            })
+            .query("get_history_settings", |t| {
+                t(|_, collection: Collection| get_history_settings(collection))
+            })
+            .mutation("save_history_settings", |t| {
+                t(|_, params: SaveHistorySettingsParams| save_history_settings(params))
+            })
             .query("get_response_filepath", |t| {
                 t(|_, params: PathBuf| get_response_filepath(params))
             })
@@ -146,6 +156,24 @@ fn router() -> Arc<Router> {
                 t(|_, params: RenameGroupParams| rename_group(params))
             })
             .query("hide_group", |t| t(|_, params: PathBuf| hide_group(params)))
+            .query("get_client_settings", |t| {
+                t(|_, params: Collection| get_client_settings(params))
+            })
+            .mutation("save_client_settings", |t| {
+                t(|_, params: SaveClientSettingsParams| save_client_settings_command(params))
+            })
+            .query("get_tls_profiles", |t| {
+                t(|_, params: Collection| get_tls_profiles(params))
+            })
+            .mutation("save_tls_profiles", |t| {
+                t(|_, params: SaveTlsProfilesParams| save_tls_profiles(params))
+            })
+            .query("get_group_auth", |t| {
+                t(|_, params: GetGroupAuthParams| get_group_auth(params))
+            })
+            .mutation("save_group_auth", |t| {
+                t(|_, params: SaveGroupAuthParams| save_group_auth(params))
+            })
             .query("choose_file_relative_to", |t| {
                 t(|_, params: ChooseFileRelativeToParams| choose_file_relative_to(params))
             })
@@ -219,6 +247,9 @@ fn main() {
         LICENSE_PUB_KEY = content;
     }
 
-    //
-    app.run(|_, _| {});
+    app.run(|_, event| {
+        if let tauri::RunEvent::Exit = event {
+            remove_response_temp_files();
+        }
+    });
 }
@@ -1,6 +1,7 @@
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 use cookie::{time::format_description, Expiration};
@@ -98,11 +99,122 @@ pub type PathOrder = HashMap<PathBuf, u32>;
 pub type EnvName = String;
 pub type EnvVarDescriptions = HashMap<EnvName, Vec<SingleEnvVarDescription>>;
 
+/// Auth of groups and requests, requests use the auth of their closest group if they do not
+/// define one themselves. The auth is kept here instead of the request files so they stay free of
+/// relynx specific content. Credentials are references to secrets such as `{{password}}`, they
+/// are not stored in plaintext.
+#[derive(Serialize, Deserialize, Type, Default, Debug, Clone, PartialEq)]
+pub struct AuthSettings {
+    /// folder or file group path -> auth
+    pub groups: HashMap<PathBuf, RequestAuth>,
+    /// request file path -> request id -> auth
+    pub requests: HashMap<PathBuf, HashMap<Uuid, RequestAuth>>,
+}
+
+/// The id of a request within a request file, the files do not contain ids so they are kept within
+/// the collection config
+#[derive(Serialize, Deserialize, Type, Debug, Clone, PartialEq)]
+pub struct StoredRequestId {
+    pub name: String,
+    pub id: Uuid,
+}
+
 #[derive(Serialize, Deserialize, Type, Default, Debug)]
 pub struct CollectionConfig {
     pub name: String,
     pub path_orders: PathOrder,
     pub env_var_descriptions: EnvVarDescriptions,
+    #[serde(default)]
+    pub auth: AuthSettings,
+    /// request file path -> ids of the requests in the order of the file
+    #[serde(default)]
+    pub request_ids: HashMap<PathBuf, Vec<StoredRequestId>>,
+}
+
+impl CollectionConfig {
+    /// Returns the auth a request is sent with, either its own or the one of the closest group
+    /// within the collection
+    pub fn resolve_auth(
+        &self,
+        request: &RequestModel,
+        collection_path: &Path,
+    ) -> Option<RequestAuth> {
+        if request.auth.is_some() {
+            return request.auth.clone();
+        }
+        request
+            .rest_file_path
+            .ancestors()
+            .take_while(|path| path.starts_with(collection_path))
+            .find_map(|path| self.auth.groups.get(path))
+            .cloned()
+    }
+
+    /// Returns the auth that is stored for the request itself, without the one of its groups
+    pub fn request_auth(&self, request: &RequestModel) -> Option<RequestAuth> {
+        self.auth
+            .requests
+            .get(&request.rest_file_path)
+            .and_then(|auths| auths.get(&request.id))
+            .cloned()
+    }
+
+    /// Stores the auth of the requests within a request file, requests without auth are removed
+    pub fn set_request_auth(&mut self, rest_file_path: &Path, requests: &[RequestModel]) {
+        let auths: HashMap<Uuid, RequestAuth> = requests
+            .iter()
+            .filter_map(|request| {
+                request
+                    .auth
+                    .as_ref()
+                    .map(|auth| (request.id.clone(), auth.clone()))
+            })
+            .collect();
+        if auths.is_empty() {
+            self.auth.requests.remove(rest_file_path);
+        } else {
+            self.auth.requests.insert(rest_file_path.to_owned(), auths);
+        }
+    }
+
+    /// Stores the ids of the requests within a request file so they are the same the next time
+    /// the file is loaded
+    pub fn set_request_ids(&mut self, rest_file_path: &Path, requests: &[RequestModel]) {
+        if requests.is_empty() {
+            self.request_ids.remove(rest_file_path);
+            return;
+        }
+        let ids = requests
+            .iter()
+            .map(|request| StoredRequestId {
+                name: request.name.clone(),
+                id: request.id.clone(),
+            })
+            .collect();
+        self.request_ids.insert(rest_file_path.to_owned(), ids);
+    }
+
+    /// Assigns the stored ids to the requests loaded from a request file. A request gets the id
+    /// stored at its position if the name matches, otherwise the id of another request with the
+    /// same name, so the ids are kept if the file has been reordered or edited outside of relynx.
+    pub fn assign_request_ids(&self, rest_file_path: &Path, requests: &mut [RequestModel]) {
+        let stored_ids = match self.request_ids.get(rest_file_path) {
+            Some(stored_ids) => stored_ids,
+            None => return,
+        };
+        let mut used = vec![false; stored_ids.len()];
+        for (index, request) in requests.iter_mut().enumerate() {
+            let is_match =
+                |position: usize| !used[position] && stored_ids[position].name == request.name;
+            let position = Some(index)
+                .filter(|index| *index < stored_ids.len() && is_match(*index))
+                .or_else(|| (0..stored_ids.len()).find(|position| is_match(*position)));
+            if let Some(position) = position {
+                used[position] = true;
+                request.id = stored_ids[position].id.clone();
+            }
+        }
+    }
 }
 
 pub type Uuid = String;
@@ -203,6 +315,8 @@ pub fn request_to_request_model(
         save_response: value.save_response.map(Into::<SaveResponse>::into),
         pre_request_script: value.pre_request_script,
         response_handler: value.response_handler,
+        auth: None,
+        client_settings: ClientSettings::default(),
     }
 }
 
@@ -259,6 +373,136 @@ impl From<&HttpRestFileHeader> for Header {
     }
 }
 
+/// How a request authenticates itself, applied by the client when the request is sent. All values
+/// may contain environment variables.
+#[derive(Serialize, Deserialize, Type, Debug, Clone, PartialEq, Eq)]
+pub enum RequestAuth {
+    /// the request is sent without auth even if its group has one
+    NoAuth,
+    Basic {
+        username: String,
+        password: String,
+    },
+    Bearer {
+        token: String,
+    },
+    Digest {
+        username: String,
+        password: String,
+    },
+    ApiKey {
+        name: String,
+        value: String,
+        location: ApiKeyLocation,
+    },
+    /// AWS Signature Version 4
+    AwsSigV4 {
+        access_key_id: String,
+        secret_access_key: String,
+        session_token: Option<String>,
+        region: String,
+        service: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Type, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiKeyLocation {
+    Header,
+    Query,
+}
+
+impl RequestAuth {
+    /// Returns the auth with the environment variables replaced within all values
+    pub fn with_env(&self, environment: Option<&Environment>) -> RequestAuth {
+        let replace = |value: &String| replace_variables_in_str(value, environment);
+        match self {
+            RequestAuth::NoAuth => RequestAuth::NoAuth,
+            RequestAuth::Basic { username, password } => RequestAuth::Basic {
+                username: replace(username),
+                password: replace(password),
+            },
+            RequestAuth::Bearer { token } => RequestAuth::Bearer {
+                token: replace(token),
+            },
+            RequestAuth::Digest { username, password } => RequestAuth::Digest {
+                username: replace(username),
+                password: replace(password),
+            },
+            RequestAuth::ApiKey {
+                name,
+                value,
+                location,
+            } => RequestAuth::ApiKey {
+                name: replace(name),
+                value: replace(value),
+                location: *location,
+            },
+            RequestAuth::AwsSigV4 {
+                access_key_id,
+                secret_access_key,
+                session_token,
+                region,
+                service,
+            } => RequestAuth::AwsSigV4 {
+                access_key_id: replace(access_key_id),
+                secret_access_key: replace(secret_access_key),
+                session_token: session_token.as_ref().map(replace),
+                region: replace(region),
+                service: replace(service),
+            },
+        }
+    }
+
+    /// Whether a credential of the auth is a plaintext value instead of a reference to a secret
+    /// such as `{{password}}`, the auth is stored within the collection folder
+    pub fn has_plaintext_credentials(&self) -> bool {
+        let credentials: Vec<&str> = match self {
+            RequestAuth::NoAuth => vec![],
+            RequestAuth::Basic { password, .. } | RequestAuth::Digest { password, .. } => {
+                vec![password.as_str()]
+            }
+            RequestAuth::Bearer { token } => vec![token.as_str()],
+            RequestAuth::ApiKey { value, .. } => vec![value.as_str()],
+            RequestAuth::AwsSigV4 {
+                secret_access_key,
+                session_token,
+                ..
+            } => std::iter::once(secret_access_key.as_str())
+                .chain(session_token.as_deref())
+                .collect(),
+        };
+        credentials.into_iter().any(is_plaintext_secret)
+    }
+
+    fn templates(&self) -> Vec<&str> {
+        match self {
+            RequestAuth::NoAuth => vec![],
+            RequestAuth::Basic { username, password }
+            | RequestAuth::Digest { username, password } => {
+                vec![username.as_str(), password.as_str()]
+            }
+            RequestAuth::Bearer { token } => vec![token.as_str()],
+            RequestAuth::ApiKey { name, value, .. } => vec![name.as_str(), value.as_str()],
+            RequestAuth::AwsSigV4 {
+                access_key_id,
+                secret_access_key,
+                session_token,
+                region,
+                service,
+            } => {
+                let mut templates = vec![
+                    access_key_id.as_str(),
+                    secret_access_key.as_str(),
+                    region.as_str(),
+                    service.as_str(),
+                ];
+                templates.extend(session_token.as_deref());
+                templates
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Type, Debug, Clone, PartialEq, Eq)]
 pub enum RequestBody {
     None,
@@ -323,6 +567,24 @@ impl ToString for DataSource<String> {
 }
 
 impl DataSource<String> {
+    /// Returns the data source with variables replaced within the raw data or the file path
+    pub fn with_env(&self, env: Option<&Environment>) -> DataSource<String> {
+        match self {
+            DataSource::Raw(raw) => DataSource::Raw(replace_variables_in_str(raw, env)),
+            DataSource::FromFilepath(path) => {
+                DataSource::FromFilepath(replace_variables_in_str(path, env))
+            }
+        }
+    }
+
+    /// The raw data or the file path, both may contain variables
+    fn template(&self) -> &str {
+        match self {
+            DataSource::Raw(raw) => raw,
+            DataSource::FromFilepath(path) => path,
+        }
+    }
+
     pub fn get_abs_path_relative_to(&self, request: &RequestModel) -> Option<PathBuf> {
         let request_folder = request.rest_file_path.parent()?;
         return self.get_abs_path(request_folder);
@@ -484,6 +746,12 @@ pub struct RequestModel {
     pub save_response: Option<SaveResponse>,
     pub pre_request_script: Option<PreRequestScript>,
     pub response_handler: Option<ResponseHandler>,
+    /// auth of the request itself, if not present the auth of the parent group is used
+    #[serde(default)]
+    pub auth: Option<RequestAuth>,
+    /// overrides of the client settings of the collection for this request
+    #[serde(default)]
+    pub client_settings: ClientSettings,
 }
 
 const DEFAULT_HTTP_EXTENSION: &str = "http";
@@ -508,6 +776,8 @@ impl Default for RequestModel {
             save_response: None,
             pre_request_script: None,
             response_handler: None,
+            auth: None,
+            client_settings: ClientSettings::default(),
         }
     }
 }
@@ -528,11 +798,11 @@ impl RequestModel {
         remove_inactive_params: bool,
         env: Option<&Environment>,
     ) -> String {
+        let url = replace_variables_in_str(&self.url, env);
         if env.is_none() {
-            return self.url.clone();
+            return url;
         }
         let env = env.unwrap();
-        let url = env.replace_values_in_str(&self.url);
 
         let host_header = self.get_header_values("Host", GetHeadersOption::JustValues);
         let mut url_with_host: Option<String> = None;
@@ -569,15 +839,11 @@ impl RequestModel {
     }
 
     pub fn get_query_params_with_env(&self, env: Option<&Environment>) -> Vec<QueryParam> {
-        if env.is_none() {
-            return self.query_params.clone();
-        }
-        let env = env.unwrap();
         self.query_params
             .iter()
             .map(|query_param| {
                 let mut new_param = query_param.clone();
-                new_param.value = env.replace_values_in_str(&new_param.value);
+                new_param.value = replace_variables_in_str(&new_param.value, env);
                 new_param
             })
             .collect()
@@ -593,15 +859,11 @@ impl RequestModel {
     }
 
     pub fn get_headers_with_env(&self, env: Option<&Environment>) -> Vec<Header> {
-        if env.is_none() {
-            return self.headers.clone();
-        }
-        let env = env.unwrap();
         self.headers
             .iter()
             .map(|header| {
                 let mut new_header = header.clone();
-                new_header.value = env.replace_values_in_str(&new_header.value);
+                new_header.value = replace_variables_in_str(&new_header.value, env);
                 new_header
             })
             .collect()
@@ -615,25 +877,56 @@ impl RequestModel {
             ref url_encoded_params,
         } = self.body
         {
-            if let Some(env) = env {
-                Some(
-                    url_encoded_params
-                        .iter()
-                        .map(|param| {
-                            let mut new_param = param.clone();
-                            new_param.value = env.replace_values_in_str(&new_param.value);
-                            new_param
-                        })
-                        .collect(),
-                )
-            } else {
-                Some(url_encoded_params.clone())
-            }
+            Some(
+                url_encoded_params
+                    .iter()
+                    .map(|param| {
+                        let mut new_param = param.clone();
+                        new_param.value = replace_variables_in_str(&new_param.value, env);
+                        new_param
+                    })
+                    .collect(),
+            )
         } else {
             None
         }
     }
 
+    /// Returns the names of all placeholders within the url, headers and body which cannot be
+    /// resolved with the environment. The contents of body files are not checked.
+    pub fn get_unresolved_variables(&self, env: Option<&Environment>) -> Vec<String> {
+        let mut templates: Vec<&str> = vec![self.url.as_str()];
+        templates.extend(self.query_params.iter().map(|param| param.value.as_str()));
+        templates.extend(self.headers.iter().map(|header| header.value.as_str()));
+        templates.extend(self.auth.iter().flat_map(RequestAuth::templates));
+        match self.body {
+            RequestBody::None => (),
+            RequestBody::Raw { ref data } => templates.push(data.template()),
+            RequestBody::UrlEncoded {
+                ref url_encoded_params,
+            } => templates.extend(url_encoded_params.iter().map(|param| param.value.as_str())),
+            RequestBody::Multipart { ref parts, .. } => {
+                for part in parts.iter() {
+                    templates.push(part.data.template());
+                    if let Some(ref filename) = part.disposition.filename {
+                        templates.push(filename);
+                    }
+                }
+            }
+        }
+
+        let mut unresolved: Vec<String> = Vec::new();
+        for name in templates
+            .into_iter()
+            .flat_map(|template| unresolved_variables(template, env))
+        {
+            if !unresolved.contains(&name) {
+                unresolved.push(name);
+            }
+        }
+        unresolved
+    }
+
     pub fn get_request_file_path(&self, parent_path: &str) -> String {
         let parent_path = std::path::Path::new(parent_path);
         let previous_path = std::path::PathBuf::from(&self.rest_file_path);
@@ -700,6 +993,8 @@ impl RequestModel {
             save_response: None,
             pre_request_script: None,
             response_handler: None,
+            auth: None,
+            client_settings: ClientSettings::default(),
         }
     }
 }
@@ -725,12 +1020,20 @@ impl<T> From<WithDefault<T>> for Replaced<T> {
     }
 }
 
-#[derive(Serialize, Deserialize, Type, Debug)]
+#[derive(Serialize, Deserialize, Type, Debug, Clone)]
 pub struct EnvironmentVariable {
     pub name: String,
     pub initial_value: String,
     pub current_value: Option<String>,
     pub description: Option<String>,
+    /// name of the environment the variable is inherited from, `None` if the environment defines
+    /// it itself
+    #[serde(default)]
+    pub source: Option<String>,
+    /// the value is a json number, boolean, array or object within the env file instead of a
+    /// string, it is written back as such
+    #[serde(default)]
+    pub is_json: bool,
 }
 
 impl EnvironmentVariable {
@@ -744,17 +1047,26 @@ impl EnvironmentVariable {
             initial_value: initial_value.into(),
             current_value: None,
             description: None,
+            source: None,
+            is_json: false,
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Type, Debug)]
+#[derive(Serialize, Deserialize, Type, Debug, Clone)]
 pub struct EnvironmentSecret {
     pub name: String,
     pub initial_value: String,
     pub current_value: Option<String>,
     pub description: Option<String>,
     pub persist_to_file: bool,
+    /// name of the environment the secret is inherited from, `None` if the environment defines
+    /// it itself
+    #[serde(default)]
+    pub source: Option<String>,
+    /// see [`EnvironmentVariable::is_json`]
+    #[serde(default)]
+    pub is_json: bool,
 }
 
 #[derive(Serialize, Deserialize, Type, Debug)]
@@ -764,11 +1076,364 @@ pub struct SingleEnvVarDescription {
     pub is_secret: bool,
 }
 
-#[derive(Serialize, Deserialize, Type, Debug)]
+#[derive(Serialize, Deserialize, Type, Debug, Clone)]
 pub struct Environment {
     pub name: String,
     pub variables: Vec<EnvironmentVariable>,
     pub secrets: Vec<EnvironmentSecret>,
+    /// name of the environment whose variables and secrets are inherited
+    #[serde(default)]
+    pub extends: Option<String>,
+    /// the `SSLConfiguration` section of the env file
+    #[serde(default)]
+    pub ssl_configuration: Option<SslConfiguration>,
+    /// OAuth 2.0 configurations of the `Security.Auth` section of the env file by their id, a
+    /// token is used within a request with `{{$auth.token("id")}}`
+    #[serde(default)]
+    pub auth_configurations: HashMap<String, OAuth2Configuration>,
+    /// whether the vault was unlocked when the environment has been loaded, only then the secrets
+    /// of the vault are part of the environment
+    #[serde(default)]
+    pub vault_loaded: bool,
+}
+
+#[derive(Serialize, Deserialize, Type, Debug, Clone, PartialEq)]
+pub struct OAuth2Configuration {
+    pub grant_type: OAuth2GrantType,
+    pub token_url: String,
+    /// url of the authorization endpoint, only used for the authorization code grant
+    pub auth_url: Option<String>,
+    /// loopback url the authorization code is sent to, only used for the authorization code grant
+    pub redirect_url: Option<String>,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub client_credentials: OAuth2ClientCredentials,
+    pub scope: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub pkce: bool,
+    /// additional parameters sent with the authorization and token requests
+    pub custom_parameters: HashMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Type, Debug, Clone, Copy, PartialEq)]
+pub enum OAuth2GrantType {
+    ClientCredentials,
+    Password,
+    AuthorizationCode,
+}
+
+/// How the client id and secret are sent to the token endpoint
+#[derive(Serialize, Deserialize, Type, Debug, Clone, Copy, PartialEq)]
+pub enum OAuth2ClientCredentials {
+    /// as basic authentication header
+    Basic,
+    /// as form parameters within the body
+    InBody,
+    /// only the client id is sent within the body
+    None,
+}
+
+/// Client certificate and verification settings of an environment, the same as within the
+/// `SSLConfiguration` section of the JetBrains http client env files
+#[derive(Serialize, Deserialize, Type, Debug, Clone, PartialEq)]
+pub struct SslConfiguration {
+    /// absolute path of the client certificate
+    pub client_certificate: Option<PathBuf>,
+    #[serde(default)]
+    pub client_certificate_format: CertificateFormat,
+    /// absolute path of the private key of the client certificate
+    pub client_certificate_key: Option<PathBuf>,
+    /// the passphrase is taken from the secret [`CERTIFICATE_PASSPHRASE_SECRET`]
+    #[serde(default)]
+    pub has_certificate_passphrase: bool,
+    pub verify_host_certificate: bool,
+}
+
+impl SslConfiguration {
+    /// Uses the client certificate of the environment for requests and disables the verification
+    /// of the server certificate if the environment requests it
+    pub fn apply_to(&self, options: &mut ClientOptions) {
+        if let Some(ref client_certificate) = self.client_certificate {
+            options.client_cert_file = Some(client_certificate.to_string_lossy().to_string());
+            options.client_cert_format = self.client_certificate_format;
+        }
+        if self.has_certificate_passphrase {
+            // the secret is resolved when the certificate is used, so its value is not part of
+            // the options
+            options.client_key_password =
+                Some(format!("{{{{{}}}}}", CERTIFICATE_PASSPHRASE_SECRET));
+        }
+        if let Some(ref client_certificate_key) = self.client_certificate_key {
+            options.client_key_file = Some(client_certificate_key.to_string_lossy().to_string());
+        }
+        if !self.verify_host_certificate {
+            options.insecure = true;
+        }
+    }
+}
+
+/// Settings of the http client such as timeouts, proxy or certificates. Every value is optional,
+/// values that are not set keep the value of the level below. Requests override the settings of
+/// their collection which override the defaults of [`ClientOptions`].
+#[derive(Serialize, Deserialize, Type, Default, Debug, Clone, PartialEq, Eq)]
+pub struct ClientSettings {
+    /// in seconds
+    pub timeout: Option<u32>,
+    /// in seconds
+    pub connect_timeout: Option<u32>,
+    pub proxy: Option<String>,
+    pub no_proxy: Option<String>,
+    pub insecure: Option<bool>,
+    pub cacert_file: Option<PathBuf>,
+    pub client_cert_file: Option<PathBuf>,
+    pub client_key_file: Option<PathBuf>,
+    /// entries in the form of `host:port:address`
+    pub resolves: Option<Vec<String>>,
+    /// entries in the form of `host:port:connect-to-host:connect-to-port`
+    pub connects_to: Option<Vec<String>>,
+    pub max_redirect: Option<u32>,
+    pub compressed: Option<bool>,
+    pub user_agent: Option<String>,
+    /// warn about server certificates that expire within this number of days
+    pub certificate_expiry_warning_days: Option<u32>,
+}
+
+impl ClientSettings {
+    pub fn is_empty(&self) -> bool {
+        *self == ClientSettings::default()
+    }
+
+    /// Sets all values that are present within the settings
+    pub fn apply_to(&self, options: &mut ClientOptions) {
+        let path_to_string = |path: &PathBuf| path.to_string_lossy().to_string();
+        if let Some(timeout) = self.timeout {
+            options.timeout = Duration::from_secs(timeout as u64);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            options.connect_timeout = Duration::from_secs(connect_timeout as u64);
+        }
+        if let Some(ref proxy) = self.proxy {
+            options.proxy = Some(proxy.clone());
+        }
+        if let Some(ref no_proxy) = self.no_proxy {
+            options.no_proxy = Some(no_proxy.clone());
+        }
+        if let Some(insecure) = self.insecure {
+            options.insecure = insecure;
+        }
+        if let Some(ref cacert_file) = self.cacert_file {
+            options.cacert_file = Some(path_to_string(cacert_file));
+        }
+        if let Some(ref client_cert_file) = self.client_cert_file {
+            options.client_cert_file = Some(path_to_string(client_cert_file));
+            options.client_cert_format = CertificateFormat::Pem;
+        }
+        if let Some(ref client_key_file) = self.client_key_file {
+            options.client_key_file = Some(path_to_string(client_key_file));
+        }
+        if let Some(ref resolves) = self.resolves {
+            options.resolves = resolves.clone();
+        }
+        if let Some(ref connects_to) = self.connects_to {
+            options.connects_to = connects_to.clone();
+        }
+        if let Some(max_redirect) = self.max_redirect {
+            options.max_redirect = Some(max_redirect as usize);
+        }
+        if let Some(compressed) = self.compressed {
+            options.compressed = compressed;
+        }
+        if let Some(ref user_agent) = self.user_agent {
+            options.user_agent = Some(user_agent.clone());
+        }
+        if let Some(days) = self.certificate_expiry_warning_days {
+            options.certificate_expiry_warning_days = days;
+        }
+    }
+
+    /// Returns the settings with relative file paths resolved against the given folder
+    pub fn with_absolute_paths(&self, base_path: &Path) -> ClientSettings {
+        self.with_mapped_paths(|path| base_path.join(path))
+    }
+
+    /// Returns the settings with file paths within the given folder made relative to it, so the
+    /// settings can be shared between machines
+    pub fn with_relative_paths(&self, base_path: &Path) -> ClientSettings {
+        self.with_mapped_paths(|path| relative_path_within(path, base_path))
+    }
+
+    fn with_mapped_paths(&self, map: impl Fn(&Path) -> PathBuf) -> ClientSettings {
+        ClientSettings {
+            cacert_file: self.cacert_file.as_deref().map(&map),
+            client_cert_file: self.client_cert_file.as_deref().map(&map),
+            client_key_file: self.client_key_file.as_deref().map(&map),
+            ..self.clone()
+        }
+    }
+}
+
+/// Makes a path relative to a base folder if it is within it
+fn relative_path_within(path: &Path, base_path: &Path) -> PathBuf {
+    if path.starts_with(base_path) {
+        diff_paths(path, base_path).unwrap_or_else(|| path.to_owned())
+    } else {
+        path.to_owned()
+    }
+}
+
+/// Certificates used for the connection to all hosts matching a pattern, e.g. for services that
+/// require mutual TLS
+#[derive(Serialize, Deserialize, Type, Clone, PartialEq, Eq)]
+pub struct TlsProfile {
+    /// glob pattern such as `*.internal.example.com`, if it contains a port such as
+    /// `api.example.com:8443` only connections to that port match
+    pub host: String,
+    pub client_certificate: Option<PathBuf>,
+    #[serde(default)]
+    pub client_certificate_format: CertificateFormat,
+    /// private key of a PEM certificate, PKCS#12 files contain the key themselves
+    pub client_key: Option<PathBuf>,
+    /// passphrase of the private key or the PKCS#12 file, has to reference a secret of the
+    /// environment such as `{{passphrase}}`
+    pub passphrase: Option<String>,
+    /// PEM files with the certificate authorities that are trusted for matching hosts instead of
+    /// the default ones
+    #[serde(default)]
+    pub ca_bundles: Vec<PathBuf>,
+    /// sha256 hashes of the public keys the server may use, either `sha256//<base64>` or just the
+    /// base64 encoded hash
+    #[serde(default)]
+    pub pinned_public_keys: Vec<String>,
+}
+
+// the passphrase is not part of the debug output as options are written to the logs
+impl std::fmt::Debug for TlsProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsProfile")
+            .field("host", &self.host)
+            .field("client_certificate", &self.client_certificate)
+            .field("client_certificate_format", &self.client_certificate_format)
+            .field("client_key", &self.client_key)
+            .field("passphrase", &self.passphrase.as_ref().map(|_| REDACTED))
+            .field("ca_bundles", &self.ca_bundles)
+            .field("pinned_public_keys", &self.pinned_public_keys)
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, Type, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CertificateFormat {
+    #[default]
+    Pem,
+    Pkcs12,
+}
+
+/// Whether a value that is stored within the collection folder contains text besides references
+/// to secrets such as `{{password}}`
+fn is_plaintext_secret(value: &str) -> bool {
+    !render_template(value, &|_: &str| Some(String::new()))
+        .value
+        .trim()
+        .is_empty()
+}
+
+impl TlsProfile {
+    /// Whether the passphrase is a plaintext value instead of a reference to a secret, the
+    /// profiles are stored within the collection folder
+    pub fn has_plaintext_passphrase(&self) -> bool {
+        self.passphrase.as_deref().is_some_and(is_plaintext_secret)
+    }
+
+    fn with_mapped_paths(&self, map: impl Fn(&Path) -> PathBuf) -> TlsProfile {
+        TlsProfile {
+            client_certificate: self.client_certificate.as_deref().map(&map),
+            client_key: self.client_key.as_deref().map(&map),
+            ca_bundles: self.ca_bundles.iter().map(|path| map(path)).collect(),
+            ..self.clone()
+        }
+    }
+}
+
+/// The client settings of a collection and the overrides of its requests as they are stored
+/// within the client settings file next to the collection config
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+pub struct CollectionClientSettings {
+    #[serde(default)]
+    pub defaults: ClientSettings,
+    /// the first profile matching the host of a request is used
+    #[serde(default)]
+    pub tls_profiles: Vec<TlsProfile>,
+    /// request file path -> request id -> settings
+    #[serde(default)]
+    pub requests: HashMap<PathBuf, HashMap<Uuid, ClientSettings>>,
+}
+
+impl CollectionClientSettings {
+    /// Stores the settings of the requests within a request file, requests without settings are
+    /// removed
+    pub fn set_request_settings(&mut self, rest_file_path: &Path, requests: &[RequestModel]) {
+        let settings: HashMap<Uuid, ClientSettings> = requests
+            .iter()
+            .filter(|request| !request.client_settings.is_empty())
+            .map(|request| (request.id.clone(), request.client_settings.clone()))
+            .collect();
+        if settings.is_empty() {
+            self.requests.remove(rest_file_path);
+        } else {
+            self.requests.insert(rest_file_path.to_owned(), settings);
+        }
+    }
+
+    /// Returns the settings with all paths, including the request file paths, resolved against
+    /// the collection folder
+    pub fn with_absolute_paths(self, collection_path: &Path) -> CollectionClientSettings {
+        CollectionClientSettings {
+            defaults: self.defaults.with_absolute_paths(collection_path),
+            tls_profiles: self
+                .tls_profiles
+                .iter()
+                .map(|profile| profile.with_mapped_paths(|path| collection_path.join(path)))
+                .collect(),
+            requests: self
+                .requests
+                .into_iter()
+                .map(|(path, settings)| {
+                    let settings = settings
+                        .into_iter()
+                        .map(|(id, settings)| (id, settings.with_absolute_paths(collection_path)))
+                        .collect();
+                    (collection_path.join(path), settings)
+                })
+                .collect(),
+        }
+    }
+
+    /// Inverse of [`CollectionClientSettings::with_absolute_paths`]
+    pub fn with_relative_paths(self, collection_path: &Path) -> CollectionClientSettings {
+        CollectionClientSettings {
+            defaults: self.defaults.with_relative_paths(collection_path),
+            tls_profiles: self
+                .tls_profiles
+                .iter()
+                .map(|profile| {
+                    profile.with_mapped_paths(|path| relative_path_within(path, collection_path))
+                })
+                .collect(),
+            requests: self
+                .requests
+                .into_iter()
+                .map(|(path, settings)| {
+                    let settings = settings
+                        .into_iter()
+                        .map(|(id, settings)| (id, settings.with_relative_paths(collection_path)))
+                        .collect();
+                    let path = diff_paths(&path, collection_path).unwrap_or(path);
+                    (path, settings)
+                })
+                .collect(),
+        }
+    }
 }
 
 impl Environment {
@@ -777,33 +1442,118 @@ impl Environment {
             name,
             variables: vec![],
             secrets: vec![],
+            extends: None,
+            ssl_configuration: None,
+            auth_configurations: HashMap::new(),
+            vault_loaded: false,
         }
     }
 
-    pub fn replace_values_in_str(&self, str: &str) -> String {
-        let mut result = str.to_string();
-        for variable in &self.variables {
-            let replace_key = "{{".to_string() + &variable.name + "}}";
-            if result.contains(&replace_key) {
-                let value = variable
-                    .current_value
-                    .as_ref()
-                    .unwrap_or(&variable.initial_value);
-                result = result.replace(&replace_key, value);
-            }
+    /// Sets the current value of a variable, if it does not exist yet it is added without an
+    /// initial value so it is not persisted when the environment is saved
+    pub fn set_variable(&mut self, name: &str, value: String) {
+        match self
+            .variables
+            .iter_mut()
+            .find(|variable| variable.name == name)
+        {
+            Some(variable) => variable.current_value = Some(value),
+            None => self.variables.push(EnvironmentVariable {
+                name: name.to_string(),
+                initial_value: String::new(),
+                current_value: Some(value),
+                description: None,
+                source: None,
+                is_json: false,
+            }),
         }
+    }
 
-        for secret in &self.secrets {
-            let replace_key = format!("{{{}}}", secret.name);
-            if result.contains(&replace_key) {
-                let value = secret
-                    .current_value
-                    .as_ref()
-                    .unwrap_or(&secret.initial_value);
-                result = result.replace(&replace_key, value);
+    /// Removes a variable that has been set at runtime. Variables defined within the env file
+    /// keep their initial value and only lose the value that has been set.
+    pub fn remove_variable(&mut self, name: &str) {
+        self.variables.retain_mut(|variable| {
+            if variable.name != name {
+                return true;
             }
+            variable.current_value = None;
+            !variable.initial_value.is_empty()
+        });
+    }
+
+    /// Writes the globals a script has set or cleared back into the environment
+    pub fn apply_script_globals(&mut self, changed: HashMap<String, String>, removed: &[String]) {
+        for name in removed {
+            self.remove_variable(name);
         }
-        result
+        for (name, value) in changed {
+            self.set_variable(&name, value);
+        }
+    }
+
+    /// Returns if the environment has a variable or secret with the given name
+    pub fn is_defined(&self, name: &str) -> bool {
+        self.variables.iter().any(|variable| variable.name == name)
+            || self.secrets.iter().any(|secret| secret.name == name)
+    }
+
+    /// Returns the current or initial value of a variable or secret. Values within json objects
+    /// and arrays are accessed with a dotted path such as `db.host` or `servers.0`.
+    pub fn get_value(&self, name: &str) -> Option<String> {
+        if let Some((value, _)) = self.find_value(name) {
+            return Some(value.clone());
+        }
+        // the longest variable name matching the start of the path wins
+        name.match_indices('.').rev().find_map(|(index, _)| {
+            let (value, is_json) = self.find_value(&name[..index])?;
+            if !is_json {
+                return None;
+            }
+            let json = serde_json::from_str::<serde_json::Value>(value).ok()?;
+            let path = name[index + 1..].replace('.', "/");
+            match json.pointer(&format!("/{}", path))? {
+                serde_json::Value::String(value) => Some(value.clone()),
+                value => Some(value.to_string()),
+            }
+        })
+    }
+
+    /// Returns the value of a variable or secret and if it is a json value
+    fn find_value(&self, name: &str) -> Option<(&String, bool)> {
+        let variable = self
+            .variables
+            .iter()
+            .find(|variable| variable.name == name)
+            .map(|variable| {
+                (
+                    variable
+                        .current_value
+                        .as_ref()
+                        .unwrap_or(&variable.initial_value),
+                    variable.is_json,
+                )
+            });
+        let secret = || {
+            self.secrets
+                .iter()
+                .find(|secret| secret.name == name)
+                .map(|secret| {
+                    (
+                        secret
+                            .current_value
+                            .as_ref()
+                            .unwrap_or(&secret.initial_value),
+                        secret.is_json,
+                    )
+                })
+        };
+        variable.or_else(secret)
+    }
+
+    /// Replaces the variables and secrets of the environment, dynamic variables and unknown
+    /// variables are kept as they are
+    pub fn replace_values_in_str(&self, str: &str) -> String {
+        render_template(str, &|name: &str| self.get_value(name)).value
     }
 }
 
@@ -814,20 +1564,178 @@ pub struct RunRequestCommand {
     pub environment: Option<Environment>,
 }
 
+#[derive(Serialize, Deserialize, Type, Debug)]
+pub struct RunCollectionCommand {
+    pub collection: Collection,
+    /// folder or file group within the collection that is run, the whole collection if not present
+    pub group_path: Option<PathBuf>,
+    pub environment: Option<Environment>,
+    /// csv or json file, the requests are run once for each of its rows
+    pub data_file: Option<PathBuf>,
+}
+
+/// Emitted to the frontend after each request of a collection run
+#[derive(Serialize, Deserialize, Type, Debug, Clone)]
+pub struct RunCollectionProgress {
+    pub iteration: u32,
+    pub total_iterations: u32,
+    pub index: u32,
+    pub total: u32,
+    pub request_id: Uuid,
+    pub request_name: String,
+    pub rest_file_path: PathBuf,
+    /// the result does not contain the environment, it is part of the [`RunCollectionResult`]
+    pub result: Option<RequestResult>,
+    /// set if the request could not be sent
+    pub error: Option<String>,
+}
+
+/// All requests run with the variables of one data file row, a run without data file has a single
+/// iteration without data
+#[derive(Serialize, Deserialize, Type, Debug)]
+pub struct RunCollectionIteration {
+    pub index: u32,
+    pub data: HashMap<String, String>,
+    pub runs: Vec<RunCollectionProgress>,
+}
+
+#[derive(Serialize, Deserialize, Type, Debug)]
+pub struct RunCollectionResult {
+    pub iterations: Vec<RunCollectionIteration>,
+    /// the environment after all requests have run
+    pub environment: Option<Environment>,
+}
+
 pub type ContentType = String;
 pub type StatusCode = String;
 
-#[derive(Serialize, Deserialize, Type, Debug)]
+#[derive(Serialize, Deserialize, Type, Debug, Clone)]
 pub struct RequestResult {
     pub id: Uuid, // internal id, not from request itself
+    /// the decoded body, empty for binary responses
     pub result: String,
+    /// the charset the body has been decoded with, not present for binary responses
+    pub charset: Option<String>,
+    /// the body of responses that can not be displayed as text such as images
+    pub binary: Option<BinaryPayload>,
     pub status_code: StatusCode,
+    /// the http version the server responded with, such as `HTTP/2`
+    pub http_version: String,
     pub total_time: f64,
     pub total_result_size: f64,
+    /// size of the body as received, differs from `total_result_size` for compressed responses
+    pub raw_result_size: f64,
     pub content_type: Option<ContentType>,
     pub warnings: Vec<String>,
     pub result_file: Option<PathBuf>,
     pub result_file_folder: Option<PathBuf>,
+    /// the environment after response handler scripts have set global variables
+    pub environment: Option<Environment>,
+    /// checks from `client.test` and `client.assert` within the response handler
+    pub tests: Vec<TestResult>,
+    /// negotiated tls parameters and server certificates, only present for tls connections
+    pub tls: Option<TlsInfo>,
+    /// timing breakdown of every call, one per redirect hop
+    pub timings: Vec<CallTimings>,
+    /// all headers of the final response
+    pub headers: Vec<Header>,
+    /// the calls that have been redirected before the final response, in the order they were sent
+    pub redirects: Vec<RedirectHop>,
+    /// the final request as it has been sent by the http client
+    pub sent_request: SentRequest,
+}
+
+#[derive(Serialize, Deserialize, Type, Debug, Clone, PartialEq)]
+pub struct BinaryPayload {
+    pub mime_type: Option<String>,
+    /// in bytes
+    pub size: f64,
+    pub content: BinaryContent,
+}
+
+/// Small bodies are passed base64 encoded, larger ones are written to a temporary file
+#[derive(Serialize, Deserialize, Type, Debug, Clone, PartialEq, Eq)]
+pub enum BinaryContent {
+    Base64(String),
+    File(PathBuf),
+}
+
+#[derive(Serialize, Deserialize, Type, Debug, Clone, PartialEq, Eq)]
+pub struct RedirectHop {
+    pub url: String,
+    pub status_code: u32,
+    /// the location header of the response, as sent by the server
+    pub location: Option<String>,
+    /// the request of this hop as it has been sent by the http client
+    pub request: SentRequest,
+}
+
+/// A request as it went over the wire, including the headers added by the http client such as
+/// `Host`, `User-Agent` or auth headers
+#[derive(Serialize, Deserialize, Type, Debug, Clone, PartialEq, Eq)]
+pub struct SentRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<Header>,
+    pub body: String,
+}
+
+/// Timing breakdown, sizes and speeds of a single call, used for the result of a request as well
+/// as for run reports. The durations are in seconds and measured from the start of the call, so
+/// they are cumulative: `connect` includes `name_lookup` and so on.
+#[derive(Serialize, Deserialize, Type, Debug, Clone, PartialEq)]
+pub struct CallTimings {
+    pub url: String,
+    pub status_code: u32,
+    /// rfc3339 timestamps of the start and the end of the call
+    pub begin_call: String,
+    pub end_call: String,
+    pub name_lookup: f64,
+    pub connect: f64,
+    /// tls handshake completed, zero for plain connections
+    pub app_connect: f64,
+    pub pre_transfer: f64,
+    /// time to first byte
+    pub start_transfer: f64,
+    pub total: f64,
+    /// uploaded body bytes
+    pub size_upload: f64,
+    /// downloaded body bytes
+    pub size_download: f64,
+    /// bytes per second
+    pub speed_upload: f64,
+    /// bytes per second
+    pub speed_download: f64,
+}
+
+/// Information about the tls connection of the final response
+#[derive(Serialize, Deserialize, Type, Debug, Clone, PartialEq, Eq)]
+pub struct TlsInfo {
+    /// the negotiated protocol version such as `TLSv1.3`
+    pub version: Option<String>,
+    pub cipher: Option<String>,
+    /// the certificate chain sent by the server, starting with the end-user certificate
+    pub certificates: Vec<CertificateInfo>,
+}
+
+#[derive(Serialize, Deserialize, Type, Debug, Clone, PartialEq, Eq)]
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    /// RFC 3339 date
+    pub start_date: String,
+    /// RFC 3339 date
+    pub expire_date: String,
+    pub serial_number: String,
+}
+
+#[derive(Serialize, Deserialize, Type, Debug, Clone, PartialEq)]
+pub struct TestResult {
+    pub name: String,
+    pub passed: bool,
+    pub message: Option<String>,
+    /// duration in seconds, same as the `total_time` of a `RequestResult`
+    pub duration: f64,
 }
 
 #[derive(Serialize, Deserialize, Type, Debug)]
@@ -858,7 +1766,16 @@ use http_rest_file::model::RequestBody as RestFileBody;
 use url::Url;
 
 use crate::{
-    config::COLLECTION_CONFIGFILE, sanitize::sanitize_filename_with_options, tree::DEFAULT_OPTIONS,
+    client::options::ClientOptions,
+    config::COLLECTION_CONFIGFILE,
+    environment::{
+        render_template, replace_variables_in_str, unresolved_variables,
+        CERTIFICATE_PASSPHRASE_SECRET,
+    },
+    pathdiff::diff_paths,
+    redaction::{Redactor, REDACTED},
+    sanitize::sanitize_filename_with_options,
+    tree::DEFAULT_OPTIONS,
 };
 impl From<RequestBody> for http_rest_file::model::RequestBody {
     fn from(value: RequestBody) -> Self {
@@ -933,35 +1850,50 @@ impl From<&RequestModel> for http_rest_file::model::Request {
     }
 }
 
+/// Logs the messages of a single request run. Secret values of the environment and credential
+/// headers are redacted within every message.
 pub struct RunLogger {
     no_log: bool,
+    redactor: Redactor,
 }
 
 impl RunLogger {
-    pub fn new(no_log: bool) -> Self {
-        log::warn!("Not logging current request as no_log is set!");
-        RunLogger { no_log }
+    pub fn new(no_log: bool, environment: Option<&Environment>) -> Self {
+        RunLogger::with_redactor(no_log, Redactor::new(environment))
+    }
+
+    /// Creates a logger that redacts with the given redactor, such as the one shared by all
+    /// requests of a run
+    pub fn with_redactor(no_log: bool, redactor: Redactor) -> Self {
+        if no_log {
+            log::warn!("Not logging current request as no_log is set!");
+        }
+        RunLogger { no_log, redactor }
+    }
+
+    pub fn redactor(&self) -> &Redactor {
+        &self.redactor
     }
 
     pub fn log_error<S: AsRef<str>>(&self, msg: S) {
         if self.no_log {
             return;
         }
-        log::error!("{}", msg.as_ref());
+        log::error!("{}", self.redactor.redact(msg.as_ref()));
     }
 
     pub fn log_info<S: AsRef<str>>(&self, msg: S) {
         if self.no_log {
             return;
         }
-        log::info!("{}", msg.as_ref());
+        log::info!("{}", self.redactor.redact(msg.as_ref()));
     }
 
     pub fn log_debug<S: AsRef<str>>(&self, msg: S) {
         if self.no_log {
             return;
         }
-        log::debug!("{}", msg.as_ref());
+        log::debug!("{}", self.redactor.redact(msg.as_ref()));
     }
 }
 
@@ -1015,3 +1947,161 @@ impl ToString for Cookie {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assign_request_ids() {
+        let path = PathBuf::from("/collection/requests.http");
+        let request = |name: &str| RequestModel::new(name.to_string(), &path);
+        let mut collection_config = CollectionConfig::default();
+        collection_config
+            .set_request_ids(&path, &[request("login"), request("list"), request("list")]);
+        let stored: Vec<Uuid> = collection_config.request_ids[&path]
+            .iter()
+            .map(|stored| stored.id.clone())
+            .collect();
+
+        // reordered outside of relynx and a new request added
+        let mut requests = vec![
+            request("list"),
+            request("login"),
+            request("list"),
+            request("new"),
+        ];
+        let new_id = requests[3].id.clone();
+        collection_config.assign_request_ids(&path, &mut requests);
+        let ids: Vec<Uuid> = requests.iter().map(|request| request.id.clone()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                stored[1].clone(),
+                stored[0].clone(),
+                stored[2].clone(),
+                new_id
+            ]
+        );
+    }
+
+    #[test]
+    fn test_client_settings_precedence() {
+        let collection_settings = ClientSettings {
+            timeout: Some(10),
+            proxy: Some("http://proxy:3128".to_string()),
+            insecure: Some(true),
+            ..Default::default()
+        };
+        let request_settings = ClientSettings {
+            timeout: Some(60),
+            insecure: Some(false),
+            ..Default::default()
+        };
+        let mut options = ClientOptions::default();
+        collection_settings.apply_to(&mut options);
+        request_settings.apply_to(&mut options);
+
+        assert_eq!(options.timeout, Duration::from_secs(60));
+        assert!(!options.insecure);
+        assert_eq!(options.proxy.as_deref(), Some("http://proxy:3128"));
+        // values that are not set at all keep the defaults of the client
+        assert_eq!(
+            options.connect_timeout,
+            ClientOptions::default().connect_timeout
+        );
+        assert_eq!(options.max_redirect, ClientOptions::default().max_redirect);
+    }
+
+    #[test]
+    fn test_client_settings_relative_paths_roundtrip() {
+        let collection_path = PathBuf::from("/collection");
+        let settings = CollectionClientSettings {
+            defaults: ClientSettings {
+                cacert_file: Some(collection_path.join("certs/ca.pem")),
+                client_cert_file: Some(PathBuf::from("/etc/ssl/client.pem")),
+                ..Default::default()
+            },
+            tls_profiles: vec![TlsProfile {
+                host: "*.internal".to_string(),
+                client_certificate: Some(collection_path.join("certs/client.p12")),
+                client_certificate_format: CertificateFormat::Pkcs12,
+                client_key: None,
+                passphrase: Some("{{passphrase}}".to_string()),
+                ca_bundles: vec![collection_path.join("certs/internal-ca.pem")],
+                pinned_public_keys: vec![],
+            }],
+            requests: HashMap::from([(
+                collection_path.join("users/get_users.http"),
+                HashMap::from([(
+                    "request-id".to_string(),
+                    ClientSettings {
+                        client_key_file: Some(collection_path.join("certs/key.pem")),
+                        ..Default::default()
+                    },
+                )]),
+            )]),
+        };
+
+        let relative = settings.clone().with_relative_paths(&collection_path);
+        assert_eq!(
+            relative.defaults.cacert_file,
+            Some(PathBuf::from("certs/ca.pem"))
+        );
+        // paths outside of the collection stay absolute
+        assert_eq!(
+            relative.defaults.client_cert_file,
+            Some(PathBuf::from("/etc/ssl/client.pem"))
+        );
+        assert_eq!(
+            relative.tls_profiles[0].ca_bundles,
+            vec![PathBuf::from("certs/internal-ca.pem")]
+        );
+        assert!(relative
+            .requests
+            .contains_key(&PathBuf::from("users/get_users.http")));
+
+        assert_eq!(relative.with_absolute_paths(&collection_path), settings);
+    }
+
+    #[test]
+    fn test_has_plaintext_credentials() {
+        let bearer = |token: &str| RequestAuth::Bearer {
+            token: token.to_string(),
+        };
+        assert!(bearer("abc").has_plaintext_credentials());
+        assert!(bearer("Bearer {{token}}").has_plaintext_credentials());
+        assert!(!bearer("{{token}}").has_plaintext_credentials());
+        assert!(!bearer(" {{ token }} ").has_plaintext_credentials());
+        assert!(!RequestAuth::Basic {
+            username: "admin".to_string(),
+            password: "{{password}}".to_string(),
+        }
+        .has_plaintext_credentials());
+        assert!(RequestAuth::AwsSigV4 {
+            access_key_id: "AKID".to_string(),
+            secret_access_key: "{{aws_secret}}".to_string(),
+            session_token: Some("session".to_string()),
+            region: "eu-west-1".to_string(),
+            service: "s3".to_string(),
+        }
+        .has_plaintext_credentials());
+    }
+
+    #[test]
+    fn test_has_plaintext_passphrase() {
+        let profile = |passphrase: Option<&str>| TlsProfile {
+            host: "*.internal.example.com".to_string(),
+            client_certificate: None,
+            client_certificate_format: CertificateFormat::Pkcs12,
+            client_key: None,
+            passphrase: passphrase.map(str::to_string),
+            ca_bundles: vec![],
+            pinned_public_keys: vec![],
+        };
+        assert!(!profile(None).has_plaintext_passphrase());
+        assert!(!profile(Some("{{passphrase}}")).has_plaintext_passphrase());
+        assert!(profile(Some("secret")).has_plaintext_passphrase());
+        assert!(profile(Some("secret{{suffix}}")).has_plaintext_passphrase());
+    }
+}
@@ -0,0 +1,248 @@
+use std::sync::{Arc, RwLock};
+
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+use crate::model::Environment;
+
+/// Replacement for secret values and credentials
+pub const REDACTED: &str = "*****";
+
+/// Headers whose values are always redacted as they carry credentials
+pub const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+];
+
+/// Secret values shorter than this are not redacted within text as they would mask arbitrary
+/// parts of it
+const MIN_SECRET_LEN: usize = 6;
+
+lazy_static! {
+    /// Header lines such as `Authorization: Bearer abc` in raw http messages and curl output
+    static ref HEADER_LINE_REGEX: Regex = Regex::new(&format!(
+        r"(?i)\b({})(\s*:[ \t]*)([^\r\n]+)",
+        SENSITIVE_HEADERS.join("|")
+    ))
+    .unwrap();
+    /// Debug output of header structs such as `key: "Authorization", value: "Bearer abc"`
+    static ref HEADER_DEBUG_REGEX: Regex = Regex::new(&format!(
+        r#"(?i)((?:key|name): "(?:{})", value: ")((?:[^"\\]|\\.)*)(")"#,
+        SENSITIVE_HEADERS.join("|")
+    ))
+    .unwrap();
+}
+
+/// Masks the secret values of an environment as well as credential headers before anything is
+/// written to logs, saved responses, history entries or reports. Clones share their secrets so
+/// values acquired during a run, such as OAuth tokens, are redacted by every clone.
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    /// sorted by length so a secret containing another one is replaced as a whole
+    secrets: Arc<RwLock<Vec<String>>>,
+}
+
+impl Redactor {
+    pub fn new(environment: Option<&Environment>) -> Self {
+        let mut secrets: Vec<String> = environment
+            .map(|environment| {
+                environment
+                    .secrets
+                    .iter()
+                    .flat_map(|secret| {
+                        std::iter::once(&secret.initial_value)
+                            .chain(secret.current_value.as_ref())
+                            .flat_map(|value| secret_values(value, secret.is_json))
+                    })
+                    .chain(
+                        environment
+                            .auth_configurations
+                            .values()
+                            .flat_map(|configuration| {
+                                configuration
+                                    .client_secret
+                                    .iter()
+                                    .chain(configuration.password.iter())
+                                    .cloned()
+                            }),
+                    )
+                    .filter(|value| value.len() >= MIN_SECRET_LEN)
+                    .collect()
+            })
+            .unwrap_or_default();
+        sort_secrets(&mut secrets);
+        Redactor {
+            secrets: Arc::new(RwLock::new(secrets)),
+        }
+    }
+
+    /// Adds a value that is only known during the run, such as an acquired token
+    pub fn add_secret(&self, value: &str) {
+        if value.len() < MIN_SECRET_LEN {
+            return;
+        }
+        let mut secrets = self.secrets.write().unwrap();
+        if !secrets.iter().any(|secret| secret == value) {
+            secrets.push(value.to_string());
+            sort_secrets(&mut secrets);
+        }
+    }
+
+    pub fn is_sensitive_header(name: &str) -> bool {
+        SENSITIVE_HEADERS.contains(&name.to_lowercase().as_str())
+    }
+
+    /// Replaces secret values and the values of credential headers within a text
+    pub fn redact(&self, text: &str) -> String {
+        let mut result = text.to_string();
+        for secret in self.secrets.read().unwrap().iter() {
+            if result.contains(secret.as_str()) {
+                result = result.replace(secret.as_str(), REDACTED);
+            }
+        }
+        let result = HEADER_LINE_REGEX.replace_all(&result, |captures: &Captures| {
+            format!("{}{}{}", &captures[1], &captures[2], REDACTED)
+        });
+        HEADER_DEBUG_REGEX
+            .replace_all(&result, |captures: &Captures| {
+                format!("{}{}{}", &captures[1], REDACTED, &captures[3])
+            })
+            .to_string()
+    }
+
+    /// Same as [`Redactor::redact`] for binary content, only text is changed
+    pub fn redact_bytes(&self, bytes: &[u8]) -> Vec<u8> {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => self.redact(text).into_bytes(),
+            Err(_) => bytes.to_vec(),
+        }
+    }
+
+    pub fn redact_header(&self, name: &str, value: &str) -> String {
+        if Redactor::is_sensitive_header(name) {
+            REDACTED.to_string()
+        } else {
+            self.redact(value)
+        }
+    }
+}
+
+fn sort_secrets(secrets: &mut Vec<String>) {
+    secrets.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    secrets.dedup();
+}
+
+/// Returns the value of a secret and, for json secrets, the text values within it as they may be
+/// used on their own via a dotted path such as `{{credentials.password}}`. Numbers and booleans
+/// within json secrets are not redacted as they are too common within any text.
+fn secret_values(value: &str, is_json: bool) -> Vec<String> {
+    if !is_json {
+        return vec![value.to_string()];
+    }
+    match serde_json::from_str::<serde_json::Value>(value) {
+        Ok(json @ (serde_json::Value::Array(_) | serde_json::Value::Object(_))) => {
+            let mut values = vec![value.to_string()];
+            collect_json_values(&json, &mut values);
+            values
+        }
+        Ok(serde_json::Value::String(value)) => vec![value],
+        Ok(_) => vec![],
+        Err(_) => vec![value.to_string()],
+    }
+}
+
+fn collect_json_values(json: &serde_json::Value, values: &mut Vec<String>) {
+    match json {
+        serde_json::Value::String(value) => values.push(value.clone()),
+        serde_json::Value::Array(array) => array
+            .iter()
+            .for_each(|value| collect_json_values(value, values)),
+        serde_json::Value::Object(object) => object
+            .values()
+            .for_each(|value| collect_json_values(value, values)),
+        serde_json::Value::Number(_) | serde_json::Value::Bool(_) | serde_json::Value::Null => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::EnvironmentSecret;
+
+    fn redactor() -> Redactor {
+        let mut environment = Environment::new("dev".to_string());
+        environment.secrets = vec![EnvironmentSecret {
+            name: "password".to_string(),
+            initial_value: "hunter2".to_string(),
+            current_value: Some("hunter22".to_string()),
+            description: None,
+            persist_to_file: false,
+            source: None,
+            is_json: false,
+        }];
+        Redactor::new(Some(&environment))
+    }
+
+    #[test]
+    fn test_redact_secrets() {
+        assert_eq!(
+            redactor().redact("https://example.com?pw=hunter22&old=hunter2"),
+            "https://example.com?pw=*****&old=*****"
+        );
+        assert_eq!(
+            redactor().redact_bytes(b"{\"password\": \"hunter2\"}"),
+            b"{\"password\": \"*****\"}".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_added_secrets_are_shared() {
+        let redactor = redactor();
+        let clone = redactor.clone();
+        redactor.add_secret("access-token");
+        assert_eq!(
+            clone.redact("Bearer access-token, hunter22"),
+            "Bearer *****, *****"
+        );
+    }
+
+    #[test]
+    fn test_redact_json_secrets() {
+        let mut environment = Environment::new("dev".to_string());
+        environment.secrets = vec![EnvironmentSecret {
+            name: "credentials".to_string(),
+            initial_value:
+                r#"{"user": "admin", "password": "s3cr3t-pw", "port": 8080, "tls": true}"#
+                    .to_string(),
+            current_value: None,
+            description: None,
+            persist_to_file: false,
+            source: None,
+            is_json: true,
+        }];
+        assert_eq!(
+            Redactor::new(Some(&environment)).redact("admin:s3cr3t-pw@localhost:8080?tls=true"),
+            "admin:*****@localhost:8080?tls=true"
+        );
+    }
+
+    #[test]
+    fn test_redact_headers() {
+        let redactor = Redactor::default();
+        assert_eq!(
+            redactor.redact("> authorization: Bearer abc\r\n> Accept: */*"),
+            "> authorization: *****\r\n> Accept: */*"
+        );
+        assert_eq!(
+            redactor.redact(
+                r#"[Header { key: "Cookie", value: "session=\"1\"", active: true }, Header { key: "Accept", value: "*/*", active: true }]"#
+            ),
+            r#"[Header { key: "Cookie", value: "*****", active: true }, Header { key: "Accept", value: "*/*", active: true }]"#
+        );
+        assert_eq!(redactor.redact_header("X-Api-Key", "abc"), REDACTED);
+        assert_eq!(redactor.redact_header("Accept", "*/*"), "*/*");
+    }
+}
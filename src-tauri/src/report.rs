@@ -0,0 +1,362 @@
+use serde::Serialize;
+
+use crate::{
+    client::client_model::Call,
+    error::RelynxError,
+    model::{CallTimings, TestResult},
+    redaction::Redactor,
+    runner::RequestRun,
+};
+
+/// Machine readable report of a collection run, serialized as json or converted to JUnit XML
+/// and TAP for CI systems.
+#[derive(Serialize, Debug)]
+pub struct RunReport {
+    pub name: String,
+    pub requests: Vec<RequestReport>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RequestReport {
+    pub name: String,
+    pub file: String,
+    pub method: String,
+    pub url: String,
+    pub success: bool,
+    pub error: Option<String>,
+    /// total time of all calls in seconds
+    pub duration: f64,
+    pub tests: Vec<TestResult>,
+    pub calls: Vec<CallReport>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CallReport {
+    pub request: CallRequestReport,
+    pub response: CallResponseReport,
+    pub timings: CallTimings,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CallRequestReport {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CallResponseReport {
+    pub version: String,
+    pub status: u32,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+/// A single entry within a JUnit or TAP report. Every request is one case and every
+/// `client.test` of its response handler is an additional case.
+struct ReportCase {
+    classname: String,
+    name: String,
+    time: f64,
+    failure: Option<String>,
+    error: Option<String>,
+}
+
+impl CallReport {
+    /// Secret values and credential headers are redacted as reports are often stored as CI
+    /// artifacts
+    fn new(call: &Call, redactor: &Redactor) -> Self {
+        CallReport {
+            request: CallRequestReport {
+                method: call.request.method.clone(),
+                url: redactor.redact(&call.request.url),
+                headers: call
+                    .request
+                    .headers
+                    .iter()
+                    .map(|header| {
+                        (
+                            header.name.clone(),
+                            redactor.redact_header(&header.name, &header.value),
+                        )
+                    })
+                    .collect(),
+                body: redactor.redact(&String::from_utf8_lossy(&call.request.body)),
+            },
+            response: CallResponseReport {
+                version: format!(
+                    "HTTP/{}.{}",
+                    call.response.version.major, call.response.version.minor
+                ),
+                status: call.response.status,
+                headers: call
+                    .response
+                    .headers
+                    .iter()
+                    .map(|header| {
+                        (
+                            header.key.clone(),
+                            redactor.redact_header(&header.key, &header.value),
+                        )
+                    })
+                    .collect(),
+                body: redactor.redact(
+                    &call.response.text().unwrap_or_else(|_| {
+                        String::from_utf8_lossy(&call.response.body).to_string()
+                    }),
+                ),
+            },
+            timings: CallTimings {
+                url: redactor.redact(&call.request.url),
+                ..CallTimings::from(call)
+            },
+        }
+    }
+}
+
+impl RequestReport {
+    fn new(run: &RequestRun, redactor: &Redactor) -> Self {
+        RequestReport {
+            name: run.request.name.clone(),
+            file: run.request.rest_file_path.to_string_lossy().to_string(),
+            method: run.request.method.to_string(),
+            url: run.request.url.clone(),
+            success: run.is_success(),
+            error: run.error.as_ref().map(|error| redactor.redact(error)),
+            duration: run
+                .calls
+                .iter()
+                .map(|call| call.timings.total.as_secs_f64())
+                .sum(),
+            tests: run.tests.clone(),
+            calls: run
+                .calls
+                .iter()
+                .map(|call| CallReport::new(call, redactor))
+                .collect(),
+        }
+    }
+}
+
+impl RunReport {
+    pub fn new(name: &str, runs: &[RequestRun], redactor: &Redactor) -> Self {
+        RunReport {
+            name: name.to_string(),
+            requests: runs
+                .iter()
+                .map(|run| RequestReport::new(run, redactor))
+                .collect(),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, RelynxError> {
+        serde_json::to_string_pretty(self).map_err(|err| {
+            log::error!("Could not serialize run report: {}", self.name);
+            log::error!("Serde Error: {:?}", err);
+            RelynxError::CreateReportError
+        })
+    }
+
+    pub fn to_junit_xml(&self) -> String {
+        let cases = self.cases();
+        let failures = cases.iter().filter(|case| case.failure.is_some()).count();
+        let errors = cases.iter().filter(|case| case.error.is_some()).count();
+        let time: f64 = self.requests.iter().map(|request| request.duration).sum();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"{name}\" tests=\"{tests}\" failures=\"{failures}\" errors=\"{errors}\" time=\"{time:.3}\">\n",
+            name = escape_xml(&self.name),
+            tests = cases.len(),
+        ));
+        xml.push_str(&format!(
+            "  <testsuite name=\"{name}\" tests=\"{tests}\" failures=\"{failures}\" errors=\"{errors}\" time=\"{time:.3}\">\n",
+            name = escape_xml(&self.name),
+            tests = cases.len(),
+        ));
+        for case in cases.iter() {
+            let attributes = format!(
+                "classname=\"{}\" name=\"{}\" time=\"{:.3}\"",
+                escape_xml(&case.classname),
+                escape_xml(&case.name),
+                case.time
+            );
+            match (&case.error, &case.failure) {
+                (Some(error), _) => {
+                    xml.push_str(&format!("    <testcase {}>\n", attributes));
+                    xml.push_str(&format!(
+                        "      <error message=\"{}\"/>\n",
+                        escape_xml(error)
+                    ));
+                    xml.push_str("    </testcase>\n");
+                }
+                (None, Some(failure)) => {
+                    xml.push_str(&format!("    <testcase {}>\n", attributes));
+                    xml.push_str(&format!(
+                        "      <failure message=\"{}\"/>\n",
+                        escape_xml(failure)
+                    ));
+                    xml.push_str("    </testcase>\n");
+                }
+                (None, None) => xml.push_str(&format!("    <testcase {}/>\n", attributes)),
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+
+    pub fn to_tap(&self) -> String {
+        let cases = self.cases();
+        let mut tap = format!("TAP version 13\n1..{}\n", cases.len());
+        for (index, case) in cases.iter().enumerate() {
+            let description = format!("{} - {}", case.classname, case.name);
+            match case.error.as_ref().or(case.failure.as_ref()) {
+                Some(message) => {
+                    tap.push_str(&format!("not ok {} {}\n", index + 1, description));
+                    tap.push_str("  ---\n");
+                    tap.push_str(&format!("  message: {}\n", escape_yaml(message)));
+                    tap.push_str("  ...\n");
+                }
+                None => tap.push_str(&format!("ok {} {}\n", index + 1, description)),
+            }
+        }
+        tap
+    }
+
+    fn cases(&self) -> Vec<ReportCase> {
+        let mut cases = Vec::new();
+        for request in self.requests.iter() {
+            cases.push(ReportCase {
+                classname: request.file.clone(),
+                name: format!("{} {}", request.method, request.name),
+                time: request.duration,
+                failure: None,
+                error: request.error.clone(),
+            });
+            cases.extend(request.tests.iter().map(|test| ReportCase {
+                classname: request.name.clone(),
+                name: test.name.clone(),
+                time: test.duration,
+                failure: if test.passed {
+                    None
+                } else {
+                    Some(test.message.clone().unwrap_or_default())
+                },
+                error: None,
+            }));
+        }
+        cases
+    }
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c if is_valid_xml_char(c) => escaped.push(c),
+            // characters that are not allowed within XML 1.0, even escaped, would make the report
+            // unreadable for CI systems
+            _ => escaped.push(char::REPLACEMENT_CHARACTER),
+        }
+    }
+    escaped
+}
+
+fn is_valid_xml_char(c: char) -> bool {
+    matches!(
+        c,
+        '\t' | '\n' | '\r' | '\u{20}'..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}' | '\u{10000}'..='\u{10FFFF}'
+    )
+}
+
+/// Messages are written as a quoted yaml string within the diagnostics block of a TAP test
+fn escape_yaml(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::model::RequestModel;
+
+    fn runs() -> Vec<RequestRun> {
+        let request = RequestModel {
+            name: "Get <users>".to_string(),
+            rest_file_path: PathBuf::from("users.http"),
+            ..Default::default()
+        };
+        vec![
+            RequestRun {
+                request: request.clone(),
+                calls: vec![],
+                tests: vec![
+                    TestResult {
+                        name: "status is 200".to_string(),
+                        passed: true,
+                        message: None,
+                        duration: 0.0,
+                    },
+                    TestResult {
+                        name: "has users".to_string(),
+                        passed: false,
+                        message: Some("users missing".to_string()),
+                        duration: 0.0,
+                    },
+                ],
+                warnings: vec![],
+                error: None,
+            },
+            RequestRun {
+                request,
+                calls: vec![],
+                tests: vec![],
+                warnings: vec![],
+                error: Some("Invalid url: ''".to_string()),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_junit_report() {
+        let xml = RunReport::new("collection", &runs(), &Redactor::default()).to_junit_xml();
+        assert!(xml.contains(
+            "<testsuite name=\"collection\" tests=\"4\" failures=\"1\" errors=\"1\" time=\"0.000\">"
+        ));
+        assert!(xml.contains("name=\"GET Get &lt;users&gt;\""));
+        assert!(xml.contains("<failure message=\"users missing\"/>"));
+        assert!(xml.contains("<error message=\"Invalid url: &apos;&apos;\"/>"));
+    }
+
+    #[test]
+    fn test_tap_report() {
+        let tap = RunReport::new("collection", &runs(), &Redactor::default()).to_tap();
+        let lines: Vec<&str> = tap.lines().collect();
+        assert_eq!(lines[0], "TAP version 13");
+        assert_eq!(lines[1], "1..4");
+        assert_eq!(lines[2], "ok 1 users.http - GET Get <users>");
+        assert_eq!(lines[3], "ok 2 Get <users> - status is 200");
+        assert_eq!(lines[4], "not ok 3 Get <users> - has users");
+        assert_eq!(lines[6], "  message: \"users missing\"");
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml("<a href=\"x\">'b' & c</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&apos;b&apos; &amp; c&lt;/a&gt;"
+        );
+        assert_eq!(
+            escape_xml("tab\tline\nnull\u{0}bell\u{7}"),
+            "tab\tline\nnull\u{FFFD}bell\u{FFFD}"
+        );
+    }
+}
@@ -1,16 +1,29 @@
 use crate::{
-    client::{client_model::Call, options::ClientOptions, Client},
+    client::{client_model::Call, error::HttpError, options::ClientOptions, Client},
     error::RelynxError,
     import::load_requests_from_file,
-    model::{Environment, RunLogger},
+    model::{Environment, RequestModel, RunLogger, TestResult},
+    redaction::Redactor,
+    scripting::run_response_handler,
 };
 
 #[derive(Debug, PartialEq)]
 pub struct RequestRun {
-    calls: Vec<Call>,
+    pub request: RequestModel,
+    pub calls: Vec<Call>,
+    pub tests: Vec<TestResult>,
+    /// problems that did not prevent the request from being sent
+    pub warnings: Vec<String>,
+    /// set if the request could not be sent or its response handler failed
+    pub error: Option<String>,
+}
+
+impl RequestRun {
+    pub fn is_success(&self) -> bool {
+        self.error.is_none() && self.tests.iter().all(|test| test.passed)
+    }
 }
 
-#[allow(dead_code)]
 pub fn load_and_run(
     request_path: &std::path::Path,
     options: &ClientOptions,
@@ -27,7 +40,7 @@ pub fn load_and_run(
         return Err(RelynxError::ParseErrorGeneric);
     }
 
-    let logger = RunLogger::new(false);
+    let logger = RunLogger::new(false, Some(environment));
 
     let mut request_runs: Vec<RequestRun> = Vec::new();
 
@@ -41,21 +54,194 @@ pub fn load_and_run(
                 log::error!("Http Error: {:?}", http_err);
                 RelynxError::RequestSendErrorGeneric
             })?;
-        request_runs.push(RequestRun { calls });
+        request_runs.push(RequestRun {
+            request: request_model,
+            calls,
+            tests: vec![],
+            warnings: vec![],
+            error: None,
+        });
     }
     Ok(request_runs)
 }
 
+/// Everything that happened when a request has been sent and its scripts have been run
+#[derive(Debug)]
+pub struct HandledRequest {
+    pub calls: Vec<Call>,
+    pub tests: Vec<TestResult>,
+    /// problems that did not prevent the request from being sent
+    pub warnings: Vec<String>,
+    /// set if the response handler could not be run
+    pub handler_error: Option<String>,
+}
+
+/// Sends a request and runs its response handler. Globals set by the pre request script and the
+/// response handler are written into the environment. This is shared by single requests of the
+/// app and collection runs so both behave the same.
+pub fn execute_and_handle(
+    client: &mut Client,
+    request: &RequestModel,
+    options: &ClientOptions,
+    mut environment: Option<&mut Environment>,
+    logger: &RunLogger,
+) -> Result<HandledRequest, HttpError> {
+    let calls = client.execute(request, options, environment.as_deref(), logger)?;
+    let call = calls.last().expect("at least one call is executed");
+
+    let mut warnings: Vec<String> = client
+        .unresolved_variables()
+        .iter()
+        .map(|name| {
+            format!(
+                "The variable '{{{{{}}}}}' could not be resolved and has been sent as it is.",
+                name
+            )
+        })
+        .collect();
+    warnings.extend(client.warnings().iter().cloned());
+    warnings.extend(
+        call.response
+            .certificate_expiry_warnings(options.certificate_expiry_warning_days),
+    );
+
+    // globals of the pre request script are visible to the response handler
+    let has_script_globals =
+        !client.changed_globals().is_empty() || !client.removed_globals().is_empty();
+    match environment {
+        Some(ref mut environment) => environment
+            .apply_script_globals(client.changed_globals().clone(), client.removed_globals()),
+        None if has_script_globals => {
+            warnings.push("The pre request script set global variables but they are not stored as no environment is selected.".to_string());
+        }
+        None => (),
+    }
+
+    let mut tests = vec![];
+    let mut handler_error = None;
+    if let Some(ref response_handler) = request.response_handler {
+        match run_response_handler(
+            response_handler,
+            request,
+            &call.response,
+            environment.as_deref(),
+        ) {
+            Ok(handler_result) => {
+                for msg in handler_result.logs.iter() {
+                    logger.log_info(format!("Response handler: {}", msg));
+                }
+                for test in handler_result.tests.iter().filter(|test| !test.passed) {
+                    logger.log_info(format!(
+                        "Test '{}' failed: {}",
+                        test.name,
+                        test.message.as_deref().unwrap_or_default()
+                    ));
+                }
+                tests = handler_result.tests;
+                let has_handler_globals = !handler_result.changed_globals.is_empty()
+                    || !handler_result.removed_globals.is_empty();
+                match environment {
+                    Some(ref mut environment) => environment.apply_script_globals(
+                        handler_result.changed_globals,
+                        &handler_result.removed_globals,
+                    ),
+                    None if has_handler_globals => {
+                        warnings.push("The response handler set global variables but they are not stored as no environment is selected.".to_string());
+                    }
+                    None => (),
+                }
+            }
+            Err(err) => {
+                logger.log_error(format!("Response handler error: {:?}", err));
+                handler_error = Some(format!(
+                    "Could not run the response handler script. {}",
+                    err
+                ));
+            }
+        }
+    }
+
+    Ok(HandledRequest {
+        calls,
+        tests,
+        warnings,
+        handler_error,
+    })
+}
+
+/// Runs the requests one after another with the same client so cookies received by one request
+/// are sent with the following ones. Global variables set by a pre request script or response
+/// handler are written into the environment and can be used by the subsequent requests.
+/// A failing request does not stop the run, the error is stored within its [`RequestRun`].
+/// The client settings of a request override the given options. Tokens acquired during the run
+/// are added to the given redactor.
+pub fn run_requests(
+    requests: &[RequestModel],
+    options: &ClientOptions,
+    mut environment: Option<&mut Environment>,
+    redactor: &Redactor,
+) -> Vec<RequestRun> {
+    let mut client = Client::new(None);
+    let mut request_runs: Vec<RequestRun> = Vec::new();
+
+    for request in requests {
+        let logger =
+            RunLogger::with_redactor(request.settings.no_log.unwrap_or(false), redactor.clone());
+        let mut options = ClientOptions {
+            follow_location: !request.settings.no_redirect.unwrap_or(false),
+            ..options.clone()
+        };
+        if let Some(ssl_configuration) = environment
+            .as_deref()
+            .and_then(|environment| environment.ssl_configuration.as_ref())
+        {
+            ssl_configuration.apply_to(&mut options);
+        }
+        request.client_settings.apply_to(&mut options);
+
+        let request_run = match execute_and_handle(
+            &mut client,
+            request,
+            &options,
+            environment.as_deref_mut(),
+            &logger,
+        ) {
+            Ok(handled) => RequestRun {
+                request: request.clone(),
+                calls: handled.calls,
+                tests: handled.tests,
+                warnings: handled.warnings,
+                error: handled.handler_error,
+            },
+            Err(http_err) => {
+                logger.log_error(format!("Http error occurred: {:?}", http_err));
+                RequestRun {
+                    request: request.clone(),
+                    calls: vec![],
+                    tests: vec![],
+                    warnings: vec![],
+                    error: Some(http_err.to_string()),
+                }
+            }
+        };
+        request_runs.push(request_run);
+    }
+    request_runs
+}
+
 #[cfg(test)]
 mod tests {
 
-    use std::path::PathBuf;
+    use std::{collections::HashMap, path::PathBuf};
 
     use httptest::{matchers::*, responders::*, Expectation, Server};
 
+    use http_rest_file::model::PreRequestScript;
+
     use crate::{
         client::options::ClientOptions,
-        model::{Environment, EnvironmentVariable},
+        model::{Environment, EnvironmentVariable, RequestModel},
+        redaction::Redactor,
     };
 
     pub fn setup() -> (Server, Environment) {
@@ -72,6 +258,10 @@ mod tests {
             name: "default".to_string(),
             variables: vec![EnvironmentVariable::new("base_url", server_url)],
             secrets: vec![],
+            extends: None,
+            ssl_configuration: None,
+            auth_configurations: HashMap::new(),
+            vault_loaded: false,
         };
 
         // so we can define the path relative to this file, this helps as neovim has autocomplete
@@ -81,7 +271,8 @@ mod tests {
         (server, environment)
     }
 
-    use super::load_and_run;
+    use super::{load_and_run, run_requests};
+    use crate::import::load_requests_from_file;
     #[test]
     pub fn test_simple_get() {
         let (server, environment) = setup();
@@ -286,4 +477,59 @@ mod tests {
 
         assert_eq!(runs[0].calls[0].response.status, 200);
     }
+
+    #[test]
+    pub fn test_run_requests_with_response_handler() {
+        let (server, mut environment) = setup();
+        server.expect(
+            Expectation::matching(request::method_path("GET", "/devices"))
+                .respond_with(status_code(200)),
+        );
+
+        let filepath =
+            PathBuf::from("../tests/relynx-collection/simple/get_request_with_handler.http");
+        let (requests, errs) = load_requests_from_file(&filepath).unwrap();
+        assert!(errs.is_empty());
+
+        let runs = run_requests(
+            &requests,
+            &ClientOptions::default(),
+            Some(&mut environment),
+            &Redactor::default(),
+        );
+
+        assert_eq!(runs.len(), 1);
+        assert!(runs[0].is_success());
+        assert_eq!(runs[0].tests.len(), 1);
+        assert!(environment
+            .variables
+            .iter()
+            .any(|var| var.name == "device_id" && var.current_value.as_deref() == Some("42")));
+    }
+
+    #[test]
+    pub fn test_run_requests_keeps_pre_request_script_globals() {
+        let (server, mut environment) = setup();
+        server.expect(
+            Expectation::matching(request::method_path("GET", "/devices"))
+                .respond_with(status_code(200)),
+        );
+        let request = RequestModel {
+            url: "{{base_url}}/devices".to_string(),
+            pre_request_script: Some(PreRequestScript::Script(
+                r#"client.global.set("session", "abc");"#.to_string(),
+            )),
+            ..Default::default()
+        };
+
+        let runs = run_requests(
+            &[request],
+            &ClientOptions::default(),
+            Some(&mut environment),
+            &Redactor::default(),
+        );
+
+        assert!(runs[0].is_success());
+        assert_eq!(environment.get_value("session"), Some("abc".to_string()));
+    }
 }
@@ -0,0 +1,434 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use boa_engine::{Context, Source};
+use http_rest_file::model::{PreRequestScript, ResponseHandler};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::client_model::Response;
+use crate::model::{
+    DataSource, Environment, GetHeadersOption, RequestBody, RequestModel, TestResult,
+};
+
+/// Javascript implementation of the `client` and `response` objects that are available within
+/// scripts of the JetBrains http client
+const CLIENT_API: &str = include_str!("scripting/client.js");
+
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+pub enum ScriptError {
+    #[error("Could not read script file: '{0}'")]
+    CouldNotReadScriptFile(PathBuf),
+
+    #[error("Error within script: {0}")]
+    Evaluation(String),
+
+    #[error("Could not read the variables set by the script")]
+    InvalidState,
+}
+
+#[derive(Serialize, Debug)]
+struct ScriptInput {
+    globals: HashMap<String, String>,
+    request: Option<ScriptRequest>,
+    response: Option<ScriptResponse>,
+}
+
+#[derive(Serialize, Debug)]
+struct ScriptRequest {
+    method: String,
+    url: String,
+    body: Option<String>,
+    environment: HashMap<String, String>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ScriptResponse {
+    status: u32,
+    headers: Vec<(String, String)>,
+    body: serde_json::Value,
+    content_type: ScriptContentType,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ScriptContentType {
+    mime_type: String,
+    charset: String,
+}
+
+/// Everything a script changed, read back from the js context after the script has run
+#[derive(Deserialize, Debug, Default)]
+struct ScriptState {
+    globals: HashMap<String, String>,
+    #[serde(default)]
+    variables: HashMap<String, String>,
+    logs: Vec<String>,
+    #[serde(default)]
+    tests: Vec<TestResult>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct ResponseHandlerResult {
+    /// global variables which have been set or changed by the handler
+    pub changed_globals: HashMap<String, String>,
+    /// global variables removed with `client.global.clear` or `client.global.clearAll`
+    pub removed_globals: Vec<String>,
+    /// messages from `client.log`
+    pub logs: Vec<String>,
+    /// results of `client.test` and `client.assert` calls in the order they have been run
+    pub tests: Vec<TestResult>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct PreRequestScriptResult {
+    /// request scoped variables set with `request.variables.set`
+    pub variables: HashMap<String, String>,
+    /// global variables which have been set or changed by the script
+    pub changed_globals: HashMap<String, String>,
+    /// global variables removed with `client.global.clear` or `client.global.clearAll`
+    pub removed_globals: Vec<String>,
+    /// messages from `client.log`
+    pub logs: Vec<String>,
+}
+
+impl ScriptRequest {
+    fn new(request: &RequestModel, environment: Option<&Environment>) -> Self {
+        let body = match request.body {
+            RequestBody::Raw {
+                data: DataSource::Raw(ref raw),
+            } => Some(raw.clone()),
+            _ => None,
+        };
+        let mut env_values = environment.map(globals_from_env).unwrap_or_default();
+        if let Some(environment) = environment {
+            env_values.extend(environment.secrets.iter().map(|secret| {
+                let value = secret
+                    .current_value
+                    .as_ref()
+                    .unwrap_or(&secret.initial_value);
+                (secret.name.clone(), value.clone())
+            }));
+        }
+        ScriptRequest {
+            method: request.method.to_string(),
+            url: request.url.clone(),
+            body,
+            environment: env_values,
+        }
+    }
+}
+
+impl From<&Response> for ScriptResponse {
+    fn from(response: &Response) -> Self {
+        let content_type = response
+            .get_header_values("Content-Type", GetHeadersOption::JustValues)
+            .first()
+            .cloned()
+            .unwrap_or_default();
+        let mut content_type_parts = content_type.split(';').map(str::trim);
+        let mime_type = content_type_parts.next().unwrap_or_default().to_string();
+        let charset = content_type_parts
+            .find_map(|part| part.strip_prefix("charset="))
+            .unwrap_or("utf-8")
+            .to_string();
+
+        let body_text = response
+            .text()
+            .unwrap_or_else(|_| String::from_utf8_lossy(&response.body).to_string());
+        // json responses are passed as objects to the script, everything else as a string
+        let body = if mime_type.contains("json") {
+            serde_json::from_str(&body_text).unwrap_or(serde_json::Value::String(body_text))
+        } else {
+            serde_json::Value::String(body_text)
+        };
+
+        ScriptResponse {
+            status: response.status,
+            headers: response
+                .headers
+                .iter()
+                .map(|header| (header.key.clone(), header.value.clone()))
+                .collect(),
+            body,
+            content_type: ScriptContentType { mime_type, charset },
+        }
+    }
+}
+
+/// Runs the response handler of a request after the response has been received.
+/// Global variables set with `client.global.set` are returned and have to be written back into
+/// the environment by the caller, see [`Environment::set_variable`].
+pub fn run_response_handler(
+    handler: &ResponseHandler,
+    request: &RequestModel,
+    response: &Response,
+    environment: Option<&Environment>,
+) -> Result<ResponseHandlerResult, ScriptError> {
+    let script = match handler {
+        ResponseHandler::Script(script) => script.clone(),
+        ResponseHandler::FromFilepath(path) => read_script_file(path, request)?,
+    };
+
+    let globals = environment.map(globals_from_env).unwrap_or_default();
+    let input = ScriptInput {
+        globals: globals.clone(),
+        request: None,
+        response: Some(response.into()),
+    };
+    let state = run_script(&script, &input)?;
+
+    Ok(ResponseHandlerResult {
+        removed_globals: removed_globals(&globals, &state.globals),
+        changed_globals: changed_globals(&globals, state.globals),
+        logs: state.logs,
+        tests: state.tests,
+    })
+}
+
+/// Runs the pre request script of a request before any variables are replaced within it.
+/// The returned request variables have to be added to the environment that is used for sending
+/// the request.
+pub fn run_pre_request_script(
+    pre_request_script: &PreRequestScript,
+    request: &RequestModel,
+    environment: Option<&Environment>,
+) -> Result<PreRequestScriptResult, ScriptError> {
+    let script = match pre_request_script {
+        PreRequestScript::Script(script) => script.clone(),
+        PreRequestScript::FromFilepath(path) => read_script_file(path, request)?,
+    };
+
+    let globals = environment.map(globals_from_env).unwrap_or_default();
+    let input = ScriptInput {
+        globals: globals.clone(),
+        request: Some(ScriptRequest::new(request, environment)),
+        response: None,
+    };
+    let state = run_script(&script, &input)?;
+
+    Ok(PreRequestScriptResult {
+        variables: state.variables,
+        removed_globals: removed_globals(&globals, &state.globals),
+        changed_globals: changed_globals(&globals, state.globals),
+        logs: state.logs,
+    })
+}
+
+fn changed_globals(
+    before: &HashMap<String, String>,
+    after: HashMap<String, String>,
+) -> HashMap<String, String> {
+    after
+        .into_iter()
+        .filter(|(name, value)| before.get(name) != Some(value))
+        .collect()
+}
+
+fn removed_globals(
+    before: &HashMap<String, String>,
+    after: &HashMap<String, String>,
+) -> Vec<String> {
+    let mut removed: Vec<String> = before
+        .keys()
+        .filter(|name| !after.contains_key(*name))
+        .cloned()
+        .collect();
+    removed.sort();
+    removed
+}
+
+/// Scripts can either be inline within the request file or within a separate js file which is
+/// relative to the request file
+fn read_script_file(path: &str, request: &RequestModel) -> Result<String, ScriptError> {
+    let abs_path = DataSource::FromFilepath(path.to_string())
+        .get_abs_path_relative_to(request)
+        .unwrap_or(PathBuf::from(path));
+    std::fs::read_to_string(&abs_path).map_err(|err| {
+        log::error!("Could not read script file: '{}'", abs_path.display());
+        log::error!("Io Error: {:?}", err);
+        ScriptError::CouldNotReadScriptFile(abs_path)
+    })
+}
+
+fn globals_from_env(environment: &Environment) -> HashMap<String, String> {
+    environment
+        .variables
+        .iter()
+        .map(|variable| {
+            let value = variable
+                .current_value
+                .as_ref()
+                .unwrap_or(&variable.initial_value);
+            (variable.name.clone(), value.clone())
+        })
+        .collect()
+}
+
+fn run_script(script: &str, input: &ScriptInput) -> Result<ScriptState, ScriptError> {
+    let input = serde_json::to_string(input).map_err(|err| {
+        log::error!("Could not serialize script input: {:?}", input);
+        log::error!("Serde Error: {:?}", err);
+        ScriptError::InvalidState
+    })?;
+    let prelude = format!("const __relynx_input = {};\n{}", input, CLIENT_API);
+
+    let mut context = Context::default();
+    context
+        .eval(Source::from_bytes(&prelude))
+        .map_err(|err| ScriptError::Evaluation(err.to_string()))?;
+    context
+        .eval(Source::from_bytes(script))
+        .map_err(|err| ScriptError::Evaluation(err.to_string()))?;
+
+    let state = context
+        .eval(Source::from_bytes("__relynx_state"))
+        .and_then(|state| state.to_json(&mut context))
+        .map_err(|err| {
+            log::error!("Could not read script state from context: {}", err);
+            ScriptError::InvalidState
+        })?;
+    serde_json::from_value::<ScriptState>(state).map_err(|err| {
+        log::error!("Could not deserialize script state");
+        log::error!("Serde Error: {:?}", err);
+        ScriptError::InvalidState
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use http_rest_file::model::{HttpVersion, ResponseHandler};
+
+    use super::*;
+    use crate::model::{EnvironmentVariable, Header};
+
+    fn json_response(body: &str) -> Response {
+        Response {
+            version: HttpVersion { major: 1, minor: 1 },
+            status: 200,
+            headers: vec![Header::new(
+                "Content-Type",
+                "application/json; charset=utf-8",
+            )],
+            body: body.as_bytes().to_vec(),
+            raw_body_size: body.len(),
+            duration: Duration::default(),
+            url: "http://localhost/token".to_string(),
+            certificate_chain: vec![],
+            tls_session: None,
+        }
+    }
+
+    #[test]
+    fn test_response_handler_sets_global_from_body() {
+        let handler = ResponseHandler::Script(
+            r#"client.global.set("token", response.body.token);"#.to_string(),
+        );
+        let mut environment = Environment::new("dev".to_string());
+        environment.variables = vec![EnvironmentVariable::new("host", "localhost")];
+
+        let result = run_response_handler(
+            &handler,
+            &RequestModel::default(),
+            &json_response(r#"{"token": "abc"}"#),
+            Some(&environment),
+        )
+        .expect("handler runs");
+
+        assert_eq!(
+            result.changed_globals,
+            HashMap::from([("token".to_string(), "abc".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_response_handler_clears_globals() {
+        let handler = ResponseHandler::Script(
+            r#"client.global.clear("token");
+            client.global.set("user", "alice");"#
+                .to_string(),
+        );
+        let mut environment = Environment::new("dev".to_string());
+        environment.variables = vec![EnvironmentVariable::new("host", "localhost")];
+        environment.set_variable("token", "abc".to_string());
+
+        let result = run_response_handler(
+            &handler,
+            &RequestModel::default(),
+            &json_response("{}"),
+            Some(&environment),
+        )
+        .expect("handler runs");
+        assert_eq!(result.removed_globals, vec!["token".to_string()]);
+
+        environment.apply_script_globals(result.changed_globals, &result.removed_globals);
+        assert_eq!(environment.get_value("token"), None);
+        assert_eq!(environment.get_value("user"), Some("alice".to_string()));
+        assert_eq!(environment.get_value("host"), Some("localhost".to_string()));
+    }
+
+    #[test]
+    fn test_response_handler_collects_test_results() {
+        let handler = ResponseHandler::Script(
+            r#"client.test("status is 200", function () {
+                client.assert(response.status === 200, "unexpected status");
+            });
+            client.test("has token", function () {
+                client.assert(response.body.token !== undefined, "token missing");
+            });
+            client.assert(true, "top level assertion");"#
+                .to_string(),
+        );
+        let result = run_response_handler(
+            &handler,
+            &RequestModel::default(),
+            &json_response("{}"),
+            None,
+        )
+        .expect("handler runs");
+
+        let tests: Vec<(&str, bool, Option<&str>)> = result
+            .tests
+            .iter()
+            .map(|test| (test.name.as_str(), test.passed, test.message.as_deref()))
+            .collect();
+        assert_eq!(
+            tests,
+            vec![
+                ("status is 200", true, None),
+                ("has token", false, Some("token missing")),
+                ("top level assertion", true, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pre_request_script_sets_request_variables() {
+        let script = PreRequestScript::Script(
+            r#"request.variables.set("ts", Date.now());
+            request.variables.set("greeting", request.variables.get("missing") || "hello");"#
+                .to_string(),
+        );
+        let result =
+            run_pre_request_script(&script, &RequestModel::default(), None).expect("script runs");
+
+        assert!(result.variables.get("ts").unwrap().parse::<u64>().is_ok());
+        assert_eq!(result.variables.get("greeting").unwrap(), "hello");
+        assert!(result.changed_globals.is_empty());
+    }
+
+    #[test]
+    fn test_response_handler_reports_script_error() {
+        let handler = ResponseHandler::Script("client.unknownFunction();".to_string());
+        let result = run_response_handler(
+            &handler,
+            &RequestModel::default(),
+            &json_response("{}"),
+            None,
+        );
+        assert!(matches!(result, Err(ScriptError::Evaluation(_))));
+    }
+}
@@ -0,0 +1,148 @@
+// Provides the `client`, `request` and `response` objects of the JetBrains http client scripting api.
+// `__relynx_input` is defined by relynx before this file is evaluated, everything the script
+// changes is collected within `__relynx_state` and read back after the script has run.
+(function (input) {
+  var state = {
+    globals: Object.assign({}, input.globals),
+    variables: {},
+    logs: [],
+    tests: [],
+  };
+  // set while a `client.test` function runs, failed assertions within it fail the test
+  var runningTest = false;
+
+  function errorMessage(err) {
+    return err && err.message ? err.message : String(err);
+  }
+
+  function toVariableValue(value) {
+    if (value === undefined || value === null) {
+      return "";
+    }
+    if (typeof value === "object") {
+      return JSON.stringify(value);
+    }
+    return String(value);
+  }
+
+  function headerValues(headers, name) {
+    var lowercaseName = String(name).toLowerCase();
+    return headers
+      .filter(function (header) {
+        return header[0].toLowerCase() === lowercaseName;
+      })
+      .map(function (header) {
+        return header[1];
+      });
+  }
+
+  globalThis.client = {
+    global: {
+      set: function (name, value) {
+        state.globals[name] = toVariableValue(value);
+      },
+      get: function (name) {
+        var value = state.globals[name];
+        return value === undefined ? null : value;
+      },
+      isEmpty: function () {
+        return Object.keys(state.globals).length === 0;
+      },
+      clear: function (name) {
+        delete state.globals[name];
+      },
+      clearAll: function () {
+        Object.keys(state.globals).forEach(function (name) {
+          delete state.globals[name];
+        });
+      },
+    },
+    log: function () {
+      var parts = Array.prototype.slice.call(arguments).map(toVariableValue);
+      state.logs.push(parts.join(" "));
+    },
+    test: function (name, fn) {
+      var start = Date.now();
+      var result = { name: String(name), passed: true, message: null, duration: 0 };
+      runningTest = true;
+      try {
+        fn();
+      } catch (err) {
+        result.passed = false;
+        result.message = errorMessage(err);
+      } finally {
+        runningTest = false;
+      }
+      result.duration = (Date.now() - start) / 1000;
+      state.tests.push(result);
+    },
+    assert: function (condition, message) {
+      var failureMessage = message ? String(message) : "Assertion failed";
+      if (runningTest) {
+        if (!condition) {
+          throw new Error(failureMessage);
+        }
+        return;
+      }
+      // assertions outside of a test are reported as their own check
+      state.tests.push({
+        name: failureMessage,
+        passed: !!condition,
+        message: condition ? null : failureMessage,
+        duration: 0,
+      });
+    },
+  };
+
+  if (input.request) {
+    var environment = input.request.environment;
+    globalThis.request = {
+      method: input.request.method,
+      url: {
+        getRaw: function () {
+          return input.request.url;
+        },
+      },
+      body: {
+        getRaw: function () {
+          return input.request.body;
+        },
+      },
+      environment: {
+        get: function (name) {
+          var value = environment[name];
+          return value === undefined ? null : value;
+        },
+      },
+      variables: {
+        set: function (name, value) {
+          state.variables[name] = toVariableValue(value);
+        },
+        get: function (name) {
+          var value = state.variables[name];
+          return value === undefined ? null : value;
+        },
+      },
+    };
+  }
+
+  if (input.response) {
+    var headers = input.response.headers;
+    globalThis.response = {
+      status: input.response.status,
+      body: input.response.body,
+      headers: {
+        valueOf: function (name) {
+          var values = headerValues(headers, name);
+          return values.length > 0 ? values[0] : null;
+        },
+        valuesOf: function (name) {
+          return headerValues(headers, name);
+        },
+      },
+      contentType: input.response.contentType,
+    };
+  }
+
+  globalThis.__relynx_state = state;
+})(__relynx_input);
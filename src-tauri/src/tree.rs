@@ -95,6 +95,29 @@ impl RequestTreeNode {
         let path = PathBuf::from(&self.filepath);
         path.file_name().unwrap().to_string_lossy().to_string()
     }
+
+    /// Finds the node with the given filepath within this node and its descendants
+    pub fn find_by_path(&self, path: &std::path::Path) -> Option<&RequestTreeNode> {
+        if self.filepath == path {
+            return Some(self);
+        }
+        self.children
+            .iter()
+            .find_map(|child| child.find_by_path(path))
+    }
+
+    /// All requests within this node and its descendants in the order they are displayed
+    pub fn requests(&self) -> Vec<&RequestModel> {
+        let mut requests = Vec::new();
+        let mut nodes: Vec<&RequestTreeNode> = vec![self];
+        while let Some(node) = nodes.pop() {
+            if let Some(ref request) = node.request {
+                requests.push(request);
+            }
+            nodes.extend(node.children.iter().rev());
+        }
+        requests
+    }
 }
 
 impl Default for RequestTreeNode {
@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use argon2::Argon2;
+use base64::{engine::general_purpose, Engine};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    Key, XChaCha20Poly1305, XNonce,
+};
+use rand::RngCore;
+use rspc::Type;
+use serde::{Deserialize, Serialize};
+
+use crate::{config::get_data_dir, error::RelynxError};
+
+const VAULT_FILENAME: &str = "secrets.vault";
+const VAULT_VERSION: u32 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const KEY_LEN: usize = 32;
+
+/// secret name -> value
+pub type VaultSecrets = HashMap<String, String>;
+/// environment name -> secrets
+pub type VaultEnvironments = HashMap<String, VaultSecrets>;
+/// collection path -> environments
+type VaultContent = HashMap<String, VaultEnvironments>;
+
+/// The vault as it is stored on disk, the content is encrypted with a key derived from the
+/// passphrase of the user
+#[derive(Serialize, Deserialize, Debug)]
+struct VaultFile {
+    version: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+struct UnlockedVault {
+    path: PathBuf,
+    salt: Vec<u8>,
+    key: [u8; KEY_LEN],
+    content: VaultContent,
+}
+
+#[derive(Serialize, Deserialize, Type, Debug)]
+pub struct VaultStatus {
+    pub exists: bool,
+    pub unlocked: bool,
+}
+
+/// The vault is unlocked once per session, the derived key is kept in memory until the vault is
+/// locked again or the app is closed
+static UNLOCKED_VAULT: Mutex<Option<UnlockedVault>> = Mutex::new(None);
+
+fn get_vault_path() -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join(VAULT_FILENAME))
+}
+
+pub fn get_vault_status() -> VaultStatus {
+    VaultStatus {
+        exists: get_vault_path().map(|path| path.exists()).unwrap_or(false),
+        unlocked: is_vault_unlocked(),
+    }
+}
+
+pub fn is_vault_unlocked() -> bool {
+    UNLOCKED_VAULT.lock().unwrap().is_some()
+}
+
+/// Unlocks the vault for the rest of the session. If there is no vault yet a new one is created
+/// which is protected by the given passphrase.
+pub fn unlock_vault(passphrase: &str) -> Result<(), RelynxError> {
+    let path = get_vault_path().ok_or_else(|| {
+        log::error!("Could not unlock vault as the data dir cannot be determined");
+        RelynxError::LoadVaultError
+    })?;
+    let vault = open_vault(path, passphrase)?;
+    *UNLOCKED_VAULT.lock().unwrap() = Some(vault);
+    Ok(())
+}
+
+pub fn lock_vault() {
+    *UNLOCKED_VAULT.lock().unwrap() = None;
+}
+
+/// Returns the secrets stored for the environments of a collection, `None` is returned if the
+/// vault is locked
+pub fn get_vault_secrets(collection_path: &Path) -> Option<VaultEnvironments> {
+    UNLOCKED_VAULT.lock().unwrap().as_ref().map(|vault| {
+        vault
+            .content
+            .get(&collection_key(collection_path))
+            .cloned()
+            .unwrap_or_default()
+    })
+}
+
+/// Merges the given secrets into the secrets stored for the environments of a collection and
+/// writes the vault to disk. Stored secrets that are not given are kept, except for the
+/// environments within `replaced` whose stored secrets are replaced by the given ones.
+pub fn store_vault_secrets(
+    collection_path: &Path,
+    environments: VaultEnvironments,
+    replaced: &[String],
+) -> Result<(), RelynxError> {
+    let mut unlocked_vault = UNLOCKED_VAULT.lock().unwrap();
+    let vault = unlocked_vault.as_mut().ok_or_else(|| {
+        log::error!("Could not store secrets as the vault is locked");
+        RelynxError::VaultLocked
+    })?;
+    let key = collection_key(collection_path);
+    let mut stored = vault.content.remove(&key).unwrap_or_default();
+    merge_secrets(&mut stored, environments, replaced);
+    if !stored.is_empty() {
+        vault.content.insert(key, stored);
+    }
+    save_vault(vault)
+}
+
+fn merge_secrets(
+    stored: &mut VaultEnvironments,
+    environments: VaultEnvironments,
+    replaced: &[String],
+) {
+    for name in replaced {
+        stored.remove(name);
+    }
+    for (name, secrets) in environments {
+        stored.entry(name).or_default().extend(secrets);
+    }
+    stored.retain(|_, secrets| !secrets.is_empty());
+}
+
+fn collection_key(collection_path: &Path) -> String {
+    collection_path.to_string_lossy().to_string()
+}
+
+fn open_vault(path: PathBuf, passphrase: &str) -> Result<UnlockedVault, RelynxError> {
+    if !path.exists() {
+        let mut salt = vec![0u8; SALT_LEN];
+        rand::thread_rng().fill_bytes(&mut salt);
+        let vault = UnlockedVault {
+            key: derive_key(passphrase, &salt)?,
+            path,
+            salt,
+            content: VaultContent::new(),
+        };
+        save_vault(&vault)?;
+        return Ok(vault);
+    }
+
+    let content = std::fs::read_to_string(&path).map_err(|err| {
+        log::error!("Could not read vault file: '{}'", path.display());
+        log::error!("Io Error: {:?}", err);
+        RelynxError::LoadVaultError
+    })?;
+    let vault_file: VaultFile = serde_json::from_str(&content).map_err(|err| {
+        log::error!("Could not deserialize vault file: '{}'", path.display());
+        log::error!("Serde Error: {:?}", err);
+        RelynxError::LoadVaultError
+    })?;
+    if vault_file.version != VAULT_VERSION {
+        log::error!("Unsupported vault version: {}", vault_file.version);
+        return Err(RelynxError::LoadVaultError);
+    }
+
+    let salt = decode_base64(&vault_file.salt)?;
+    let nonce = decode_base64(&vault_file.nonce)?;
+    let ciphertext = decode_base64(&vault_file.ciphertext)?;
+    if nonce.len() != NONCE_LEN {
+        log::error!("Invalid nonce length within vault file");
+        return Err(RelynxError::LoadVaultError);
+    }
+
+    let key = derive_key(passphrase, &salt)?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&key));
+    // decryption only fails if the key is wrong or the file has been tampered with
+    let plaintext = cipher
+        .decrypt(XNonce::from_slice(&nonce), ciphertext.as_ref())
+        .map_err(|_| RelynxError::InvalidVaultPassphrase)?;
+    let content: VaultContent = serde_json::from_slice(&plaintext).map_err(|_| {
+        // we do not log specifics as there are secrets within the content
+        log::error!("Could not deserialize decrypted vault content");
+        RelynxError::LoadVaultError
+    })?;
+
+    Ok(UnlockedVault {
+        path,
+        salt,
+        key,
+        content,
+    })
+}
+
+fn save_vault(vault: &UnlockedVault) -> Result<(), RelynxError> {
+    let plaintext = serde_json::to_vec(&vault.content).map_err(|_| {
+        // we do not log specifics as there are secrets within the content
+        log::error!("Could not serialize vault content");
+        RelynxError::SaveVaultError
+    })?;
+
+    // a new nonce is used every time the vault is written
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&vault.key));
+    let ciphertext = cipher
+        .encrypt(XNonce::from_slice(&nonce), plaintext.as_ref())
+        .map_err(|err| {
+            log::error!("Could not encrypt vault content");
+            log::error!("Aead Error: {:?}", err);
+            RelynxError::SaveVaultError
+        })?;
+
+    let vault_file = VaultFile {
+        version: VAULT_VERSION,
+        salt: general_purpose::STANDARD.encode(&vault.salt),
+        nonce: general_purpose::STANDARD.encode(nonce),
+        ciphertext: general_purpose::STANDARD.encode(ciphertext),
+    };
+    let content = serde_json::to_string(&vault_file).map_err(|err| {
+        log::error!("Could not serialize vault file");
+        log::error!("Serde Error: {:?}", err);
+        RelynxError::SaveVaultError
+    })?;
+
+    if let Some(parent) = vault.path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| {
+            log::error!("Could not create vault folder: '{}'", parent.display());
+            log::error!("Io Error: {:?}", err);
+            RelynxError::SaveVaultError
+        })?;
+    }
+    // the vault is written to a temporary file first and then moved, so an interrupted write
+    // does not leave a corrupt vault behind
+    let tmp_path = vault.path.with_extension("vault.tmp");
+    std::fs::write(&tmp_path, content)
+        .and_then(|_| std::fs::rename(&tmp_path, &vault.path))
+        .map_err(|err| {
+            log::error!("Could not write vault file: '{}'", vault.path.display());
+            log::error!("Io Error: {:?}", err);
+            let _ = std::fs::remove_file(&tmp_path);
+            RelynxError::SaveVaultError
+        })
+}
+
+/// Derives the encryption key from the passphrase with argon2id
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; KEY_LEN], RelynxError> {
+    let mut key = [0u8; KEY_LEN];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|err| {
+            log::error!("Could not derive vault key from passphrase");
+            log::error!("Argon2 Error: {:?}", err);
+            RelynxError::LoadVaultError
+        })?;
+    Ok(key)
+}
+
+fn decode_base64(value: &str) -> Result<Vec<u8>, RelynxError> {
+    general_purpose::STANDARD.decode(value).map_err(|err| {
+        log::error!("Invalid base64 within vault file");
+        log::error!("Decode Error: {:?}", err);
+        RelynxError::LoadVaultError
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vault_roundtrip() {
+        let path = std::env::temp_dir().join(format!("relynx-{}.vault", uuid::Uuid::new_v4()));
+
+        let mut vault = open_vault(path.clone(), "passphrase").unwrap();
+        vault.content.insert(
+            "/collection".to_string(),
+            VaultEnvironments::from([(
+                "dev".to_string(),
+                VaultSecrets::from([("token".to_string(), "secret-token".to_string())]),
+            )]),
+        );
+        save_vault(&vault).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("secret-token"));
+
+        let reopened = open_vault(path.clone(), "passphrase").unwrap();
+        assert_eq!(reopened.content, vault.content);
+
+        assert!(matches!(
+            open_vault(path.clone(), "wrong passphrase"),
+            Err(RelynxError::InvalidVaultPassphrase)
+        ));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_merge_secrets() {
+        let secrets = |values: &[(&str, &str)]| -> VaultSecrets {
+            values
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect()
+        };
+        let mut stored = VaultEnvironments::from([
+            (
+                "dev".to_string(),
+                secrets(&[("token", "a"), ("password", "b")]),
+            ),
+            ("prod".to_string(), secrets(&[("token", "c")])),
+        ]);
+        merge_secrets(
+            &mut stored,
+            VaultEnvironments::from([
+                ("dev".to_string(), secrets(&[("token", "d")])),
+                ("prod".to_string(), secrets(&[("api_key", "e")])),
+            ]),
+            &["dev".to_string()],
+        );
+        assert_eq!(
+            stored,
+            VaultEnvironments::from([
+                ("dev".to_string(), secrets(&[("token", "d")])),
+                (
+                    "prod".to_string(),
+                    secrets(&[("token", "c"), ("api_key", "e")])
+                ),
+            ])
+        );
+    }
+}
@@ -11,14 +11,21 @@ export type Procedures = {
         { key: "delete_node", input: DeleteNodeParams, result: null } | 
         { key: "drag_and_drop", input: DragAndDropParams, result: DragAndDropResult } | 
         { key: "get_app_environment", input: never, result: AppEnvironment } | 
+        { key: "get_client_settings", input: Collection, result: ClientSettings } | 
         { key: "get_cookie_jar", input: GetCookieJarParams, result: CookieJar } | 
+        { key: "get_group_auth", input: GetGroupAuthParams, result: RequestAuth | null } | 
+        { key: "get_history_entry", input: HistoryEntryParams, result: HistoryEntry } | 
+        { key: "get_history_settings", input: Collection, result: HistorySettings } | 
         { key: "get_log_path", input: never, result: string } | 
         { key: "get_response_filepath", input: string, result: string | null } | 
+        { key: "get_tls_profiles", input: Collection, result: TlsProfile[] } | 
+        { key: "get_vault_status", input: never, result: VaultStatus } | 
         { key: "hide_group", input: string, result: null } | 
         { key: "import_jetbrains_folder", input: ImportJetbrainsHttpFolderParams, result: Workspace } | 
         { key: "import_postman_collection", input: ImportPostmanCommandParams, result: ImportCollectionResult } | 
         { key: "is_directory_empty", input: string, result: boolean } | 
         { key: "is_signature_valid", input: LicenseData, result: boolean } | 
+        { key: "list_history", input: ListHistoryParams, result: HistoryEntrySummary[] } | 
         { key: "load_environments", input: string, result: Environment[] } | 
         { key: "load_license_data", input: never, result: LicenseData } | 
         { key: "load_requests_for_collection", input: Collection, result: LoadRequestsResult } | 
@@ -27,6 +34,7 @@ export type Procedures = {
         { key: "remove_collection", input: Collection, result: Workspace } | 
         { key: "rename_group", input: RenameGroupParams, result: string } | 
         { key: "reorder_nodes_within_parent", input: ReorderNodesParams, result: RequestTreeNode } | 
+        { key: "run_collection", input: RunCollectionCommand, result: RunCollectionResult } | 
         { key: "run_request", input: RunRequestCommand, result: RequestResult } | 
         { key: "save_environments", input: SaveEnvironmentsParams, result: null } | 
         { key: "save_request", input: SaveRequestCommand, result: string } | 
@@ -36,12 +44,22 @@ export type Procedures = {
         { key: "validate_group_name", input: ValidateGroupNameParams, result: ValidateGroupNameResult } | 
         { key: "validate_response_filepath", input: string, result: boolean },
     mutations: 
+        { key: "clear_auth_tokens", input: never, result: null } | 
+        { key: "clear_history", input: Collection, result: null } | 
+        { key: "delete_history_entry", input: HistoryEntryParams, result: null } | 
+        { key: "lock_vault", input: never, result: null } | 
+        { key: "rerun_history_entry", input: RerunHistoryEntryParams, result: RequestResult } | 
+        { key: "save_client_settings", input: SaveClientSettingsParams, result: null } | 
         { key: "save_cookie_jar", input: SaveCookieJarParams, result: null } | 
-        { key: "save_license_data", input: LicenseData, result: null },
+        { key: "save_group_auth", input: SaveGroupAuthParams, result: null } | 
+        { key: "save_history_settings", input: SaveHistorySettingsParams, result: null } | 
+        { key: "save_license_data", input: LicenseData, result: null } | 
+        { key: "save_tls_profiles", input: SaveTlsProfilesParams, result: null } | 
+        { key: "unlock_vault", input: UnlockVaultParams, result: null },
     subscriptions: never
 };
 
-export type LoadRequestsResult = { request_tree: RequestTree; errs: ParseErrorMsg[] }
+export type LoadRequestsResult = { request_tree: RequestTree; errs: ParseErrorMsg[]; warnings: string[] }
 
 export type ImportPostmanCommandParams = { workspace: Workspace; import_postman_path: string; import_result_path: string }
 
@@ -71,7 +89,7 @@ export type HttpMethod = "GET" | "POST" | "PUT" | "PATCH" | "DELETE" | "HEAD" |
 
 export type ParseErrorMsg = { filepath: string; filename: string; msg: string }
 
-export type Environment = { name: string; variables: EnvironmentVariable[]; secrets: EnvironmentSecret[] }
+export type Environment = { name: string; variables: EnvironmentVariable[]; secrets: EnvironmentSecret[]; extends: string | null; ssl_configuration: SslConfiguration | null; auth_configurations: { [key: string]: OAuth2Configuration }; vault_loaded: boolean }
 
 export type RequestTree = { root: RequestTreeNode }
 
@@ -85,7 +103,17 @@ export type Cookie = { domain: string; path: string; name: string; value: string
 
 export type RequestSettings = { no_redirect: boolean | null; no_log: boolean | null; no_cookie_jar: boolean | null }
 
-export type RequestResult = { id: string; result: string; status_code: string; total_time: number; total_result_size: number; content_type: string | null; warnings: string[]; result_file: string | null; result_file_folder: string | null }
+export type RequestResult = { id: string; result: string; charset: string | null; binary: BinaryPayload | null; status_code: string; http_version: string; total_time: number; total_result_size: number; raw_result_size: number; content_type: string | null; warnings: string[]; result_file: string | null; result_file_folder: string | null; environment: Environment | null; tests: TestResult[]; tls: TlsInfo | null; timings: CallTimings[]; headers: Header[]; redirects: RedirectHop[]; sent_request: SentRequest }
+
+export type RunCollectionCommand = { collection: Collection; group_path: string | null; environment: Environment | null; data_file: string | null }
+
+export type RunCollectionProgress = { iteration: number; total_iterations: number; index: number; total: number; request_id: string; request_name: string; rest_file_path: string; result: RequestResult | null; error: string | null }
+
+export type RunCollectionIteration = { index: number; data: { [key: string]: string }; runs: RunCollectionProgress[] }
+
+export type RunCollectionResult = { iterations: RunCollectionIteration[]; environment: Environment | null }
+
+export type TestResult = { name: string; passed: boolean; message: string | null; duration: number }
 
 export type DragAndDropResult = { new_drop_node: RequestTreeNode; remove_drag_node_parent: boolean }
 
@@ -93,7 +121,7 @@ export type SaveResponse = { NewFileIfExists: string } | { RewriteFile: string }
 
 export type DeleteNodeParams = { collection: Collection; node: RequestTreeNode; file_node: RequestTreeNode | null }
 
-export type RequestModel = { id: string; name: string; description: string; method: HttpMethod; url: string; query_params: QueryParam[]; headers: Header[]; body: RequestBody; rest_file_path: string; http_version: Replaced<HttpVersion>; settings: RequestSettings; save_response: SaveResponse | null; pre_request_script: PreRequestScript | null; response_handler: ResponseHandler | null }
+export type RequestModel = { id: string; name: string; description: string; method: HttpMethod; url: string; query_params: QueryParam[]; headers: Header[]; body: RequestBody; rest_file_path: string; http_version: Replaced<HttpVersion>; settings: RequestSettings; save_response: SaveResponse | null; pre_request_script: PreRequestScript | null; response_handler: ResponseHandler | null; auth: RequestAuth | null; client_settings: ClientSettings }
 
 export type DispositionField = { name: string; filename: string | null; filename_star: string | null }
 
@@ -117,9 +145,9 @@ export type RenameGroupParams = { collection_path: string; old_path: string; new
 
 export type ChooseFileRelativeToParams = { base_path: string }
 
-export type EnvironmentSecret = { name: string; initial_value: string; current_value: string | null; description: string | null; persist_to_file: boolean }
+export type EnvironmentSecret = { name: string; initial_value: string; current_value: string | null; description: string | null; persist_to_file: boolean; source: string | null; is_json: boolean }
 
-export type EnvironmentVariable = { name: string; initial_value: string; current_value: string | null; description: string | null }
+export type EnvironmentVariable = { name: string; initial_value: string; current_value: string | null; description: string | null; source: string | null; is_json: boolean }
 
 export type DataSource<T> = { Raw: T } | { FromFilepath: string }
 
@@ -142,3 +170,63 @@ export type ImportJetbrainsHttpFolderParams = { workspace: Workspace; import_jet
 export type HttpVersion = { major: number; minor: number }
 
 export type ImportCollectionResult = { collection: Collection }
+
+export type VaultStatus = { exists: boolean; unlocked: boolean }
+
+export type UnlockVaultParams = { passphrase: string }
+
+export type SslConfiguration = { client_certificate: string | null; client_certificate_format: CertificateFormat; client_certificate_key: string | null; has_certificate_passphrase: boolean; verify_host_certificate: boolean }
+
+export type OAuth2Configuration = { grant_type: OAuth2GrantType; token_url: string; auth_url: string | null; redirect_url: string | null; client_id: string; client_secret: string | null; client_credentials: OAuth2ClientCredentials; scope: string | null; username: string | null; password: string | null; pkce: boolean; custom_parameters: { [key: string]: string } }
+
+export type OAuth2GrantType = "ClientCredentials" | "Password" | "AuthorizationCode"
+
+export type OAuth2ClientCredentials = "Basic" | "InBody" | "None"
+
+export type RequestAuth = "NoAuth" | { Basic: { username: string; password: string } } | { Bearer: { token: string } } | { Digest: { username: string; password: string } } | { ApiKey: { name: string; value: string; location: ApiKeyLocation } } | { AwsSigV4: { access_key_id: string; secret_access_key: string; session_token: string | null; region: string; service: string } }
+
+export type ApiKeyLocation = "Header" | "Query"
+
+export type GetGroupAuthParams = { collection: Collection; group_path: string }
+
+export type SaveGroupAuthParams = { collection: Collection; group_path: string; auth: RequestAuth | null }
+
+export type ClientSettings = { timeout: number | null; connect_timeout: number | null; proxy: string | null; no_proxy: string | null; insecure: boolean | null; cacert_file: string | null; client_cert_file: string | null; client_key_file: string | null; resolves: string[] | null; connects_to: string[] | null; max_redirect: number | null; compressed: boolean | null; user_agent: string | null; certificate_expiry_warning_days: number | null }
+
+export type SaveClientSettingsParams = { collection: Collection; settings: ClientSettings }
+
+export type TlsProfile = { host: string; client_certificate: string | null; client_certificate_format: CertificateFormat; client_key: string | null; passphrase: string | null; ca_bundles: string[]; pinned_public_keys: string[] }
+
+export type CertificateFormat = "Pem" | "Pkcs12"
+
+export type SaveTlsProfilesParams = { collection: Collection; tls_profiles: TlsProfile[] }
+
+export type TlsInfo = { version: string | null; cipher: string | null; certificates: CertificateInfo[] }
+
+export type CertificateInfo = { subject: string; issuer: string; start_date: string; expire_date: string; serial_number: string }
+
+export type CallTimings = { url: string; status_code: number; begin_call: string; end_call: string; name_lookup: number; connect: number; app_connect: number; pre_transfer: number; start_transfer: number; total: number; size_upload: number; size_download: number; speed_upload: number; speed_download: number }
+
+export type RedirectHop = { url: string; status_code: number; location: string | null; request: SentRequest }
+
+export type SentRequest = { method: string; url: string; headers: Header[]; body: string }
+
+export type BinaryPayload = { mime_type: string | null; size: number; content: BinaryContent }
+
+export type BinaryContent = { Base64: string } | { File: string }
+
+export type HistorySettings = { enabled: boolean; max_entries: number; max_age_days: number; max_body_size: number }
+
+export type HistoryEntry = { id: string; executed_at: string; environment_name: string | null; request: RequestModel; result: RequestResult; truncated: boolean }
+
+export type HistoryEntrySummary = { id: string; executed_at: string; environment_name: string | null; request_name: string; rest_file_path: string; method: string; url: string; status_code: string; total_time: number }
+
+export type HistoryQuery = { search: string | null; rest_file_path: string | null; request_name: string | null; limit: number | null }
+
+export type ListHistoryParams = { collection: Collection; query: HistoryQuery }
+
+export type HistoryEntryParams = { collection: Collection; entry_id: string }
+
+export type RerunHistoryEntryParams = { collection: Collection; entry_id: string; environment: Environment | null }
+
+export type SaveHistorySettingsParams = { collection: Collection; settings: HistorySettings }
@@ -14,6 +14,9 @@ import { ImportJetbrainsHttpFolder } from "../components/modals/ImportJetbrainsH
 import { ErrorReportingModal } from "../components/modals/ErrorReportingModal";
 import { EditRequestNameModal } from "../components/modals/EditRequestNameModal";
 import { AddCookieHeaderModal } from "../components/modals/AddCookieHeaderModal";
+import { ClientSettingsModal } from "../components/modals/ClientSettingsModal";
+import { TlsProfilesModal } from "../components/modals/TlsProfilesModal";
+import { HistoryModal } from "../components/modals/HistoryModal";
 
 
 export const addCollectionToWorkspace = (newCollection: Collection) => {
@@ -173,3 +176,26 @@ export const openAddCookieModal = (): Promise<[key: string, value: string] | und
   return modalPromise()
 }
 
+export const openClientSettingsModal = (collection: Collection) => {
+  const modalPromise = create(({ onResolve, onReject, isOpen }) => {
+    return <ClientSettingsModal collection={collection} isOpen={isOpen} onResolve={onResolve} onReject={() => onReject()} />
+  });
+  modalPromise().then(() => {
+  }).catch(() => { })
+}
+
+export const openTlsProfilesModal = (collection: Collection) => {
+  const modalPromise = create(({ onResolve, onReject, isOpen }) => {
+    return <TlsProfilesModal collection={collection} isOpen={isOpen} onResolve={onResolve} onReject={() => onReject()} />
+  });
+  modalPromise().then(() => {
+  }).catch(() => { })
+}
+
+export const openHistoryModal = (collection: Collection) => {
+  const modalPromise = create(({ onResolve, onReject, isOpen }) => {
+    return <HistoryModal collection={collection} isOpen={isOpen} onResolve={onResolve} onReject={() => onReject()} />
+  });
+  modalPromise().then(() => {
+  }).catch(() => { })
+}
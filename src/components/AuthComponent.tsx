@@ -0,0 +1,126 @@
+import { Dropdown } from "primereact/dropdown"
+import { InputText } from "primereact/inputtext"
+import { ApiKeyLocation, RequestAuth } from "../bindings"
+import { HelpTooltip } from "./HelpTooltip"
+
+interface ComponentProps {
+  auth: RequestAuth | null
+  updateAuth: (auth: RequestAuth | null) => void
+}
+
+type AuthType = "Inherit" | "NoAuth" | "Basic" | "Bearer" | "Digest" | "ApiKey" | "AwsSigV4"
+
+const AUTH_TYPES: { label: string, value: AuthType }[] = [
+  { label: "Inherit from parent group", value: "Inherit" },
+  { label: "No Auth", value: "NoAuth" },
+  { label: "Basic", value: "Basic" },
+  { label: "Bearer Token", value: "Bearer" },
+  { label: "Digest", value: "Digest" },
+  { label: "API Key", value: "ApiKey" },
+  { label: "AWS Signature V4", value: "AwsSigV4" },
+];
+
+const API_KEY_LOCATIONS: { label: string, value: ApiKeyLocation }[] = [
+  { label: "Header", value: "Header" },
+  { label: "Query Param", value: "Query" },
+];
+
+const getAuthType = (auth: RequestAuth | null): AuthType => {
+  if (auth === null) {
+    return "Inherit";
+  }
+  if (auth === "NoAuth") {
+    return "NoAuth";
+  }
+  return Object.keys(auth)[0] as AuthType;
+}
+
+const newAuth = (authType: AuthType): RequestAuth | null => {
+  switch (authType) {
+    case "Inherit":
+      return null;
+    case "NoAuth":
+      return "NoAuth";
+    case "Basic":
+      return { Basic: { username: "", password: "" } };
+    case "Bearer":
+      return { Bearer: { token: "" } };
+    case "Digest":
+      return { Digest: { username: "", password: "" } };
+    case "ApiKey":
+      return { ApiKey: { name: "", value: "", location: "Header" } };
+    case "AwsSigV4":
+      return { AwsSigV4: { access_key_id: "", secret_access_key: "", session_token: null, region: "", service: "" } };
+  }
+}
+
+// Editor for the auth of a request or a group, the values may contain environment variables
+export function AuthComponent(props: ComponentProps) {
+
+  const authType = getAuthType(props.auth);
+  // all variants except NoAuth are objects with a single key
+  const values: any = props.auth !== null && props.auth !== "NoAuth" ? Object.values(props.auth)[0] : {};
+
+  const updateValue = (key: string, value: string | null) => {
+    if (authType === "Inherit" || authType === "NoAuth") {
+      return;
+    }
+    props.updateAuth({ [authType]: { ...values, [key]: value } } as RequestAuth);
+  }
+
+  const field = (key: string, label: string, password?: boolean) => {
+    return (
+      <div key={key} style={{ display: 'flex', alignItems: 'center', marginTop: '10px' }}>
+        <label htmlFor={key} style={{ width: '200px', textAlign: 'start' }}>{label}</label>
+        <InputText id={key} type={password ? 'password' : 'text'} style={{ width: '400px' }}
+          value={values[key] ?? ""}
+          onChange={(e) => updateValue(key, e.target.value)} />
+      </div>
+    )
+  }
+
+  return (
+    <div className="headers-block" style={{ display: 'flex', flexDirection: 'column', alignItems: 'flex-start' }}>
+      <div style={{ display: 'flex', alignItems: 'center' }}>
+        <Dropdown value={authType} options={AUTH_TYPES}
+          onChange={(e) => props.updateAuth(newAuth(e.value))} />
+        <HelpTooltip style={{ marginLeft: '20px' }}
+          text="The auth is applied when the request is sent and is not written into the request file. Credentials such as passwords and tokens have to reference a secret of the environment, for example {{token}}, as the auth is stored within the collection config." />
+      </div>
+      <div style={{ marginTop: '20px', display: 'flex', flexDirection: 'column', alignItems: 'flex-start' }}>
+        {(authType === "Basic" || authType === "Digest") &&
+          <>
+            {field("username", "Username")}
+            {field("password", "Password", true)}
+          </>
+        }
+        {authType === "Bearer" && field("token", "Token", true)}
+        {authType === "ApiKey" &&
+          <>
+            {field("name", "Key")}
+            {field("value", "Value", true)}
+            <div style={{ display: 'flex', alignItems: 'center', marginTop: '10px' }}>
+              <label htmlFor="location" style={{ width: '200px', textAlign: 'start' }}>Add to</label>
+              <Dropdown id="location" value={values.location} options={API_KEY_LOCATIONS}
+                onChange={(e) => updateValue("location", e.value)} />
+            </div>
+          </>
+        }
+        {authType === "AwsSigV4" &&
+          <>
+            {field("access_key_id", "Access Key ID")}
+            {field("secret_access_key", "Secret Access Key", true)}
+            <div style={{ display: 'flex', alignItems: 'center', marginTop: '10px' }}>
+              <label htmlFor="session_token" style={{ width: '200px', textAlign: 'start' }}>Session Token</label>
+              <InputText id="session_token" type="password" style={{ width: '400px' }}
+                value={values.session_token ?? ""}
+                onChange={(e) => updateValue("session_token", e.target.value === "" ? null : e.target.value)} />
+            </div>
+            {field("region", "Region")}
+            {field("service", "Service")}
+          </>
+        }
+      </div>
+    </div>
+  )
+}
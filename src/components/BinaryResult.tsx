@@ -0,0 +1,33 @@
+import { Button } from "primereact/button";
+import { BinaryPayload } from "../bindings";
+import { backend } from "../rpc";
+import { CopyToClipboard } from "./CopyToClipboard";
+
+export interface ComponentProps {
+  payload: BinaryPayload
+}
+
+// Responses such as images or archives are not displayed as text, images are shown as a preview
+export function BinaryResult(props: ComponentProps) {
+  const mimeType = props.payload.mime_type ?? "application/octet-stream";
+  const content = props.payload.content;
+
+  return (
+    <div style={{ display: 'flex', flexDirection: 'column', alignItems: 'flex-start', marginTop: '10px' }}>
+      <p style={{ textAlign: 'start' }}>Binary response of type '{mimeType}' with {props.payload.size} bytes.</p>
+      {"Base64" in content && mimeType.startsWith("image/") &&
+        <img src={`data:${mimeType};base64,${content.Base64}`} alt="response" style={{ maxWidth: '100%', marginTop: '10px' }} />
+      }
+      {"File" in content &&
+        <div style={{ display: 'flex', alignItems: 'center', marginTop: '10px' }}>
+          <span>The response is too large to be displayed and has been saved in: {content.File}</span>
+          <CopyToClipboard value={content.File} tooltip={"Copy Path To Clipboard"} />
+          <Button icon="pi pi-folder-open" className={"p-button-text"}
+            tooltip={"Open folder"}
+            style={{ width: '30px', height: '30px', marginLeft: '5px' }}
+            onClick={() => backend.openFolderNative(content.File.substring(0, Math.max(content.File.lastIndexOf('/'), content.File.lastIndexOf('\\'))))} />
+        </div>
+      }
+    </div>
+  )
+}
@@ -0,0 +1,99 @@
+import { Button } from "primereact/button"
+import { InputText } from "primereact/inputtext"
+import { InputTextarea } from "primereact/inputtextarea"
+import { TriStateCheckbox } from "primereact/tristatecheckbox"
+import { ClientSettings } from "../bindings"
+import { backend } from "../rpc"
+import { HelpTooltip } from "./HelpTooltip"
+
+interface ComponentProps {
+  settings: ClientSettings
+  updateSettings: (settings: ClientSettings) => void
+}
+
+type TextKey = "proxy" | "no_proxy" | "user_agent" | "cacert_file" | "client_cert_file" | "client_key_file"
+type NumberKey = "timeout" | "connect_timeout" | "max_redirect" | "certificate_expiry_warning_days"
+type BoolKey = "insecure" | "compressed"
+type ListKey = "resolves" | "connects_to"
+
+const labelStyle = { width: '200px', textAlign: 'start' as const };
+const rowStyle = { display: 'flex', alignItems: 'center', marginTop: '10px' };
+
+// Form for client settings, empty fields are not set and keep the value of the level below
+export function ClientSettingsComponent(props: ComponentProps) {
+
+  const update = (partial: Partial<ClientSettings>) => {
+    props.updateSettings({ ...props.settings, ...partial });
+  }
+
+  const textField = (key: TextKey, label: string, isFile?: boolean) => {
+    return (
+      <div style={rowStyle}>
+        <label htmlFor={key} style={labelStyle}>{label}</label>
+        <InputText id={key} style={{ width: '400px' }} value={props.settings[key] ?? ""}
+          onChange={(e) => update({ [key]: e.target.value === "" ? null : e.target.value })} />
+        {isFile &&
+          <Button icon="pi pi-folder-open" className={"p-button-text"} style={{ marginLeft: '5px' }}
+            onClick={() => backend.selectFile((path: string) => update({ [key]: path }))} />
+        }
+      </div>
+    )
+  }
+
+  const numberField = (key: NumberKey, label: string) => {
+    return (
+      <div style={rowStyle}>
+        <label htmlFor={key} style={labelStyle}>{label}</label>
+        <InputText id={key} keyfilter="pint" style={{ width: '150px' }} value={props.settings[key]?.toString() ?? ""}
+          onChange={(e) => update({ [key]: e.target.value === "" ? null : parseInt(e.target.value) })} />
+      </div>
+    )
+  }
+
+  const boolField = (key: BoolKey, label: string) => {
+    return (
+      <div style={rowStyle}>
+        <label htmlFor={key} style={labelStyle}>{label}</label>
+        <TriStateCheckbox id={key} value={props.settings[key]}
+          onChange={(e) => update({ [key]: e.value ?? null })} />
+      </div>
+    )
+  }
+
+  const listField = (key: ListKey, label: string, placeholder: string) => {
+    return (
+      <div style={rowStyle}>
+        <label htmlFor={key} style={labelStyle}>{label}</label>
+        <InputTextarea id={key} style={{ width: '400px' }} rows={2} placeholder={placeholder}
+          value={props.settings[key]?.join("\n") ?? ""}
+          onChange={(e) => {
+            let entries = e.target.value.split("\n");
+            update({ [key]: e.target.value === "" ? null : entries });
+          }} />
+      </div>
+    )
+  }
+
+  return (
+    <div style={{ display: 'flex', flexDirection: 'column', alignItems: 'flex-start' }}>
+      <div style={{ display: 'flex', alignItems: 'center' }}>
+        <p style={{ textAlign: 'start' }}>Empty fields keep the value of the collection or the default.</p>
+        <HelpTooltip style={{ marginLeft: '20px' }} text="Requests override the client settings of their collection. Files within the collection are stored relative to it." />
+      </div>
+      {numberField("timeout", "Timeout (seconds)")}
+      {numberField("connect_timeout", "Connect timeout (seconds)")}
+      {numberField("max_redirect", "Max redirects")}
+      {textField("proxy", "Proxy")}
+      {textField("no_proxy", "No proxy")}
+      {textField("user_agent", "User agent")}
+      {boolField("insecure", "Skip certificate verification")}
+      {boolField("compressed", "Request compressed response")}
+      {textField("cacert_file", "CA certificate", true)}
+      {textField("client_cert_file", "Client certificate", true)}
+      {textField("client_key_file", "Client key", true)}
+      {numberField("certificate_expiry_warning_days", "Certificate expiry warning (days)")}
+      {listField("resolves", "Resolve", "example.com:443:127.0.0.1")}
+      {listField("connects_to", "Connect to", "example.com:443:other.example.com:8443")}
+    </div>
+  )
+}
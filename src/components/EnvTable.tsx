@@ -25,6 +25,8 @@ export interface RowData {
   currentValue: string | undefined | null,
   description: string,
   persistToFile?: boolean,
+  // name of the environment the value is inherited from
+  source?: string | null,
   invalid?: boolean
   error?: string
 }
@@ -35,7 +37,12 @@ export function EnvTable(props: ComponentProps) {
 
 
   const nameTemplate = (rowData: RowData) => {
-    return sharedTemplate(rowData.name, "Name", rowData.error, rowData.invalid)
+    return <>
+      {sharedTemplate(rowData.name, "Name", rowData.error, rowData.invalid)}
+      {rowData.source &&
+        <small style={{ color: 'gray' }}>{`inherited from '${rowData.source}'`}</small>
+      }
+    </>
   }
 
   const initialValueTemplate = (rowData: RowData) => {
@@ -62,6 +69,7 @@ export function EnvTable(props: ComponentProps) {
         newSecret.initial_value = secret.initial_value;
         newSecret.current_value = secret.current_value;
         newSecret.persist_to_file = checked;
+        newSecret.is_json = secret.is_json;
         return newSecret;
       } else {
         return secret;
@@ -101,6 +109,7 @@ export function EnvTable(props: ComponentProps) {
             newSecret.current_value = null;
           }
           newSecret.persist_to_file = secret.persist_to_file // isn't edited here but by the checkbox...
+          newSecret.is_json = secret.is_json;
           if (rowData.description && rowData.description !== "") {
             newSecret.description = rowData.description;
           } else {
@@ -118,6 +127,7 @@ export function EnvTable(props: ComponentProps) {
           let newVar = newEnvironmentVariable();
           newVar.name = rowData.name;
           newVar.initial_value = rowData.initialValue;
+          newVar.is_json = variable.is_json;
           if (rowData.currentValue && rowData.currentValue !== "") {
             newVar.current_value = rowData.currentValue;
           } else {
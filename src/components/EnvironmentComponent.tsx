@@ -4,14 +4,15 @@ import { Dropdown } from "primereact/dropdown";
 import { Dialog } from "primereact/dialog";
 import { useRequestModelStore } from "../stores/requestStore";
 import { newEnvironment } from "../model/model";
-import { CSSProperties, useContext, useEffect } from "react";
-import { envDropdownStyle, environmentsToOptions, newEnvironmentSecret, newEnvironmentVariable } from "../model/environment";
+import { CSSProperties, useContext, useEffect, useState } from "react";
+import { SHARED_ENV_NAME, envDropdownStyle, environmentsToOptions, newEnvironmentSecret, newEnvironmentVariable } from "../model/environment";
 import { ToastContext } from "../App";
 import { catchError } from "../common/errorhandling";
 import { create } from "react-modal-promise";
 import { CreateEnvironmentModal } from "./modals/CreateEnvironmentModal";
+import { UnlockVaultModal } from "./modals/UnlockVaultModal";
 import { EnvTable, RowData } from "./EnvTable";
-import { EnvironmentSecret, EnvironmentVariable, Environment, Collection } from "../bindings";
+import { EnvironmentSecret, EnvironmentVariable, Environment, Collection, VaultStatus } from "../bindings";
 import { scrollMainToTop } from "../common/common";
 import { getUpdatedEnvironment } from "../model/request";
 import { backend } from "../rpc";
@@ -32,12 +33,52 @@ export function EnvironmentComponent(_props: ComponentProps) {
 
   const toast = useContext(ToastContext)
 
+  const [vaultStatus, setVaultStatus] = useState<VaultStatus | undefined>(undefined);
+
   useEffect(() => {
     if (environment === undefined && environments.length > 0) {
       setStoreCurrentEnvironment(environments[0]);
     }
+    backend.getVaultStatus().then(setVaultStatus).catch(catchError);
   }, [])
 
+  // secrets of the vault are only present within the environments if it is unlocked
+  const reloadEnvironments = () => {
+    backend.loadEnvironments(collection.path).then((newEnvironments: Environment[]) => {
+      setEnvironments(newEnvironments);
+      setStoreCurrentEnvironment(newEnvironments.find((env: Environment) => env.name === environment?.name));
+    }).catch(catchError);
+  }
+
+  const openUnlockVaultDialog = () => {
+    const unlockVaultModal = create(({ isOpen, onResolve, onReject }) => {
+      return <UnlockVaultModal isOpen={isOpen} onResolve={onResolve} onReject={onReject}
+        vaultExists={vaultStatus?.exists ?? false} />
+    });
+
+    unlockVaultModal().then((passphrase?: string) => {
+      if (!passphrase) {
+        return
+      }
+      backend.unlockVault(passphrase).then(() => {
+        setVaultStatus({ exists: true, unlocked: true });
+        toast.showInfo("Unlocked vault", "");
+        reloadEnvironments();
+      }).catch(catchError);
+    })
+  }
+
+  const lockVault = () => {
+    backend.lockVault().then(() => {
+      setVaultStatus({ exists: true, unlocked: false });
+      reloadEnvironments();
+    }).catch(catchError);
+  }
+
+  const clearAuthTokens = () => {
+    backend.clearAuthTokens().catch(catchError);
+  }
+
   const updateName = (name: string) => {
     if (!environment) {
       return
@@ -52,6 +93,18 @@ export function EnvironmentComponent(_props: ComponentProps) {
   }
 
 
+  const updateExtends = (extendsName: string) => {
+    if (!environment) {
+      return
+    }
+    let newEnv = getUpdatedEnvironment(environment, { extends: extendsName === '' ? null : extendsName });
+    let newEnvironments = environments.map((env: Environment) => env.name === environment.name ? newEnv : env);
+    // inherited values are resolved by the backend when the environments are loaded again
+    backend.saveEnvironments(collection, newEnvironments).then(() => {
+      reloadEnvironments();
+    }).catch(catchError);
+  }
+
   const updateEnvironmentInState = (name: string, newEnvironment: Environment) => {
     let index = environments.findIndex((env: Environment) => env.name == name);
     let newEnvironments = [...environments];
@@ -172,6 +225,7 @@ export function EnvironmentComponent(_props: ComponentProps) {
         initialValue: environmentVariable.initial_value,
         currentValue: environmentVariable.current_value,
         description: environmentVariable.description ?? "",
+        source: environmentVariable.source,
         invalid: false
       } as RowData
     });
@@ -190,6 +244,7 @@ export function EnvironmentComponent(_props: ComponentProps) {
         currentValue: secret.current_value,
         description: secret.description ?? "",
         persistToFile: secret.persist_to_file,
+        source: secret.source,
         invalid: false
       } as RowData
     });
@@ -253,7 +308,8 @@ export function EnvironmentComponent(_props: ComponentProps) {
   }
 
   const variableHelpText = "Variables can be inserted in your request. Use the syntax {{variableName}} within the request headers or params in order to define the placeholders for your variables. Variables are saved in the file 'http-client.env.json' for the respective environment in your collection folder. Please use secrets for passwords / tokens."
-  const secretHelpText = "Secrets can contain values and be inserted into your request the same as variables. However, for secrets you can define if you want to persist them to a file within your collection or not. The secrets are stored unencrypted within the 'http-client.private.env.json' of your collection and be sure not to check this file into git and exclude it with .gitignore. Uncheck 'Persist to File' if you do not want the secret stored in the private environment file. You will need to enter the credentials again when using relynx."
+  const secretHelpText = "Secrets can contain values and be inserted into your request the same as variables. However, for secrets you can define if you want to persist them to a file within your collection or not. The secrets are stored unencrypted within the 'http-client.private.env.json' of your collection and be sure not to check this file into git and exclude it with .gitignore. Uncheck 'Persist to File' if you do not want the secret stored in the private environment file. Such secrets are stored encrypted within your vault if it is unlocked, otherwise you will need to enter the credentials again when using relynx."
+  const extendsHelpText = `Variables and secrets of the extended environment are inherited unless they are defined within this environment. Values of the '${SHARED_ENV_NAME}' environment are inherited by all environments.`
   const helpTextStyle: CSSProperties = { marginTop: '10px', marginBottom: '20px', textAlign: 'left' }
   const envSectionStyle: CSSProperties = { display: 'flex', flexDirection: 'column' }
 
@@ -293,6 +349,13 @@ export function EnvironmentComponent(_props: ComponentProps) {
                 <Button label="Delete" onClick={openDeleteConfirmDialog} className={'p-button-outlined'}
                   icon="pi pi-trash" style={{ marginLeft: '50px' }} />
               </div>
+              <h3 style={{ marginTop: '20px' }}>Extends</h3>
+              <p style={helpTextStyle}>{extendsHelpText}</p>
+              <Dropdown style={envDropdownStyle} optionLabel="name"
+                value={environment.extends ?? ''}
+                options={environmentsToOptions(environments.filter((env: Environment) => env.name !== environment.name && env.name !== SHARED_ENV_NAME), true)}
+                onChange={(e) => updateExtends(e.value)}
+                placeholder={"No Environment"} />
 
             </div>
 
@@ -329,6 +392,15 @@ export function EnvironmentComponent(_props: ComponentProps) {
                 <p style={helpTextStyle}>
                   {secretHelpText}
                 </p>
+                <div style={{ display: 'flex', alignItems: 'center', marginBottom: '20px' }}>
+                  <i className={vaultStatus?.unlocked ? "pi pi-lock-open" : "pi pi-lock"} style={{ marginRight: '10px' }}></i>
+                  <span style={{ marginRight: '20px' }}>{vaultStatus?.unlocked ? "Vault unlocked" : "Vault locked"}</span>
+                  {vaultStatus?.unlocked ?
+                    <Button label={"Lock Vault"} className={'p-button-raised p-button-text'} onClick={lockVault} /> :
+                    <Button label={vaultStatus?.exists ? "Unlock Vault" : "Create Vault"} className={'p-button-raised p-button-text'}
+                      onClick={openUnlockVaultDialog} />
+                  }
+                </div>
                 <EnvTable isSecret={true} rowData={secretRowData}
                   updateEnvironmentInState={updateEnvironmentInState}
                   onRemoveRowData={removeSecret} />
@@ -336,6 +408,23 @@ export function EnvironmentComponent(_props: ComponentProps) {
                 <Button icon={'pi pi-plus'} label={"Add Secret"} className={'p-button-raised p-button-text'}
                   style={{ marginTop: '30px', maxWidth: '180px' }} onClick={addSecretVariable} />
               </div>
+
+              {/*OAuth 2.0*/}
+              {Object.keys(environment.auth_configurations).length > 0 &&
+                <div style={envSectionStyle}>
+                  <h2 style={{ marginTop: '40px', marginBottom: '20px', textAlign: 'left' }}>OAuth 2.0</h2>
+                  <p style={helpTextStyle}>
+                    {"Configured in the 'Security.Auth' section of the env file. Use a token within a request with {{$auth.token(\"id\")}}, it is acquired and refreshed automatically."}
+                  </p>
+                  <ul style={{ textAlign: 'left' }}>
+                    {Object.entries(environment.auth_configurations).map(([authId, configuration]) =>
+                      <li key={authId}>{authId} ({configuration.grant_type}, {configuration.token_url})</li>
+                    )}
+                  </ul>
+                  <Button label={"Clear Tokens"} className={'p-button-raised p-button-text'}
+                    style={{ marginTop: '10px', maxWidth: '180px' }} onClick={clearAuthTokens} />
+                </div>
+              }
             </div>
           </>
         }
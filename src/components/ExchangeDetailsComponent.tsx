@@ -0,0 +1,79 @@
+import { Accordion, AccordionTab } from "primereact/accordion";
+import { Header, RedirectHop, RequestResult, SentRequest } from "../bindings";
+import { CopyToClipboard } from "./CopyToClipboard";
+
+export interface ComponentProps {
+  requestResult: RequestResult,
+  style?: any
+}
+
+function HeaderList(props: { headers: Header[] }) {
+  return (
+    <div style={{ display: 'flex', flexDirection: 'column', alignItems: 'flex-start' }}>
+      {props.headers.map((header: Header, index: number) => {
+        return <div key={index} style={{ display: 'flex', marginTop: '3px', textAlign: 'start' }}>
+          <span style={{ fontWeight: 'bold', marginRight: '10px' }}>{header.key}:</span>
+          <span style={{ wordBreak: 'break-all' }}>{header.value}</span>
+        </div>
+      })}
+    </div>
+  )
+}
+
+function SentRequestDetails(props: { request: SentRequest }) {
+  const request = props.request;
+  return (
+    <div style={{ display: 'flex', flexDirection: 'column', alignItems: 'stretch' }}>
+      <div style={{ display: 'flex', alignItems: 'center', textAlign: 'start', marginBottom: '10px' }}>
+        <span style={{ fontWeight: 'bold', marginRight: '10px' }}>{request.method}</span>
+        <span style={{ wordBreak: 'break-all' }}>{request.url}</span>
+      </div>
+      <HeaderList headers={request.headers} />
+      {request.body !== "" &&
+        <div style={{ display: 'flex', alignItems: 'flex-start', marginTop: '10px' }}>
+          <pre style={{ flexGrow: 1, textAlign: 'start', whiteSpace: 'pre-wrap', wordBreak: 'break-all', margin: 0 }}>{request.body}</pre>
+          <CopyToClipboard value={request.body} />
+        </div>
+      }
+    </div>
+  )
+}
+
+// Shows what went over the wire: the response headers, the redirects that have been followed and
+// the requests as they have been sent by the http client
+export function ExchangeDetailsComponent(props: ComponentProps) {
+  const { headers, redirects, sent_request } = props.requestResult;
+
+  return (
+    <Accordion multiple style={props.style} className={"p-accordion-thin"}>
+      <AccordionTab header={`Response Headers (${headers?.length ?? 0})`}>
+        <HeaderList headers={headers ?? []} />
+      </AccordionTab>
+      {redirects && redirects.length > 0 &&
+        <AccordionTab header={`Redirects (${redirects.length})`}>
+          <Accordion multiple className={"p-accordion-thin"}>
+            {redirects.map((hop: RedirectHop, index: number) => {
+              return <AccordionTab key={index} header={
+                <span style={{ wordBreak: 'break-all' }}>
+                  <span style={{ fontWeight: 'bold', marginRight: '10px' }}>{hop.status_code}</span>
+                  {hop.url}
+                  {hop.location && <>
+                    <i className="pi pi-arrow-right" style={{ marginLeft: '10px', marginRight: '10px' }}></i>
+                    {hop.location}
+                  </>}
+                </span>
+              }>
+                <SentRequestDetails request={hop.request} />
+              </AccordionTab>
+            })}
+          </Accordion>
+        </AccordionTab>
+      }
+      {sent_request &&
+        <AccordionTab header={"Request Sent"}>
+          <SentRequestDetails request={sent_request} />
+        </AccordionTab>
+      }
+    </Accordion>
+  )
+}
@@ -7,7 +7,7 @@ import { InputTextarea } from "primereact/inputtextarea";
 import { TabPanel, TabView } from "primereact/tabview";
 import { useRequestModelStore } from "../stores/requestStore";
 import { backend } from '../rpc';
-import { RequestModel, QueryParam, Header, Collection, ImportWarning, RunRequestCommand, RequestResult, EnvironmentVariable, EnvironmentSecret, HttpMethod } from '../bindings';
+import { RequestModel, QueryParam, Header, Collection, ImportWarning, RunRequestCommand, RequestResult, HttpMethod, Environment, RequestAuth } from '../bindings';
 import { ToastContext } from "../App";
 import { catchError, catchErrorWithTitle } from "../common/errorhandling";
 import { Message } from "primereact/message";
@@ -18,6 +18,7 @@ import { changeRequestUrlParams, changeUrlParams, extractQueryParamsFromUrl, HTT
 import { RequestImportMessages } from "./RequestImportMessages";
 import { Accordion, AccordionTab } from "primereact/accordion";
 import { RequestSettingsComponent } from "./RequestSettingsComponent";
+import { AuthComponent } from "./AuthComponent";
 import { CancellationToken } from "../model/error";
 import { Dialog } from "primereact/dialog";
 import { ResultDisplay } from "./ResultDisplay";
@@ -27,6 +28,7 @@ import { QueryParams } from "./QueryParams";
 import { CookieJarComponent } from "./CookieJarComponent";
 import { openEditRequestNameModal } from "../common/modal";
 import { Headers } from "./Headers";
+import { mergeCurrentValues, toBackendEnvironment } from "../model/environment";
 
 interface ComponentProps {
 }
@@ -47,6 +49,9 @@ export function RequestComponent(_props: ComponentProps) {
   const currentCollection = useRequestModelStore((state) => state.currentCollection as Collection);
 
   const currentEnvironment = useRequestModelStore((state) => state.currentEnvironment);
+  const setCurrentEnvironment = useRequestModelStore((state) => state.setCurrentEnvironment);
+  const environments = useRequestModelStore((state) => state.environments);
+  const setEnvironments = useRequestModelStore((state) => state.setEnvironments);
 
   const requestTree = useRequestModelStore((state) => state.requestTree);
 
@@ -99,30 +104,8 @@ export function RequestComponent(_props: ComponentProps) {
       environment: null
     }
 
-    // filter out variables with duplicated key names (keep the first one)
     if (currentEnvironment) {
-      let backendEnvironment = structuredClone(currentEnvironment);
-
-      let presentVarNames: { [name: string]: boolean } = {};
-      backendEnvironment.variables = backendEnvironment.variables.filter((variable: EnvironmentVariable) => {
-        if (presentVarNames[variable.name]) {
-          return false;
-        } else {
-          presentVarNames[variable.name] = true;
-          return true;
-        }
-      });
-
-      backendEnvironment.secrets = backendEnvironment.secrets.filter((secret: EnvironmentSecret) => {
-        if (presentVarNames[secret.name]) {
-          return false;
-        } else {
-          presentVarNames[secret.name] = true;
-          return true;
-        }
-      });
-
-      backendRequest.environment = backendEnvironment;
+      backendRequest.environment = toBackendEnvironment(currentEnvironment);
     }
 
     // reset request result before the request
@@ -143,6 +126,12 @@ export function RequestComponent(_props: ComponentProps) {
       }
       setRequestResult(result);
 
+      if (result.environment && currentEnvironment) {
+        let newEnvironment = mergeCurrentValues(currentEnvironment, result.environment);
+        setCurrentEnvironment(newEnvironment);
+        setEnvironments(environments.map((environment: Environment) => environment.name === newEnvironment.name ? newEnvironment : environment));
+      }
+
       showResult();
 
       result.warnings.forEach((warning: string) => {
@@ -394,6 +383,11 @@ export function RequestComponent(_props: ComponentProps) {
             <Headers updateRequest={updateRequest} request={currentRequest} />
           </TabPanel>
 
+          <TabPanel header="Auth">
+            <AuthComponent auth={currentRequest.auth}
+              updateAuth={(auth: RequestAuth | null) => updateRequest(updatedRequestModel(currentRequest, { auth }))} />
+          </TabPanel>
+
           <TabPanel header={"Body"}>
             <RequestBodyComp updateRequest={updateRequest} request={currentRequest} environment={currentEnvironment} />
           </TabPanel>
@@ -1,12 +1,14 @@
 import { Button } from "primereact/button"
 import { Checkbox } from "primereact/checkbox"
+import { Dropdown } from "primereact/dropdown"
 import { InputText } from "primereact/inputtext"
 import { useEffect, useState } from "react"
-import { Collection, RequestModel, RequestSettings, SaveResponse } from "../bindings"
+import { ClientSettings, Collection, RequestModel, RequestSettings, SaveResponse } from "../bindings"
 import { catchError } from "../common/errorhandling"
-import { updatedRequestModel } from "../model/model"
+import { DEFAULT_HTTP_VERSION, updatedRequestModel } from "../model/model"
 import { backend } from "../rpc"
 import { HelpTooltip } from "./HelpTooltip"
+import { ClientSettingsComponent } from "./ClientSettingsComponent"
 
 interface ComponentProps {
   request: RequestModel
@@ -14,6 +16,14 @@ interface ComponentProps {
   collection: Collection
 }
 
+const HTTP_VERSIONS: { label: string, value: string }[] = [
+  { label: "Default", value: "default" },
+  { label: "HTTP/1.0", value: "1.0" },
+  { label: "HTTP/1.1", value: "1.1" },
+  { label: "HTTP/2", value: "2.0" },
+  { label: "HTTP/3", value: "3.0" },
+];
+
 export function RequestSettingsComponent(props: ComponentProps) {
 
   const [responseFilepathValid, setResponseFilepathValid] = useState<boolean>(true);
@@ -87,6 +97,18 @@ export function RequestSettingsComponent(props: ComponentProps) {
 
   }
 
+  const httpVersionValue = props.request.http_version.is_replaced ? "default"
+    : `${props.request.http_version.value.major}.${props.request.http_version.value.minor}`;
+
+  const updateHttpVersion = (value: string) => {
+    let http_version = { value: DEFAULT_HTTP_VERSION, is_replaced: true };
+    if (value !== "default") {
+      let [major, minor] = value.split(".").map(Number);
+      http_version = { value: { major, minor }, is_replaced: false };
+    }
+    props.updateRequest(updatedRequestModel(props.request, { http_version }));
+  }
+
   const selectResponseFilePath = () => {
     backend.getResponseFilepath(props.request.rest_file_path, (result: string) => {
       updateResponseFilepath(result);
@@ -122,6 +144,13 @@ export function RequestSettingsComponent(props: ComponentProps) {
         </div>
       </div>
 
+      <div style={{ marginTop: '10px', display: 'flex', alignItems: 'center' }}>
+        <label htmlFor="http_version" style={{ width: '120px', textAlign: 'start' }}>HTTP version</label>
+        <Dropdown inputId="http_version" value={httpVersionValue} options={HTTP_VERSIONS}
+          onChange={(e) => updateHttpVersion(e.value)} />
+        <HelpTooltip style={{ marginLeft: '20px' }} text="The version is written into the request line. Plain http requests with HTTP/2 are sent with prior knowledge (h2c). If the server responds with a lower version a warning is shown with the result." />
+      </div>
+
       <div style={{ display: 'flex', flexDirection: 'column', alignItems: 'start', marginTop: '30px' }}>
         <h3>Save Response</h3>
         <p style={{ marginTop: '20px', textAlign: 'left' }}>Choose if you want to save the response of a request to a file</p>
@@ -153,6 +182,12 @@ export function RequestSettingsComponent(props: ComponentProps) {
           </div>
         }
       </div>
+
+      <div style={{ display: 'flex', flexDirection: 'column', alignItems: 'start', marginTop: '30px' }}>
+        <h3>Client Settings</h3>
+        <ClientSettingsComponent settings={props.request.client_settings}
+          updateSettings={(client_settings: ClientSettings) => props.updateRequest(updatedRequestModel(props.request, { client_settings }))} />
+      </div>
     </div>
 
   )
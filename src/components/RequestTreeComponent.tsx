@@ -20,11 +20,14 @@ import { RequestItemAsButton } from "./RequestItemAsButton";
 import { create } from "react-modal-promise";
 import { CreateGroupModal } from "./modals/CreateGroupModal";
 import { useLocation, useNavigate } from "react-router";
-import { Collection, RequestTreeNode, RequestTree, RequestModel, ImportWarning, DragAndDropResult } from '../bindings';
+import { Collection, RequestTreeNode, RequestTree, RequestModel, ImportWarning, DragAndDropResult, Environment, RunCollectionResult } from '../bindings';
 import { backend } from '../rpc';
 import { getDefaultGroupName } from "../common/common";
 import { confirmPopup } from "primereact/confirmpopup";
 import { EditGroupNameModal } from "./modals/EditGroupNameModal";
+import { RunCollectionModal } from "./modals/RunCollectionModal";
+import { GroupAuthModal } from "./modals/GroupAuthModal";
+import { mergeCurrentValues } from "../model/environment";
 
 const updateRequestTree = useRequestModelStore.getState().updateRequestTree;
 const setCurrentRequest = useRequestModelStore.getState().setCurrentRequest;
@@ -286,10 +289,54 @@ export function RequestTreeComponent(props: ComponentProps) {
   }
 
 
+  const openRunGroupModal = (node: PrimeNode, dataFile?: string) => {
+    const { currentEnvironment, environments, setCurrentEnvironment, setEnvironments } = useRequestModelStore.getState();
+    const modalPromise = create(({ onResolve, onReject, isOpen }) => {
+      return <RunCollectionModal collection={props.collection} groupPath={node.groupNode?.filepath} groupName={node.label as string}
+        environment={currentEnvironment} dataFile={dataFile} isOpen={isOpen} onResolve={onResolve} onReject={() => onReject()} />
+    });
+    modalPromise().then((result?: RunCollectionResult) => {
+      if (result?.environment && currentEnvironment) {
+        let newEnvironment = mergeCurrentValues(currentEnvironment, result.environment);
+        setCurrentEnvironment(newEnvironment);
+        setEnvironments(environments.map((environment: Environment) => environment.name === newEnvironment.name ? newEnvironment : environment));
+      }
+    }).catch(catchError);
+  }
+
+  const openGroupAuthModal = (node: PrimeNode) => {
+    const modalPromise = create(({ onResolve, onReject, isOpen }) => {
+      return <GroupAuthModal collection={props.collection} groupPath={node.groupNode?.filepath as string} groupName={node.label as string}
+        isOpen={isOpen} onResolve={onResolve} onReject={() => onReject()} />
+    });
+    modalPromise().then(() => {
+      toast.showSuccess(`Saved auth of group '${node.label}'`, "");
+    }).catch(() => { });
+  }
+
   const GroupActions = ({ node, toast }: { node: PrimeNode, toast: ToastContext }) => {
     const { closeDropdown } = useContext(ActionDropdownContext);
     return (
       <>
+        <Button icon={'pi pi-play'} className={'p-button p-button-text'}
+          label={"Run Group"}
+          onClick={() => {
+            openRunGroupModal(node);
+            closeDropdown();
+          }} />
+        <Button icon={'pi pi-table'} className={'p-button p-button-text'}
+          label={"Run Group With Data File"}
+          tooltip={"Run the requests once for each row of a csv or json file"}
+          onClick={() => {
+            closeDropdown();
+            backend.selectFile((dataFile: string) => openRunGroupModal(node, dataFile));
+          }} />
+        <Button icon={'pi pi-lock'} className={'p-button p-button-text'}
+          label={"Edit Auth"}
+          onClick={() => {
+            openGroupAuthModal(node);
+            closeDropdown();
+          }} />
         <Button icon={'pi pi-plus'} className={'p-button p-button-text'}
           label={"Create Request"}
           onClick={() => {
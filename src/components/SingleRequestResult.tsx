@@ -9,6 +9,11 @@ import { CopyToClipboard } from "./CopyToClipboard";
 import { backend } from "../rpc";
 import { useMemo } from "react";
 import { InputText } from "primereact/inputtext";
+import { TestResults } from "./TestResults";
+import { TlsInfoComponent } from "./TlsInfoComponent";
+import { TimingsComponent } from "./TimingsComponent";
+import { ExchangeDetailsComponent } from "./ExchangeDetailsComponent";
+import { BinaryResult } from "./BinaryResult";
 
 interface ComponentProps {
   requestResult: RequestResult,
@@ -32,13 +37,21 @@ export function SingleRequestResult(props: ComponentProps) {
         {props.requestResult && props.requestResult.status_code && <>
           <StatusCodeTag statusCode={props.requestResult.status_code} style={{ marginRight: '5px' }} />
 
+          <Tag value={props.requestResult.http_version} style={{ maxHeight: '25px', marginRight: '5px', backgroundColor: 'lightgray' }} />
+
           <Tag value={props.requestResult.content_type} style={{ maxHeight: '25px', marginRight: '5px' }} />
 
+          {props.requestResult.charset &&
+            <Tag value={props.requestResult.charset} style={{ maxHeight: '25px', marginRight: '5px', backgroundColor: 'lightgray' }} />
+          }
+
           <Tag value={timeInMs}
             className={".result-time"}
             style={{ maxHeight: '25px', marginRight: '5px', backgroundColor: 'lightgray' }} />
 
-          <Tag value={`${props.requestResult.total_result_size}B`}
+          <Tag value={`${props.requestResult.total_result_size}B`
+            + (props.requestResult.raw_result_size && props.requestResult.raw_result_size !== props.requestResult.total_result_size
+              ? ` (${props.requestResult.raw_result_size}B compressed)` : "")}
             style={{ maxHeight: '25px', marginRight: '10px', backgroundColor: 'lightgray' }} />
         </>}
         <CopyToClipboard value={props.requestResult.result} />
@@ -57,11 +70,25 @@ export function SingleRequestResult(props: ComponentProps) {
             onClick={() => backend.openFolderNative(props.requestResult?.result_file_folder ?? '')} />
         </div>
       }
-      <SyntaxHighlighter contentEditable={true} className={"resultArea fade-in"}
-        language={props.requestResult.content_type == null ? undefined : getHighlightContentType(props.requestResult.content_type)}
-        style={dracula}>
-        {props.requestResult.result}
-      </SyntaxHighlighter>
+      {props.requestResult.warnings.map((warning: string, index: number) => {
+        return <div key={index} style={{ display: 'flex', alignItems: 'center', marginTop: '5px' }}>
+          <i className="pi pi-exclamation-triangle" style={{ color: 'orange', marginRight: '5px' }}></i>
+          <span>{warning}</span>
+        </div>
+      })}
+      <TestResults tests={props.requestResult.tests ?? []} style={{ marginTop: '10px', marginBottom: '10px' }} />
+      <TlsInfoComponent tls={props.requestResult.tls} style={{ marginBottom: '10px' }} />
+      <TimingsComponent timings={props.requestResult.timings ?? []} style={{ marginBottom: '10px' }} />
+      <ExchangeDetailsComponent requestResult={props.requestResult} style={{ marginBottom: '10px' }} />
+      {props.requestResult.binary ?
+        <BinaryResult payload={props.requestResult.binary} />
+        :
+        <SyntaxHighlighter contentEditable={true} className={"resultArea fade-in"}
+          language={props.requestResult.content_type == null ? undefined : getHighlightContentType(props.requestResult.content_type)}
+          style={dracula}>
+          {props.requestResult.result}
+        </SyntaxHighlighter>
+      }
     </div>
   )
 }
@@ -0,0 +1,29 @@
+import { Tag } from "primereact/tag";
+import { TestResult } from "../bindings";
+
+export interface ComponentProps {
+  tests: TestResult[],
+  style?: any
+}
+
+export function TestResults(props: ComponentProps) {
+  if (props.tests.length === 0) {
+    return <></>
+  }
+
+  const failed = props.tests.filter((test: TestResult) => !test.passed).length;
+  const summary = failed === 0 ? `All ${props.tests.length} tests passed` : `${failed} of ${props.tests.length} tests failed`;
+
+  return (
+    <div style={{ ...(props.style ?? {}), display: 'flex', flexDirection: 'column', alignItems: 'flex-start' }}>
+      <Tag value={summary} severity={failed === 0 ? "success" : "danger"} style={{ maxHeight: '25px', marginBottom: '5px' }} />
+      {props.tests.map((test: TestResult, index: number) => {
+        return <div key={index} style={{ display: 'flex', alignItems: 'center', marginTop: '3px' }}>
+          <i className={test.passed ? "pi pi-check" : "pi pi-times"} style={{ color: test.passed ? 'green' : 'red', marginRight: '5px' }}></i>
+          <span>{test.name}</span>
+          {test.message && <span style={{ marginLeft: '10px', color: 'gray' }}>{test.message}</span>}
+        </div>
+      })}
+    </div>
+  )
+}
@@ -0,0 +1,70 @@
+import { Accordion, AccordionTab } from "primereact/accordion";
+import { CallTimings } from "../bindings";
+
+export interface ComponentProps {
+  timings: CallTimings[],
+  style?: any
+}
+
+const formatMs = (seconds: number) => `${Math.round(seconds * 1000)} ms`;
+
+const formatBytes = (bytes: number) => {
+  if (bytes < 1024) {
+    return `${bytes} B`;
+  }
+  if (bytes < 1024 * 1024) {
+    return `${(bytes / 1024).toFixed(1)} KB`;
+  }
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+}
+
+// curl reports the durations from the start of the call, the phases are the differences between them
+const phases = (timings: CallTimings): { label: string, duration: number }[] => {
+  const tlsDone = timings.app_connect > 0 ? timings.app_connect : timings.connect;
+  return [
+    { label: "DNS lookup", duration: timings.name_lookup },
+    { label: "TCP connect", duration: timings.connect - timings.name_lookup },
+    { label: "TLS handshake", duration: timings.app_connect > 0 ? timings.app_connect - timings.connect : 0 },
+    { label: "Request sent", duration: timings.pre_transfer - tlsDone },
+    { label: "Waiting (TTFB)", duration: timings.start_transfer - timings.pre_transfer },
+    { label: "Content download", duration: timings.total - timings.start_transfer },
+  ].map((phase) => ({ ...phase, duration: Math.max(phase.duration, 0) }));
+}
+
+export function TimingsComponent(props: ComponentProps) {
+  if (props.timings.length === 0) {
+    return <></>
+  }
+
+  return (
+    <Accordion style={props.style} className={"p-accordion-thin"}>
+      <AccordionTab header={
+        <div>
+          <i className="pi pi-clock mr-2"></i>
+          <span className="vertical-align-middle">Timings{props.timings.length > 1 ? ` (${props.timings.length} calls)` : ""}</span>
+        </div>
+      }>
+        {props.timings.map((timings: CallTimings, index: number) => {
+          return <div key={index} style={{ display: 'flex', flexDirection: 'column', alignItems: 'flex-start', marginBottom: '15px' }}>
+            <span style={{ fontWeight: 'bold', marginBottom: '5px' }}>{timings.status_code} {timings.url}</span>
+            {phases(timings).map((phase) => {
+              const width = timings.total > 0 ? (phase.duration / timings.total) * 100 : 0;
+              return <div key={phase.label} style={{ display: 'flex', alignItems: 'center', width: '100%', marginTop: '2px' }}>
+                <span style={{ width: '150px', textAlign: 'start' }}>{phase.label}</span>
+                <div style={{ flexGrow: 1, marginRight: '10px' }}>
+                  <div style={{ width: `${width}%`, minWidth: '1px', height: '10px', backgroundColor: 'var(--primary-color)' }}></div>
+                </div>
+                <span style={{ width: '80px', textAlign: 'end' }}>{formatMs(phase.duration)}</span>
+              </div>
+            })}
+            <div style={{ display: 'flex', justifyContent: 'space-between', width: '100%', marginTop: '5px', color: 'gray' }}>
+              <span>Total {formatMs(timings.total)}</span>
+              <span>Upload {formatBytes(timings.size_upload)} ({formatBytes(timings.speed_upload)}/s)</span>
+              <span>Download {formatBytes(timings.size_download)} ({formatBytes(timings.speed_download)}/s)</span>
+            </div>
+          </div>
+        })}
+      </AccordionTab>
+    </Accordion>
+  )
+}
@@ -0,0 +1,37 @@
+import { Accordion, AccordionTab } from "primereact/accordion";
+import { CertificateInfo, TlsInfo } from "../bindings";
+
+export interface ComponentProps {
+  tls: TlsInfo | null,
+  style?: any
+}
+
+const formatDate = (date: string) => new Date(date).toLocaleString();
+
+export function TlsInfoComponent(props: ComponentProps) {
+  if (!props.tls) {
+    return <></>
+  }
+
+  const header = [props.tls.version, props.tls.cipher].filter((value) => value !== null).join(" / ");
+
+  return (
+    <Accordion style={props.style} className={"p-accordion-thin"}>
+      <AccordionTab header={
+        <div>
+          <i className="pi pi-lock mr-2"></i>
+          <span className="vertical-align-middle">{header === "" ? "TLS" : header}</span>
+        </div>
+      }>
+        {props.tls.certificates.map((certificate: CertificateInfo, index: number) => {
+          return <div key={index} style={{ display: 'flex', flexDirection: 'column', alignItems: 'flex-start', marginBottom: '10px' }}>
+            <span style={{ fontWeight: 'bold' }}>{certificate.subject}</span>
+            <span>Issuer: {certificate.issuer}</span>
+            <span>Valid: {formatDate(certificate.start_date)} - {formatDate(certificate.expire_date)}</span>
+            <span style={{ color: 'gray' }}>Serial number: {certificate.serial_number}</span>
+          </div>
+        })}
+      </AccordionTab>
+    </Accordion>
+  )
+}
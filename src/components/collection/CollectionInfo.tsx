@@ -9,6 +9,7 @@ import { useLocation, useNavigate } from "react-router";
 import { Collection, Environment } from '../../bindings';
 import { environmentsToOptions, envDropdownStyle } from "../../model/environment";
 import { newWorkspace } from '../../model/model';
+import { openClientSettingsModal, openHistoryModal, openTlsProfilesModal } from "../../common/modal";
 
 export interface ComponentProps {
   collection: Collection
@@ -103,6 +104,9 @@ export function CollectionInfo(props: ComponentProps) {
         </div>
 
         <Button onClick={navigateToCookieJar} style={{ marginTop: '20px' }} raised={true} text={true} icon={"pi pi-circle-off"} label="Cookie Jar" />
+        <Button onClick={() => openClientSettingsModal(props.collection)} style={{ marginTop: '10px' }} raised={true} text={true} icon={"pi pi-sliders-h"} label="Client Settings" />
+        <Button onClick={() => openTlsProfilesModal(props.collection)} style={{ marginTop: '10px' }} raised={true} text={true} icon={"pi pi-shield"} label="TLS Profiles" />
+        <Button onClick={() => openHistoryModal(props.collection)} style={{ marginTop: '10px' }} raised={true} text={true} icon={"pi pi-history"} label="History" />
 
 
         <div style={{ marginTop: '10px', display: 'flex', flexDirection: 'column', alignItems: 'flex-start', width: '100%' }}>
@@ -43,6 +43,9 @@ export function CollectionMenuView(props: ComponentProps) {
           toast.showError(`Error loading request: '${key}'`, value.toString());
         })
       }
+      result.warnings.forEach((warning: string) => {
+        toast.showWarn('', warning, 30000);
+      });
       updateRequestTree(result.request_tree);
       setInitFinished(true);
     }).catch(catchError);
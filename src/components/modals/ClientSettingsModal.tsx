@@ -0,0 +1,50 @@
+import { Dialog } from "primereact/dialog";
+import { Button } from "primereact/button";
+import { useEffect, useState } from "react";
+import { ClientSettings, Collection } from "../../bindings";
+import { backend } from "../../rpc";
+import { catchError } from "../../common/errorhandling";
+import { ClientSettingsComponent } from "../ClientSettingsComponent";
+
+interface ComponentProps {
+  isOpen: boolean
+  onResolve: () => void
+  onReject: () => void
+  collection: Collection
+}
+
+export function ClientSettingsModal(props: ComponentProps) {
+  const [settings, setSettings] = useState<ClientSettings | undefined>(undefined);
+
+  useEffect(() => {
+    backend.getClientSettings(props.collection).then((settings: ClientSettings) => {
+      setSettings(settings);
+    }).catch(catchError);
+  }, []);
+
+  const save = () => {
+    if (!settings) {
+      return;
+    }
+    backend.saveClientSettings(props.collection, settings).then(() => {
+      props.onResolve();
+    }).catch(catchError);
+  }
+
+  return (
+    <Dialog header={`Client Settings of '${props.collection.name}'`} visible={props.isOpen} dismissableMask={false}
+      style={{ width: '50vw' }}
+      onHide={() => props.onReject()}
+      footer={
+        <div>
+          <Button label="Cancel" icon="pi pi-times" className={'p-button-secondary p-button-text'}
+            onClick={() => props.onReject()} />
+          <Button label="Save" icon="pi pi-check" disabled={settings === undefined} onClick={save} />
+        </div>
+      }>
+      {settings &&
+        <ClientSettingsComponent settings={settings} updateSettings={setSettings} />
+      }
+    </Dialog>
+  )
+}
@@ -0,0 +1,52 @@
+import { Dialog } from "primereact/dialog";
+import { Button } from "primereact/button";
+import { useEffect, useState } from "react";
+import { Collection, RequestAuth } from "../../bindings";
+import { backend } from "../../rpc";
+import { catchError } from "../../common/errorhandling";
+import { AuthComponent } from "../AuthComponent";
+
+interface ComponentProps {
+  isOpen: boolean
+  onResolve: () => void
+  onReject: () => void
+  collection: Collection
+  groupPath: string
+  groupName: string
+}
+
+export function GroupAuthModal(props: ComponentProps) {
+  const [auth, setAuth] = useState<RequestAuth | null>(null);
+  const [loaded, setLoaded] = useState<boolean>(false);
+
+  useEffect(() => {
+    backend.getGroupAuth(props.collection, props.groupPath).then((auth: RequestAuth | null) => {
+      setAuth(auth);
+      setLoaded(true);
+    }).catch(catchError);
+  }, []);
+
+  const save = () => {
+    backend.saveGroupAuth(props.collection, props.groupPath, auth).then(() => {
+      props.onResolve();
+    }).catch(catchError);
+  }
+
+  return (
+    <Dialog header={`Auth of '${props.groupName}'`} visible={props.isOpen} dismissableMask={false}
+      style={{ width: '50vw' }}
+      onHide={() => props.onReject()}
+      footer={
+        <div>
+          <Button label="Cancel" icon="pi pi-times" className={'p-button-secondary p-button-text'}
+            onClick={() => props.onReject()} />
+          <Button label="Save" icon="pi pi-check" disabled={!loaded} onClick={save} />
+        </div>
+      }>
+      <p style={{ marginBottom: '20px', textAlign: 'start' }}>Requests within this group use this auth unless they define their own.</p>
+      {loaded &&
+        <AuthComponent auth={auth} updateAuth={setAuth} />
+      }
+    </Dialog>
+  )
+}
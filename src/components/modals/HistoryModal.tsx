@@ -0,0 +1,219 @@
+import { Dialog } from "primereact/dialog";
+import { Button } from "primereact/button";
+import { Checkbox } from "primereact/checkbox";
+import { InputText } from "primereact/inputtext";
+import { DataTable } from "primereact/datatable";
+import { Column } from "primereact/column";
+import { confirmPopup } from "primereact/confirmpopup";
+import { useContext, useEffect, useState } from "react";
+import { Collection, Environment, HistoryEntry, HistoryEntrySummary, HistorySettings, RequestResult } from "../../bindings";
+import { backend } from "../../rpc";
+import { catchError } from "../../common/errorhandling";
+import { useRequestModelStore } from "../../stores/requestStore";
+import { mergeCurrentValues, toBackendEnvironment } from "../../model/environment";
+import { ToastContext } from "../../App";
+import { SingleRequestResult } from "../SingleRequestResult";
+import { StatusCodeTag } from "../StatusCodeTag";
+import { HelpTooltip } from "../HelpTooltip";
+
+interface ComponentProps {
+  isOpen: boolean
+  onResolve: () => void
+  onReject: () => void
+  collection: Collection
+}
+
+type SettingsNumberKey = "max_entries" | "max_age_days" | "max_body_size"
+
+const labelStyle = { width: '200px', textAlign: 'start' as const };
+const rowStyle = { display: 'flex', alignItems: 'center', marginTop: '10px' };
+
+// Past executions of the requests of a collection, they can be searched, re-opened and run again
+export function HistoryModal(props: ComponentProps) {
+  const toast = useContext(ToastContext);
+  const currentEnvironment = useRequestModelStore((state) => state.currentEnvironment);
+  const setCurrentEnvironment = useRequestModelStore((state) => state.setCurrentEnvironment);
+  const environments = useRequestModelStore((state) => state.environments);
+  const setEnvironments = useRequestModelStore((state) => state.setEnvironments);
+
+  const [search, setSearch] = useState<string>("");
+  const [entries, setEntries] = useState<HistoryEntrySummary[]>([]);
+  const [selectedEntry, setSelectedEntry] = useState<HistoryEntry | undefined>(undefined);
+  const [settings, setSettings] = useState<HistorySettings | undefined>(undefined);
+  const [showSettings, setShowSettings] = useState<boolean>(false);
+  const [isRunning, setIsRunning] = useState<boolean>(false);
+
+  const loadEntries = () => {
+    backend.listHistory(props.collection, { search: search === "" ? null : search, rest_file_path: null, request_name: null, limit: null })
+      .then(setEntries)
+      .catch(catchError);
+  }
+
+  useEffect(() => {
+    loadEntries();
+  }, [search]);
+
+  useEffect(() => {
+    backend.getHistorySettings(props.collection).then(setSettings).catch(catchError);
+  }, []);
+
+  const openEntry = (summary: HistoryEntrySummary) => {
+    backend.getHistoryEntry(props.collection, summary.id).then(setSelectedEntry).catch(catchError);
+  }
+
+  const deleteEntry = (entryId: string) => {
+    backend.deleteHistoryEntry(props.collection, entryId).then(() => {
+      if (selectedEntry?.id === entryId) {
+        setSelectedEntry(undefined);
+      }
+      loadEntries();
+    }).catch(catchError);
+  }
+
+  // the request is run with the currently selected environment, not the one of the entry
+  const rerun = (entry: HistoryEntry) => {
+    setIsRunning(true);
+    const environment: Environment | null = currentEnvironment ? toBackendEnvironment(currentEnvironment) : null;
+    backend.rerunHistoryEntry(props.collection, entry.id, environment).then((result: RequestResult) => {
+      if (result.environment && currentEnvironment) {
+        let newEnvironment = mergeCurrentValues(currentEnvironment, result.environment);
+        setCurrentEnvironment(newEnvironment);
+        setEnvironments(environments.map((environment: Environment) => environment.name === newEnvironment.name ? newEnvironment : environment));
+      }
+      result.warnings.forEach((warning: string) => {
+        toast.showWarn('', warning, 30000);
+      });
+      setSelectedEntry({
+        ...entry,
+        id: result.id,
+        executed_at: new Date().toISOString(),
+        environment_name: currentEnvironment?.name ?? null,
+        result: result,
+        truncated: false
+      });
+      loadEntries();
+    }).catch(catchError).finally(() => setIsRunning(false));
+  }
+
+  const confirmClearHistory = (event: any) => {
+    confirmPopup({
+      target: event.currentTarget,
+      message: 'Are you sure you want to remove all entries of the history?',
+      icon: 'pi pi-exclamation-triangle',
+      accept: () => {
+        backend.clearHistory(props.collection).then(() => {
+          setSelectedEntry(undefined);
+          loadEntries();
+        }).catch(catchError);
+      }
+    });
+  }
+
+  const saveSettings = () => {
+    if (!settings) {
+      return;
+    }
+    backend.saveHistorySettings(props.collection, settings).then(() => {
+      setShowSettings(false);
+      loadEntries();
+      toast.showSuccess("History settings saved", "");
+    }).catch(catchError);
+  }
+
+  const settingsNumberField = (key: SettingsNumberKey, label: string) => {
+    if (!settings) {
+      return <></>
+    }
+    return (
+      <div style={rowStyle}>
+        <label htmlFor={key} style={labelStyle}>{label}</label>
+        <InputText id={key} keyfilter="pint" style={{ width: '150px' }} value={settings[key].toString()}
+          onChange={(e) => setSettings({ ...settings, [key]: e.target.value === "" ? 0 : parseInt(e.target.value) })} />
+      </div>
+    )
+  }
+
+  const settingsForm = () => {
+    if (!settings) {
+      return <></>
+    }
+    return (
+      <div style={{ display: 'flex', flexDirection: 'column', alignItems: 'flex-start', marginBottom: '20px' }}>
+        <div style={rowStyle}>
+          <label htmlFor="history_enabled" style={labelStyle}>Record history</label>
+          <Checkbox inputId="history_enabled" checked={settings.enabled}
+            onChange={(e) => setSettings({ ...settings, enabled: e.checked ?? false })} />
+        </div>
+        {settingsNumberField("max_entries", "Max entries")}
+        {settingsNumberField("max_age_days", "Max age (days)")}
+        {settingsNumberField("max_body_size", "Max body size (bytes)")}
+        <Button label="Save Settings" icon="pi pi-check" style={{ marginTop: '10px' }} onClick={saveSettings} />
+      </div>
+    )
+  }
+
+  const entryDetails = (entry: HistoryEntry) => {
+    return (
+      <div style={{ display: 'flex', flexDirection: 'column', alignItems: 'stretch' }}>
+        <div style={{ display: 'flex', alignItems: 'center', marginBottom: '10px' }}>
+          <Button icon="pi pi-arrow-left" label="Back" className={"p-button-text"} onClick={() => setSelectedEntry(undefined)} />
+          <h3 style={{ margin: '0 10px' }}>{entry.request.name}</h3>
+          <span>{new Date(entry.executed_at).toLocaleString()}</span>
+          {entry.environment_name && <span style={{ marginLeft: '10px' }}>{`Environment: ${entry.environment_name}`}</span>}
+          <Button icon="pi pi-replay" label="Run Again" loading={isRunning} style={{ marginLeft: 'auto' }}
+            onClick={() => rerun(entry)} />
+        </div>
+        {entry.truncated &&
+          <p style={{ textAlign: 'start' }}>The body has been cut off as it exceeds the maximum body size of the history.</p>
+        }
+        <SingleRequestResult requestResult={entry.result} clearResult={() => deleteEntry(entry.id)} />
+      </div>
+    )
+  }
+
+  const entryList = () => {
+    return (
+      <DataTable value={entries} selectionMode="single" dataKey="id" size="small" scrollable scrollHeight="50vh"
+        onRowClick={(e) => openEntry(e.data as HistoryEntrySummary)}
+        emptyMessage={"No requests have been recorded yet"}>
+        <Column header="Executed" body={(summary: HistoryEntrySummary) => new Date(summary.executed_at).toLocaleString()} />
+        <Column field="request_name" header="Request" />
+        <Column field="method" header="Method" />
+        <Column field="url" header="Url" style={{ wordBreak: 'break-all' }} />
+        <Column header="Status" body={(summary: HistoryEntrySummary) => <StatusCodeTag statusCode={summary.status_code} />} />
+        <Column header="Time" body={(summary: HistoryEntrySummary) => `${Math.floor(summary.total_time * 1000)} ms`} />
+        <Column field="environment_name" header="Environment" />
+        <Column body={(summary: HistoryEntrySummary) =>
+          <Button icon="pi pi-trash" className={"p-button-text"} tooltip={"Remove entry"}
+            onClick={(e) => { e.stopPropagation(); deleteEntry(summary.id); }} />
+        } />
+      </DataTable>
+    )
+  }
+
+  return (
+    <Dialog header={`History of '${props.collection.name}'`} visible={props.isOpen} dismissableMask={true}
+      style={{ width: '80vw' }}
+      onHide={() => props.onResolve()}
+      footer={
+        <div>
+          <Button label="Clear History" icon="pi pi-trash" className={'p-button-danger p-button-text'}
+            onClick={confirmClearHistory} />
+          <Button label="Close" icon="pi pi-times" onClick={() => props.onResolve()} />
+        </div>
+      }>
+      <div style={{ display: 'flex', alignItems: 'center', marginBottom: '20px' }}>
+        <span className="p-input-icon-left">
+          <i className="pi pi-search" />
+          <InputText style={{ width: '400px' }} value={search} placeholder={"Search by name, url, status or environment"}
+            onChange={(e) => setSearch(e.target.value)} />
+        </span>
+        <HelpTooltip style={{ marginLeft: '20px' }} text="Every request run is recorded with secrets and credential headers redacted. The auth of a request is not recorded, running it again uses its current auth. The history is only stored on this machine and not shared with the collection." />
+        <Button icon="pi pi-cog" label="Settings" className={"p-button-text"} style={{ marginLeft: 'auto' }}
+          onClick={() => setShowSettings(!showSettings)} />
+      </div>
+      {showSettings && settingsForm()}
+      {selectedEntry ? entryDetails(selectedEntry) : entryList()}
+    </Dialog>
+  )
+}
@@ -0,0 +1,90 @@
+import { Dialog } from "primereact/dialog";
+import { Button } from "primereact/button";
+import { ProgressBar } from "primereact/progressbar";
+import { useEffect, useState } from "react";
+import { Collection, Environment, RunCollectionIteration, RunCollectionProgress, RunCollectionResult, TestResult } from "../../bindings";
+import { backend } from "../../rpc";
+import { catchError } from "../../common/errorhandling";
+import { StatusCodeTag } from "../StatusCodeTag";
+
+interface ComponentProps {
+  isOpen: boolean
+  onResolve: (result?: RunCollectionResult) => void
+  onReject: () => void
+  collection: Collection
+  // the whole collection is run if no group is given
+  groupPath?: string
+  groupName: string
+  environment?: Environment
+  // csv or json file, the requests are run once per row
+  dataFile?: string
+}
+
+const isSuccess = (progress: RunCollectionProgress) => {
+  return progress.error === null && (progress.result?.tests ?? []).every((test: TestResult) => test.passed);
+}
+
+export function RunCollectionModal(props: ComponentProps) {
+  const [runs, setRuns] = useState<RunCollectionProgress[]>([]);
+  const [total, setTotal] = useState<number | undefined>(undefined);
+  const [result, setResult] = useState<RunCollectionResult | undefined>(undefined);
+
+  useEffect(() => {
+    backend.runCollection({
+      collection: props.collection,
+      group_path: props.groupPath ?? null,
+      environment: props.environment ?? null,
+      data_file: props.dataFile ?? null
+    }, (progress: RunCollectionProgress) => {
+      setTotal(progress.total * progress.total_iterations);
+      setRuns((runs: RunCollectionProgress[]) => [...runs, progress]);
+    }).then((result: RunCollectionResult) => {
+      setRuns(result.iterations.flatMap((iteration: RunCollectionIteration) => iteration.runs));
+      setResult(result);
+    }).catch(catchError);
+  }, []);
+
+  const progressValue = total ? Math.floor(runs.length / total * 100) : 0;
+  const failed = runs.filter((run: RunCollectionProgress) => !isSuccess(run)).length;
+
+  return (
+    <Dialog header={`Run '${props.groupName}'`} visible={props.isOpen} dismissableMask={false}
+      style={{ width: '50vw' }}
+      onHide={() => props.onResolve(result)}
+      footer={
+        <div>
+          <Button label="Close" icon="pi pi-times" className={'p-button-secondary p-button-text'}
+            onClick={() => props.onResolve(result)} />
+        </div>
+      }>
+      <div style={{ display: 'flex', flexDirection: 'column', alignItems: 'flex-start', marginTop: '20px', marginBottom: '30px' }}>
+        <ProgressBar value={result ? 100 : progressValue} style={{ width: '100%', marginBottom: '20px' }} />
+        {result &&
+          <p style={{ marginBottom: '20px' }}>{`${runs.length} requests, ${runs.length - failed} passed, ${failed} failed`}</p>
+        }
+        {runs.map((run: RunCollectionProgress) => {
+          return <div key={`${run.iteration}-${run.index}`} style={{ display: 'flex', flexDirection: 'column', alignItems: 'flex-start' }}>
+            {(run.total_iterations > 1 && run.index === 0) &&
+              <h4 style={{ marginTop: '15px' }}>{`Iteration ${run.iteration + 1}`}</h4>
+            }
+            <RunEntry run={run} />
+          </div>
+        })}
+      </div>
+    </Dialog>
+  )
+}
+
+function RunEntry({ run }: { run: RunCollectionProgress }) {
+  return (
+    <div style={{ display: 'flex', alignItems: 'center', marginTop: '5px' }}>
+      <i className={isSuccess(run) ? "pi pi-check" : "pi pi-times"} style={{ color: isSuccess(run) ? 'green' : 'red', marginRight: '10px' }}></i>
+      <span style={{ marginRight: '10px' }}>{run.request_name}</span>
+      {run.result && <StatusCodeTag statusCode={run.result.status_code} style={{ marginRight: '10px' }} />}
+      {run.error && <span style={{ color: 'gray' }}>{run.error}</span>}
+      {run.result?.tests.filter((test: TestResult) => !test.passed).map((test: TestResult) => {
+        return <span key={test.name} style={{ color: 'gray', marginRight: '5px' }}>{`${test.name}: ${test.message ?? ''}`}</span>
+      })}
+    </div>
+  )
+}
@@ -0,0 +1,126 @@
+import { Dialog } from "primereact/dialog";
+import { Button } from "primereact/button";
+import { Dropdown } from "primereact/dropdown";
+import { InputText } from "primereact/inputtext";
+import { InputTextarea } from "primereact/inputtextarea";
+import { useEffect, useState } from "react";
+import { CertificateFormat, Collection, TlsProfile } from "../../bindings";
+import { backend } from "../../rpc";
+import { catchError } from "../../common/errorhandling";
+import { newTlsProfile } from "../../model/model";
+import { HelpTooltip } from "../HelpTooltip";
+
+interface ComponentProps {
+  isOpen: boolean
+  onResolve: () => void
+  onReject: () => void
+  collection: Collection
+}
+
+const CERTIFICATE_FORMATS: { label: string, value: CertificateFormat }[] = [
+  { label: "PEM", value: "Pem" },
+  { label: "PKCS#12", value: "Pkcs12" },
+];
+
+const labelStyle = { width: '200px', textAlign: 'start' as const };
+const rowStyle = { display: 'flex', alignItems: 'center', marginTop: '10px' };
+
+export function TlsProfilesModal(props: ComponentProps) {
+  const [profiles, setProfiles] = useState<TlsProfile[] | undefined>(undefined);
+
+  useEffect(() => {
+    backend.getTlsProfiles(props.collection).then((profiles: TlsProfile[]) => {
+      setProfiles(profiles);
+    }).catch(catchError);
+  }, []);
+
+  const updateProfile = (index: number, partial: Partial<TlsProfile>) => {
+    setProfiles((profiles ?? []).map((profile: TlsProfile, profileIndex: number) => {
+      return profileIndex === index ? { ...profile, ...partial } : profile;
+    }));
+  }
+
+  const removeProfile = (index: number) => {
+    setProfiles((profiles ?? []).filter((_profile: TlsProfile, profileIndex: number) => profileIndex !== index));
+  }
+
+  const save = () => {
+    if (!profiles) {
+      return;
+    }
+    backend.saveTlsProfiles(props.collection, profiles).then(() => {
+      props.onResolve();
+    }).catch(catchError);
+  }
+
+  const fileField = (index: number, profile: TlsProfile, key: "client_certificate" | "client_key", label: string) => {
+    return (
+      <div style={rowStyle}>
+        <label style={labelStyle}>{label}</label>
+        <InputText style={{ width: '400px' }} value={profile[key] ?? ""}
+          onChange={(e) => updateProfile(index, { [key]: e.target.value === "" ? null : e.target.value })} />
+        <Button icon="pi pi-folder-open" className={"p-button-text"} style={{ marginLeft: '5px' }}
+          onClick={() => backend.selectFile((path: string) => updateProfile(index, { [key]: path }))} />
+      </div>
+    )
+  }
+
+  const listField = (index: number, profile: TlsProfile, key: "ca_bundles" | "pinned_public_keys", label: string, placeholder: string) => {
+    return (
+      <div style={rowStyle}>
+        <label style={labelStyle}>{label}</label>
+        <InputTextarea style={{ width: '400px' }} rows={2} placeholder={placeholder}
+          value={profile[key].join("\n")}
+          onChange={(e) => updateProfile(index, { [key]: e.target.value === "" ? [] : e.target.value.split("\n") })} />
+      </div>
+    )
+  }
+
+  return (
+    <Dialog header={`TLS Profiles of '${props.collection.name}'`} visible={props.isOpen} dismissableMask={false}
+      style={{ width: '60vw' }}
+      onHide={() => props.onReject()}
+      footer={
+        <div>
+          <Button label="Cancel" icon="pi pi-times" className={'p-button-secondary p-button-text'}
+            onClick={() => props.onReject()} />
+          <Button label="Save" icon="pi pi-check" disabled={profiles === undefined} onClick={save} />
+        </div>
+      }>
+      <div style={{ display: 'flex', alignItems: 'center', marginBottom: '20px' }}>
+        <p style={{ textAlign: 'start' }}>Certificates used for all hosts matching a pattern, the first matching profile is used.</p>
+        <HelpTooltip style={{ marginLeft: '20px' }} text="Host patterns may contain wildcards such as '*.internal.example.com' and a port such as 'api.example.com:8443'. The passphrase has to reference a secret of the environment such as '{{passphrase}}', as the profiles are stored within the collection folder." />
+      </div>
+      {profiles?.map((profile: TlsProfile, index: number) => {
+        return (
+          <div key={index} style={{ display: 'flex', flexDirection: 'column', alignItems: 'flex-start', marginBottom: '30px' }}>
+            <div style={{ display: 'flex', alignItems: 'center' }}>
+              <label style={labelStyle}>Host</label>
+              <InputText style={{ width: '400px' }} value={profile.host} placeholder={"*.internal.example.com"}
+                onChange={(e) => updateProfile(index, { host: e.target.value })} />
+              <Button icon="pi pi-trash" className={"p-button-text"} style={{ marginLeft: '5px' }}
+                tooltip={"Remove profile"} onClick={() => removeProfile(index)} />
+            </div>
+            <div style={rowStyle}>
+              <label style={labelStyle}>Certificate format</label>
+              <Dropdown value={profile.client_certificate_format} options={CERTIFICATE_FORMATS}
+                onChange={(e) => updateProfile(index, { client_certificate_format: e.value })} />
+            </div>
+            {fileField(index, profile, "client_certificate", "Client certificate")}
+            {profile.client_certificate_format === "Pem" && fileField(index, profile, "client_key", "Client key")}
+            <div style={rowStyle}>
+              <label style={labelStyle}>Passphrase</label>
+              <InputText style={{ width: '400px' }} value={profile.passphrase ?? ""} placeholder={"{{passphrase}}"}
+                onChange={(e) => updateProfile(index, { passphrase: e.target.value === "" ? null : e.target.value })} />
+            </div>
+            {listField(index, profile, "ca_bundles", "CA bundles", "/path/to/ca.pem")}
+            {listField(index, profile, "pinned_public_keys", "Pinned public keys", "sha256//YhKJKSzoTt2b5FP18fvpHo7fJYqQCjAa3HWY3tvRMwE=")}
+          </div>
+        )
+      })}
+      <Button icon="pi pi-plus" label="Add Profile" className={"p-button-text"}
+        disabled={profiles === undefined}
+        onClick={() => setProfiles([...(profiles ?? []), newTlsProfile(undefined)])} />
+    </Dialog>
+  )
+}
@@ -0,0 +1,57 @@
+import { Dialog } from "primereact/dialog";
+import { Button } from "primereact/button";
+import { Password } from "primereact/password";
+import { useState } from "react";
+import { onCtrlEnter } from "../../common/eventhandling";
+
+
+interface ComponentProps {
+  isOpen: boolean
+  onResolve: (passphrase?: string) => void
+  onReject: () => void
+  // if the vault does not exist yet it is created with the entered passphrase
+  vaultExists: boolean
+}
+
+export function UnlockVaultModal(props: ComponentProps) {
+
+  const [passphrase, setPassphrase] = useState<string>("");
+
+  const resolvePassphrase = () => {
+    if (passphrase === "") {
+      return
+    }
+    props.onResolve(passphrase)
+  }
+
+  return (
+    <Dialog header={props.vaultExists ? "Unlock Vault" : "Create Vault"} visible={props.isOpen} dismissableMask={false}
+      style={{ width: '50vw' }}
+      onHide={props.onResolve}
+      footer={
+        <div>
+          <Button label="Cancel" icon="pi pi-times" className={'p-button-secondary p-button-text'}
+            onClick={() => props.onResolve()} />
+          <Button label={props.vaultExists ? "Unlock" : "Create"} icon="pi pi-lock-open"
+            onClick={resolvePassphrase}
+            disabled={passphrase === ""}
+            style={{ marginLeft: '80px' }} />
+        </div>
+      }>
+      <div style={{ display: 'flex', flexDirection: 'column', alignItems: 'flex-start', marginBottom: '50px', marginTop: '40px' }}>
+        <p style={{ textAlign: 'left', marginBottom: '20px' }}>
+          {props.vaultExists ?
+            "Enter the passphrase of your vault. It stays unlocked until relynx is closed." :
+            "Choose a passphrase for your new vault. The passphrase cannot be recovered, if you forget it the secrets within the vault are lost."}
+        </p>
+        <div style={{ display: 'flex', width: '100%', alignItems: 'center' }}>
+          <h3>Passphrase</h3>
+          <Password autoFocus={true} feedback={!props.vaultExists} toggleMask={true} value={passphrase}
+            onKeyPress={(event: any) => onCtrlEnter(event, resolvePassphrase)}
+            onChange={(e) => setPassphrase(e.target.value)}
+            style={{ marginLeft: '20px', flexBasis: '60%' }} />
+        </div>
+      </div>
+    </Dialog>
+  )
+}
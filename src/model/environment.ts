@@ -10,6 +10,9 @@ export const environmentsToOptions = (environments: Environment[], withNone: boo
   return options;
 }
 
+// values of this environment are inherited by all other environments
+export const SHARED_ENV_NAME = "$shared";
+
 export const envDropdownStyle = {
 }
 
@@ -22,7 +25,9 @@ export function newEnvironmentVariable(): EnvironmentVariable {
     name: "",
     description: null,
     current_value: null,
-    initial_value: ""
+    initial_value: "",
+    source: null,
+    is_json: false
   };
   return variable;
 }
@@ -34,7 +39,52 @@ export function newEnvironmentSecret(): EnvironmentSecret {
     description: null,
     current_value: null,
     initial_value: "",
-    persist_to_file: true
+    persist_to_file: true,
+    source: null,
+    is_json: false
   };
   return secret;
 }
+
+// variables set by response handler scripts are returned with their current value from the backend,
+// globals cleared by a script are missing (set at runtime) or have lost their current value
+export function mergeCurrentValues(environment: Environment, updated: Environment): Environment {
+  let variables = environment.variables.filter((variable: EnvironmentVariable) => {
+    return variable.initial_value !== "" || updated.variables.some((current: EnvironmentVariable) => current.name === variable.name);
+  });
+  updated.variables.forEach((variable: EnvironmentVariable) => {
+    let index = variables.findIndex((current: EnvironmentVariable) => current.name === variable.name);
+    if (index >= 0) {
+      variables[index] = { ...variables[index], current_value: variable.current_value };
+    } else if (variable.current_value !== null) {
+      variables.push(variable);
+    }
+  });
+  return getUpdatedEnvironment(environment, { variables: variables });
+}
+
+// the environment as it is sent to the backend, variables and secrets with duplicated names are
+// filtered out (the first one is kept)
+export function toBackendEnvironment(environment: Environment): Environment {
+  let backendEnvironment = structuredClone(environment);
+
+  let presentVarNames: { [name: string]: boolean } = {};
+  backendEnvironment.variables = backendEnvironment.variables.filter((variable: EnvironmentVariable) => {
+    if (presentVarNames[variable.name]) {
+      return false;
+    } else {
+      presentVarNames[variable.name] = true;
+      return true;
+    }
+  });
+
+  backendEnvironment.secrets = backendEnvironment.secrets.filter((secret: EnvironmentSecret) => {
+    if (presentVarNames[secret.name]) {
+      return false;
+    } else {
+      presentVarNames[secret.name] = true;
+      return true;
+    }
+  });
+  return backendEnvironment;
+}
@@ -1,4 +1,4 @@
-import { Workspace, Collection, RequestModel, Header, HttpVersion, QueryParam, RequestResult, RequestSettings, Environment } from "../bindings";
+import { Workspace, Collection, RequestModel, Header, HttpVersion, QueryParam, RequestResult, RequestSettings, Environment, ClientSettings, TlsProfile } from "../bindings";
 
 // @TODO: check
 export const DEFAULT_HTTP_VERSION: HttpVersion = { major: 1, minor: 1 };
@@ -52,6 +52,45 @@ export function newRequestHeader(partial: undefined | Partial<Header>): Header {
   return header;
 }
 
+export function newClientSettings(partial: undefined | Partial<ClientSettings>): ClientSettings {
+  let clientSettings: ClientSettings = {
+    timeout: null,
+    connect_timeout: null,
+    proxy: null,
+    no_proxy: null,
+    insecure: null,
+    cacert_file: null,
+    client_cert_file: null,
+    client_key_file: null,
+    resolves: null,
+    connects_to: null,
+    max_redirect: null,
+    compressed: null,
+    user_agent: null,
+    certificate_expiry_warning_days: null
+  };
+  if (partial) {
+    clientSettings = { ...clientSettings, ...partial };
+  }
+  return clientSettings;
+}
+
+export function newTlsProfile(partial: undefined | Partial<TlsProfile>): TlsProfile {
+  let tlsProfile: TlsProfile = {
+    host: "",
+    client_certificate: null,
+    client_certificate_format: "Pem",
+    client_key: null,
+    passphrase: null,
+    ca_bundles: [],
+    pinned_public_keys: []
+  };
+  if (partial) {
+    tlsProfile = { ...tlsProfile, ...partial };
+  }
+  return tlsProfile;
+}
+
 // @TODO check defaults here
 export function newRequestSettings(partial: undefined | Partial<RequestSettings>): RequestSettings {
   let requestSettings: RequestSettings = {
@@ -71,6 +110,10 @@ export function newEnvironment(partial: undefined | Partial<Environment>): Envir
     name: "New Environment",
     secrets: [],
     variables: [],
+    extends: null,
+    ssl_configuration: null,
+    auth_configurations: {},
+    vault_loaded: false,
   }
 
   if (partial) {
@@ -2,7 +2,7 @@ import { v4 as uuidv4 } from 'uuid';
 
 import { RequestModel, RequestBody, HttpMethod, Environment, Header, DataSource, Multipart, UrlEncodedParam, QueryParam } from '../bindings';
 import { QueryParams } from '../components/QueryParams';
-import { DEFAULT_HTTP_VERSION, newQueryParam, newRequestHeader, newRequestSettings, newEnvironment } from './model';
+import { DEFAULT_HTTP_VERSION, newQueryParam, newRequestHeader, newRequestSettings, newEnvironment, newClientSettings } from './model';
 
 
 export const HTTP_METHODS = {
@@ -82,7 +82,9 @@ export function newRequestModel(partial: Partial<RequestModel>): RequestModel {
     settings: newRequestSettings(undefined),
     save_response: null,
     pre_request_script: null,
-    response_handler: null
+    response_handler: null,
+    auth: null,
+    client_settings: newClientSettings(undefined)
   }
 
   if (partial) {
@@ -1,6 +1,7 @@
 import { createClient } from '@rspc/client'
 import { TauriTransport } from '@rspc/tauri'
-import { Procedures, Workspace, Collection, AddCollectionsResult, ImportCollectionResult, LoadRequestsResult, RunRequestCommand, RequestResult, RequestModel, SaveRequestCommand, RequestTreeNode, DragAndDropResult, Environment, ValidateGroupNameResult, LicenseData, CookieJar } from './bindings';
+import { Procedures, Workspace, Collection, AddCollectionsResult, ImportCollectionResult, LoadRequestsResult, RunRequestCommand, RequestResult, RequestModel, SaveRequestCommand, RequestTreeNode, DragAndDropResult, Environment, ValidateGroupNameResult, LicenseData, CookieJar, RunCollectionCommand, RunCollectionProgress, RunCollectionResult, VaultStatus, RequestAuth, ClientSettings, TlsProfile, HistoryEntry, HistoryEntrySummary, HistoryQuery, HistorySettings } from './bindings';
+import { listen } from '@tauri-apps/api/event';
 import { catchError } from './common/errorhandling';
 import { CancellationToken } from './model/error';
 
@@ -64,6 +65,22 @@ class Backend {
     return api.query(['save_environments', { collection_path: collection.path, environments: environments }]);
   }
 
+  getVaultStatus(): Promise<VaultStatus> {
+    return api.query(['get_vault_status']);
+  }
+
+  unlockVault(passphrase: string): Promise<null> {
+    return api.mutation(['unlock_vault', { passphrase }]);
+  }
+
+  lockVault(): Promise<null> {
+    return api.mutation(['lock_vault']);
+  }
+
+  clearAuthTokens(): Promise<null> {
+    return api.mutation(['clear_auth_tokens']);
+  }
+
   importPostmanCollection(workspace: Workspace, import_postman_path: string, import_result_path: string): Promise<ImportCollectionResult> {
     return api.query(['import_postman_collection', { workspace, import_postman_path, import_result_path }]);
   }
@@ -92,6 +109,16 @@ class Backend {
     });
   }
 
+  // runs all requests of the collection or group, onProgress is called after each request
+  async runCollection(runCollectionCommand: RunCollectionCommand, onProgress: (progress: RunCollectionProgress) => void): Promise<RunCollectionResult> {
+    const unlisten = await listen<RunCollectionProgress>('run_collection_progress', (event) => onProgress(event.payload));
+    try {
+      return await api.query(['run_collection', runCollectionCommand]);
+    } finally {
+      unlisten();
+    }
+  }
+
   getResponseFilepath(request_path: string, onSelected: (request_path: string) => void) {
     api.query(['get_response_filepath', request_path]).then((result: string | null) => {
       if (result) {
@@ -156,6 +183,59 @@ class Backend {
     return api.query(['hide_group', path]);
   }
 
+  getClientSettings(collection: Collection): Promise<ClientSettings> {
+    return api.query(['get_client_settings', collection]);
+  }
+
+  saveClientSettings(collection: Collection, settings: ClientSettings): Promise<null> {
+    return api.mutation(['save_client_settings', { collection, settings }]);
+  }
+
+  getTlsProfiles(collection: Collection): Promise<TlsProfile[]> {
+    return api.query(['get_tls_profiles', collection]);
+  }
+
+  saveTlsProfiles(collection: Collection, tls_profiles: TlsProfile[]): Promise<null> {
+    return api.mutation(['save_tls_profiles', { collection, tls_profiles }]);
+  }
+
+  listHistory(collection: Collection, query: HistoryQuery): Promise<HistoryEntrySummary[]> {
+    return api.query(['list_history', { collection, query }]);
+  }
+
+  getHistoryEntry(collection: Collection, entry_id: string): Promise<HistoryEntry> {
+    return api.query(['get_history_entry', { collection, entry_id }]);
+  }
+
+  // runs the request of a history entry again with the given environment
+  rerunHistoryEntry(collection: Collection, entry_id: string, environment: Environment | null): Promise<RequestResult> {
+    return api.mutation(['rerun_history_entry', { collection, entry_id, environment }]);
+  }
+
+  deleteHistoryEntry(collection: Collection, entry_id: string): Promise<null> {
+    return api.mutation(['delete_history_entry', { collection, entry_id }]);
+  }
+
+  clearHistory(collection: Collection): Promise<null> {
+    return api.mutation(['clear_history', collection]);
+  }
+
+  getHistorySettings(collection: Collection): Promise<HistorySettings> {
+    return api.query(['get_history_settings', collection]);
+  }
+
+  saveHistorySettings(collection: Collection, settings: HistorySettings): Promise<null> {
+    return api.mutation(['save_history_settings', { collection, settings }]);
+  }
+
+  getGroupAuth(collection: Collection, group_path: string): Promise<RequestAuth | null> {
+    return api.query(['get_group_auth', { collection, group_path }]);
+  }
+
+  saveGroupAuth(collection: Collection, group_path: string, auth: RequestAuth | null): Promise<null> {
+    return api.mutation(['save_group_auth', { collection, group_path, auth }]);
+  }
+
   chooseFileRelativeTo(base_path: string, onSelect: (path: string) => void) {
     api.query(['choose_file_relative_to', { base_path: base_path }]).then((result: string | null) => {
       if (result) {